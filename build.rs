@@ -0,0 +1,63 @@
+//! Convert the builtin word lists into a compact form that needs no CSV
+//! parsing at runtime.  Each `src/<name>.csv` is written to
+//! `$OUT_DIR/<name>.bin` as a sequence of records — each one consisting of a
+//! word, its hint, its category, and its difficulty, separated by
+//! `UNIT_SEP`, and terminated by `RECORD_SEP` — compressed with DEFLATE.
+use std::fs;
+use std::path::PathBuf;
+
+/// The builtin word lists in `src/`, by file stem
+static WORD_LISTS: &[&str] = &["words", "kids", "animals", "countries", "movies", "science"];
+
+/// The character that ends each record
+const RECORD_SEP: char = '\x1E';
+
+/// The character that separates the fields of a record
+const UNIT_SEP: char = '\x1F';
+
+fn main() {
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR should be set"));
+    for name in WORD_LISTS {
+        let src = format!("src/{name}.csv");
+        println!("cargo:rerun-if-changed={src}");
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_path(&src)
+            .expect("opening builtin word list should not fail");
+        let mut table = String::new();
+        for record in reader.records() {
+            let record = record.expect("reading builtin word list should not fail");
+            let word = record.get(0).unwrap_or_default();
+            assert!(!word.is_empty(), "{src} contains an empty word");
+            let difficulty = record.get(3).unwrap_or_default();
+            assert!(
+                ["", "easy", "medium", "hard"].contains(&difficulty),
+                "{src} contains invalid difficulty {difficulty:?}"
+            );
+            let fields = [
+                word,
+                record.get(1).unwrap_or_default(),
+                record.get(2).unwrap_or_default(),
+                difficulty,
+            ];
+            for (i, field) in fields.into_iter().enumerate() {
+                assert!(
+                    !field.contains([RECORD_SEP, UNIT_SEP]),
+                    "{src} contains a control character in {word:?}"
+                );
+                if i > 0 {
+                    table.push(UNIT_SEP);
+                }
+                table.push_str(field);
+            }
+            table.push(RECORD_SEP);
+        }
+        fs::write(
+            out_dir.join(format!("{name}.bin")),
+            miniz_oxide::deflate::compress_to_vec(table.as_bytes(), 10),
+        )
+        .expect("writing builtin word table should not fail");
+    }
+}
@@ -0,0 +1,151 @@
+use crate::model::{Guess, Hangman, Response};
+use crate::solver::Solver;
+use std::fmt;
+
+/// A step-by-step comparison of the user's guesses in a game against the
+/// guesses suggested by a [`Solver`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Analysis {
+    steps: Vec<Step>,
+}
+
+impl Analysis {
+    /// Analyze the guesses made in `finished` by replaying them, one at a
+    /// time, on `initial` (a copy of the same game taken before any guesses
+    /// were made) and asking `solver` what it would have guessed at each
+    /// point
+    pub(crate) fn new(initial: &Hangman, finished: &Hangman, solver: &Solver) -> Analysis {
+        let mut game = initial.clone();
+        let mut steps = Vec::with_capacity(finished.history().len());
+        for &guess in finished.history() {
+            let suggestion = solver.suggest(&game);
+            let verdict = if suggestion == Some(guess.letter) {
+                Verdict::Best
+            } else if !guess.is_hit() && suggestion.is_some_and(|s| in_word(&game, s)) {
+                Verdict::Wasteful
+            } else {
+                Verdict::Fine
+            };
+            steps.push(Step {
+                guess,
+                suggestion,
+                verdict,
+            });
+            game.guess(guess.letter);
+        }
+        Analysis { steps }
+    }
+
+    /// Returns the number of guesses judged to be wasteful
+    pub(crate) fn wasteful(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|st| st.verdict == Verdict::Wasteful)
+            .count()
+    }
+}
+
+/// Returns true iff the (as yet unguessed) character `ch` occurs in the secret
+/// word of `game`
+fn in_word(game: &Hangman, ch: char) -> bool {
+    let mut probe = game.clone();
+    probe.set_max_misses(None);
+    matches!(probe.guess(ch), Response::GoodGuess { .. })
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  #  Guess  Result  Suggested  Verdict")?;
+        for (i, st) in self.steps.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3}  {:<5}  {:<6}  {:<9}  {}",
+                i + 1,
+                st.guess.letter,
+                if st.guess.is_hit() { "hit" } else { "miss" },
+                st.suggestion.map(String::from).unwrap_or_default(),
+                st.verdict,
+            )?;
+        }
+        write!(
+            f,
+            "Wasteful guesses: {} of {}",
+            self.wasteful(),
+            self.steps.len()
+        )
+    }
+}
+
+/// A single guess in an [`Analysis`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Step {
+    /// The guess the user made
+    guess: Guess,
+    /// The guess the solver would have made instead
+    suggestion: Option<char>,
+    verdict: Verdict,
+}
+
+/// An assessment of a guess in an [`Analysis`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Verdict {
+    /// The user made the same guess as the solver
+    Best,
+    /// The user's guess differed from the solver's, but it was either correct
+    /// or the solver's guess would also have missed
+    Fine,
+    /// The user's guess missed, but the solver's guess would have hit
+    Wasteful,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Best => write!(f, "best"),
+            Verdict::Fine => write!(f, "ok"),
+            Verdict::Wasteful => write!(f, "wasteful"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ASCII_ALPHABET;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_analysis() {
+        let initial = Hangman::new("quiz".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        let mut game = initial.clone();
+        for ch in "xeuqiz".chars() {
+            game.guess(ch);
+        }
+        let analysis = Analysis::new(&initial, &game, &Solver::default());
+        assert_eq!(
+            analysis.to_string(),
+            concat!(
+                "  #  Guess  Result  Suggested  Verdict\n",
+                "  1  X      miss    E          ok\n",
+                "  2  E      miss    E          best\n",
+                "  3  U      hit     T          ok\n",
+                "  4  Q      hit     T          ok\n",
+                "  5  I      hit     T          ok\n",
+                "  6  Z      hit     T          ok\n",
+                "Wasteful guesses: 0 of 6",
+            )
+        );
+    }
+
+    #[test]
+    fn test_wasteful() {
+        let initial = Hangman::new("tea".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        let mut game = initial.clone();
+        for ch in "ztea".chars() {
+            game.guess(ch);
+        }
+        let analysis = Analysis::new(&initial, &game, &Solver::default());
+        assert_eq!(analysis.wasteful(), 1);
+        assert_eq!(analysis.steps[0].verdict, Verdict::Wasteful);
+    }
+}
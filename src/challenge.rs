@@ -0,0 +1,127 @@
+use crate::words::{Word, WordWithHint};
+use std::fmt;
+use thiserror::Error;
+
+/// The characters used to encode challenge codes: the base32 alphabet from RFC
+/// 4648
+static ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A secret word and optional hint, encoded as an opaque string of letters &
+/// digits that can be shared with other players without giving the word away.
+///
+/// The code is the base32 encoding (without padding) of the word, followed by
+/// a newline and the hint if there is one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Challenge(pub(crate) WordWithHint);
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut payload = String::from(self.0.word.as_ref());
+        if let Some(hint) = &self.0.hint {
+            payload.push('\n');
+            payload.push_str(hint);
+        }
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        for b in payload.bytes() {
+            buffer = (buffer << 8) | u32::from(b);
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                write!(f, "{}", encode_digit(buffer >> bits))?;
+            }
+            buffer &= (1 << bits) - 1;
+        }
+        if bits > 0 {
+            write!(f, "{}", encode_digit(buffer << (5 - bits)))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Challenge {
+    type Err = ParseChallengeError;
+
+    /// Decode a challenge code.  Letters may be in either case, and any
+    /// whitespace or hyphens (as might be introduced when sharing the code)
+    /// are ignored.
+    fn from_str(s: &str) -> Result<Challenge, ParseChallengeError> {
+        let mut payload = Vec::new();
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        for ch in s.chars().filter(|&ch| !(ch.is_whitespace() || ch == '-')) {
+            let digit = decode_digit(ch).ok_or(ParseChallengeError)?;
+            buffer = (buffer << 5) | digit;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                payload.push(u8::try_from(buffer >> bits).map_err(|_| ParseChallengeError)?);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+        let payload = String::from_utf8(payload).map_err(|_| ParseChallengeError)?;
+        let (word, hint) = match payload.split_once('\n') {
+            Some((word, hint)) => (word, Some(hint.to_owned())),
+            None => (payload.as_str(), None),
+        };
+        let word = word.parse::<Word>().map_err(|_| ParseChallengeError)?;
+        Ok(Challenge(WordWithHint {
+            hint,
+            ..WordWithHint::from(word)
+        }))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("invalid challenge code")]
+pub(crate) struct ParseChallengeError;
+
+/// Returns the character encoding the low five bits of `value`
+fn encode_digit(value: u32) -> char {
+    char::from(ALPHABET[(value & 0x1F) as usize])
+}
+
+/// Returns the value encoded by the character `ch`, if it's valid
+fn decode_digit(ch: char) -> Option<u32> {
+    let ch = u8::try_from(ch.to_ascii_uppercase()).ok()?;
+    ALPHABET
+        .iter()
+        .position(|&c| c == ch)
+        .and_then(|i| u32::try_from(i).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(word: &str, hint: Option<&str>) -> Challenge {
+        Challenge(WordWithHint {
+            hint: hint.map(String::from),
+            ..WordWithHint::from(word.parse::<Word>().unwrap())
+        })
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(challenge("f", None).to_string(), "MY");
+        assert_eq!(challenge("foobar", None).to_string(), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let c = challenge("Quixotic", Some("Idealistic & impractical"));
+        let code = c.to_string();
+        assert!(!code.to_lowercase().contains("quixotic"));
+        assert_eq!(code.parse::<Challenge>(), Ok(c.clone()));
+        let mangled = format!(" {}-{} ", code[..5].to_lowercase(), &code[5..]);
+        assert_eq!(mangled.parse::<Challenge>(), Ok(c));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!("".parse::<Challenge>(), Err(ParseChallengeError));
+        assert_eq!("MZ1W6".parse::<Challenge>(), Err(ParseChallengeError));
+        // Decodes to a lone 0xFF byte, which isn't UTF-8
+        assert_eq!("74".parse::<Challenge>(), Err(ParseChallengeError));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::difficulty::{Difficulty, Preset};
+use crate::theme::{Theme, ThemeSpec};
+use crate::view::Arrangement;
+use crate::words::{FrequencyTier, Pool};
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+/// User configuration, read from a TOML file in the user's configuration
+/// directory
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Config {
+    /// Overrides for the settings of difficulty presets, keyed by difficulty
+    /// name
+    pub(crate) difficulty: BTreeMap<Difficulty, PresetOverrides>,
+    /// The name of the theme to use when `--theme` isn't given
+    pub(crate) theme: Option<String>,
+    /// User-defined themes, keyed by name
+    pub(crate) themes: BTreeMap<String, ThemeSpec>,
+    /// The arrangement of the game display
+    pub(crate) layout: Arrangement,
+}
+
+impl Config {
+    /// Returns the path to the configuration file, or `None` if the user's
+    /// configuration directory could not be determined
+    pub(crate) fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("hangman").join("config.toml"))
+    }
+
+    /// Read the configuration file.  If the file does not exist (or its
+    /// location cannot be determined), a default `Config` is returned.
+    pub(crate) fn load() -> anyhow::Result<Config> {
+        let Some(path) = Config::path() else {
+            return Ok(Config::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(src) => toml::from_str(&src)
+                .with_context(|| format!("failed to parse config file {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => {
+                Err(e).with_context(|| format!("failed to read config file {}", path.display()))
+            }
+        }
+    }
+
+    /// Returns the settings for the given difficulty level, with any
+    /// overrides from the configuration applied
+    pub(crate) fn preset(&self, difficulty: Difficulty) -> Preset {
+        let mut preset = difficulty.preset();
+        if let Some(overrides) = self.difficulty.get(&difficulty) {
+            overrides.apply(&mut preset);
+        }
+        preset
+    }
+
+    /// Returns the theme with the given name — or, if `name` is `None`, the
+    /// theme named in the configuration, if any, or else the default theme.
+    /// Themes defined in the configuration take precedence over builtin
+    /// themes of the same name.
+    pub(crate) fn theme(&self, name: Option<&str>) -> anyhow::Result<Theme> {
+        let Some(name) = name.or(self.theme.as_deref()) else {
+            return Ok(Theme::default());
+        };
+        match self.themes.get(name) {
+            Some(spec) => spec.resolve().with_context(|| {
+                format!(
+                    "theme {name:?} is based on unknown builtin theme {:?}",
+                    spec.base.as_deref().unwrap_or_default()
+                )
+            }),
+            None => Theme::builtin(name).with_context(|| {
+                format!(
+                    "unknown theme {name:?} (builtin themes: {})",
+                    Theme::BUILTIN.join(", ")
+                )
+            }),
+        }
+    }
+}
+
+/// Settings in the configuration file that override those of a [`Preset`]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct PresetOverrides {
+    pub(crate) min_length: Option<usize>,
+    pub(crate) max_length: Option<usize>,
+    pub(crate) misses: Option<NonZeroUsize>,
+    pub(crate) show_hint: Option<bool>,
+    pub(crate) pool: Option<Pool>,
+    pub(crate) tier: Option<FrequencyTier>,
+}
+
+impl PresetOverrides {
+    fn apply(&self, preset: &mut Preset) {
+        if let Some(min_length) = self.min_length {
+            preset.min_length = Some(min_length);
+        }
+        if let Some(max_length) = self.max_length {
+            preset.max_length = Some(max_length);
+        }
+        if let Some(misses) = self.misses {
+            preset.misses = misses.get();
+        }
+        if let Some(show_hint) = self.show_hint {
+            preset.show_hint = show_hint;
+        }
+        if let Some(pool) = self.pool {
+            preset.pool = pool;
+        }
+        if let Some(tier) = self.tier {
+            preset.tier = tier;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::{Hidden, HintPlacement, Panel, WordPlacement};
+
+    #[test]
+    fn test_override_preset() {
+        let config = toml::from_str::<Config>(concat!(
+            "[difficulty.hard]\n",
+            "misses = 2\n",
+            "max-length = 8\n",
+            "tier = \"uncommon\"\n",
+        ))
+        .unwrap();
+        assert_eq!(
+            config.preset(Difficulty::Hard),
+            Preset {
+                misses: 2,
+                max_length: Some(8),
+                tier: FrequencyTier::Uncommon,
+                ..Difficulty::Hard.preset()
+            }
+        );
+        assert_eq!(config.preset(Difficulty::Easy), Difficulty::Easy.preset());
+    }
+
+    #[test]
+    fn test_theme() {
+        let config = toml::from_str::<Config>(concat!(
+            "theme = \"mine\"\n",
+            "[themes.mine]\n",
+            "hint = \"italic\"\n",
+            "[themes.broken]\n",
+            "base = \"nonexistent\"\n",
+        ))
+        .unwrap();
+        let mine = Theme {
+            hint: ratatui::style::Style::new().add_modifier(ratatui::style::Modifier::ITALIC),
+            ..Theme::default()
+        };
+        assert_eq!(config.theme(None).unwrap(), mine);
+        assert_eq!(
+            config.theme(Some("vivid")).unwrap(),
+            Theme::builtin("vivid").unwrap()
+        );
+        assert!(config.theme(Some("broken")).is_err());
+        assert!(config.theme(Some("nonexistent")).is_err());
+        assert_eq!(Config::default().theme(None).unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn test_layout() {
+        let config = toml::from_str::<Config>(
+            "[layout]\nmirror = true\nhint = \"bottom\"\nhide = [\"gallows\", \"history\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.layout,
+            Arrangement {
+                mirror: true,
+                hint: HintPlacement::Bottom,
+                word: WordPlacement::Below,
+                hide: Hidden::from(vec![Panel::Gallows, Panel::History]),
+            }
+        );
+        let r = toml::from_str::<Config>("[layout]\nword = \"sideways\"\n");
+        assert!(r.is_err());
+        let r = toml::from_str::<Config>("[layout]\nhide = [\"word\"]\n");
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_zero_misses() {
+        let r = toml::from_str::<Config>("[difficulty.easy]\nmisses = 0\n");
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_unknown_difficulty() {
+        let r = toml::from_str::<Config>("[difficulty.extreme]\nmisses = 1\n");
+        assert!(r.is_err());
+    }
+}
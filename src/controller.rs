@@ -1,74 +1,1503 @@
+use crate::analysis::Analysis;
+use crate::graphics::Graphics;
 use crate::model::*;
+use crate::score::score;
+use crate::solver::Solver;
+use crate::theme::{Theme, ThemeSpec};
 use crate::view::*;
-use crate::words::WordWithHint;
-use std::io;
+use crate::words::{Fetched, LazyList, LengthLimit, WordFilter, WordList, WordWithHint};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// The number of players in co-op mode
+const COOP_PLAYERS: usize = 2;
+
+/// Settings that affect how a game is played, independent of the secret word
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Settings {
+    /// Kids mode: incorrect guesses never lose the game, and the gallows is
+    /// replaced with a friendly animation
+    pub(crate) kids: bool,
+    /// The maximum length of the secret word
+    pub(crate) length_limit: LengthLimit,
+    /// The number of incorrect guesses after which the user loses, or `None`
+    /// for no limit
+    pub(crate) max_misses: Option<usize>,
+    /// Whether to show the secret word's hint
+    pub(crate) show_hint: bool,
+    /// Whether to start the game with assist mode (highlighting of the
+    /// suggested next guess) enabled
+    pub(crate) assist: bool,
+    /// Whether to show the number of words in the word list that are
+    /// consistent with what the user knows about the secret word
+    pub(crate) cheat_meter: bool,
+    /// Whether to print an analysis of the user's guesses after the game ends
+    pub(crate) analyze: bool,
+    /// Whether to play a duel against the computer
+    pub(crate) duel: bool,
+    /// Whether to play with the line-based interface instead of taking over
+    /// the terminal
+    pub(crate) teletype: bool,
+    /// Whether the line-based interface describes the game in words instead
+    /// of drawing it, for screen readers
+    pub(crate) accessible: bool,
+    /// Whether to play reverse Hangman, in which the computer guesses the
+    /// user's word
+    pub(crate) reverse: bool,
+    /// If set, play a sequence of words rather than just one
+    pub(crate) sequence: Option<Sequence>,
+    /// Co-op mode: two players take turns guessing letters in the same word
+    pub(crate) coop: bool,
+    /// The number of points deducted from a round's score for each extra
+    /// hint revealed
+    pub(crate) hint_cost: usize,
+    /// Whether to look up a definition of the secret word online at the end
+    /// of the game
+    pub(crate) define: bool,
+    /// The colors & attributes with which to draw the game
+    pub(crate) theme: Theme,
+    /// The art drawn in place of the gallows, when not in kids mode
+    pub(crate) art: Art,
+    /// How to draw already-guessed letters in the letter grid
+    pub(crate) guessed: GuessedLetters,
+    /// How to arrange the letters in the letter grid
+    pub(crate) letter_layout: LetterLayout,
+    /// Where to draw the parts of the display relative to each other
+    pub(crate) arrangement: Arrangement,
+    /// If set, draw the gallows as an image with the given graphics protocol
+    /// rather than as text
+    pub(crate) graphics: Option<Graphics>,
+    /// Whether to draw the secret word in large letters when the terminal
+    /// has room for them
+    pub(crate) big_word: bool,
+    /// When the secret word is too wide for the terminal & is wrapped across
+    /// lines, the number of characters kept together on a line
+    pub(crate) wrap_group: NonZeroUsize,
+    /// The character drawn in place of each unrevealed character of the word
+    pub(crate) blank: Placeholder,
+    /// Whether to show a status line with the time spent on the current word
+    /// and the user's progress
+    pub(crate) status_bar: bool,
+    /// If set, the time allowed for each guess, after which a miss is counted
+    /// against the user
+    pub(crate) timer: Option<Duration>,
+    /// Whether the user can click letters in the letter grid to guess them
+    pub(crate) mouse: bool,
+    /// Whether the user can select letters in the letter grid with the cursor
+    /// keys & guess them with Enter
+    pub(crate) cursor_keys: bool,
+    /// Whether the secret word is the word of the day, which can't be
+    /// swapped for another from the pause menu
+    pub(crate) daily: bool,
+    /// Whether to print the secret word when the user quits partway through
+    /// a game
+    pub(crate) reveal_on_quit: bool,
+    /// Whether to hide the game while it's paused because the terminal
+    /// window lost focus
+    pub(crate) hide_unfocused: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            kids: false,
+            length_limit: LengthLimit::default(),
+            max_misses: Some(DEFAULT_MAX_MISSES),
+            show_hint: true,
+            assist: false,
+            cheat_meter: false,
+            analyze: false,
+            duel: false,
+            teletype: false,
+            accessible: false,
+            reverse: false,
+            sequence: None,
+            coop: false,
+            hint_cost: 0,
+            define: false,
+            theme: Theme::default(),
+            art: Art::default(),
+            guessed: GuessedLetters::default(),
+            letter_layout: LetterLayout::default(),
+            arrangement: Arrangement::default(),
+            graphics: None,
+            big_word: false,
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            status_bar: false,
+            timer: None,
+            mouse: false,
+            cursor_keys: false,
+            daily: false,
+            reveal_on_quit: false,
+            hide_unfocused: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Returns the keys for guessing letters, for the help overlay
+    fn guessing_keys(&self) -> Vec<(&'static str, &'static str)> {
+        let mut keys = vec![("A-Z", "Guess a letter")];
+        if self.mouse {
+            keys.push(("Click", "Guess the letter clicked on"));
+        }
+        if self.cursor_keys {
+            keys.push(("Arrows", "Select a letter"));
+            keys.push(("Enter", "Guess the selected letter"));
+        }
+        keys
+    }
+
+    /// Returns the figure to draw for a gallows in the state `gallows`
+    fn figure(&self, gallows: Gallows) -> Figure {
+        match (self.art, self.graphics) {
+            (Art::Gallows, Some(graphics)) => Figure::Image(graphics, gallows),
+            (art, _) => art.figure(gallows),
+        }
+    }
+
+    /// Returns the display of a game with the given figure, letters, word, &
+    /// message, laid out according to the settings.  The parts of the
+    /// display that only some game modes fill in are left empty.
+    fn content(
+        &self,
+        figure: Figure,
+        guess_options: Vec<GuessOption>,
+        word_display: Vec<CharDisplay>,
+        message: Message,
+    ) -> Content {
+        Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure,
+            opponent: None,
+            tally: None,
+            guess_options,
+            guessed: self.guessed,
+            letter_layout: self.letter_layout,
+            arrangement: self.arrangement,
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display,
+            wrap_group: self.wrap_group,
+            blank: self.blank,
+            big_word: self.big_word,
+            message,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            title: Title::default(),
+            history: Vec::new(),
+        }
+    }
+}
+
+/// A game in which the user plays a series of words drawn from the word list
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Sequence {
+    /// Keep drawing new words after each one is solved until the user loses,
+    /// treating the gallows between words as specified.  Each word counts as
+    /// a separate game.
+    Endless(GallowsCarry),
+    /// Lightning round: the user must solve the given number of words in a
+    /// row with a single gallows shared between them.  The whole sequence
+    /// counts as one game.
+    Lightning(NonZeroUsize),
+    /// A match of the given number of rounds, each a separate game with its
+    /// own gallows, scored cumulatively
+    Match(NonZeroUsize),
+}
+
+impl Sequence {
+    /// Returns what happens to the gallows between words
+    fn carry(self) -> GallowsCarry {
+        match self {
+            Sequence::Endless(carry) => carry,
+            Sequence::Lightning(_) => GallowsCarry::Persist,
+            Sequence::Match(_) => GallowsCarry::Reset,
+        }
+    }
+}
+
+/// What happens to the gallows when moving on to a new word in a sequence
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum GallowsCarry {
+    /// The gallows carries over, so misses accumulate across words
+    #[default]
+    Persist,
+    /// The gallows is cleared for each new word
+    Reset,
+}
+
+impl std::str::FromStr for GallowsCarry {
+    type Err = ParseGallowsCarryError;
+
+    fn from_str(s: &str) -> Result<GallowsCarry, ParseGallowsCarryError> {
+        match s {
+            "persist" => Ok(GallowsCarry::Persist),
+            "reset" => Ok(GallowsCarry::Reset),
+            _ => Err(ParseGallowsCarryError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"endless mode must be "persist" or "reset""#)]
+pub(crate) struct ParseGallowsCarryError;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Controller {
     game: Hangman,
-    hint: Option<String>,
+    /// A copy of `game` from before any guesses were made, for use in
+    /// post-game analysis
+    initial: Hangman,
+    clues: Clues,
+    /// The index in `clues.hints` of the hint currently shown
+    hint_index: usize,
+    settings: Settings,
+    /// The themes defined in the configuration file, for `:theme`
+    themes: BTreeMap<String, ThemeSpec>,
+    /// Whether the secret word was truncated to fit the length limit
+    truncated: bool,
+    /// The solver for the word list, built the first time it's needed
+    solver: OnceCell<Solver>,
+    /// Whether to highlight the solver's suggested next guess
+    assist: bool,
+    /// The word list that the secret word was selected from, for drawing
+    /// further words in endless mode
+    list: Option<LazyList>,
+    /// The criteria for drawing further words from `list`
+    filter: WordFilter,
+    /// The random number generator for drawing further words from `list`
+    rng: StdRng,
+    /// The words from `list` not yet played in the current sequence, in the
+    /// order in which they will be drawn (from the end)
+    deck: Vec<WordWithHint>,
+    /// The number of words solved so far in a sequence of words
+    solved: usize,
+    /// The results of the rounds of a match completed so far
+    rounds: Vec<RoundResult>,
+    /// A definition of the secret word, once the game is over
+    definition: Option<String>,
+    /// The time spent on the current word
+    clock: Clock,
+    /// The reading of `clock` when each guess in the game's history was made
+    guess_times: Vec<Duration>,
+    /// The time left for the user's next guess, if guesses are timed
+    countdown: Option<Countdown>,
+    /// Which phrasing of the current message to show, picked at random for
+    /// each guess
+    flavor: usize,
+    /// The random number generator for picking `flavor`, kept apart from
+    /// `rng` so that the words drawn don't depend on how many guesses are made
+    flavors: StdRng,
+    /// A description of where the secret word came from
+    source: String,
 }
 
 impl Controller {
-    pub(crate) fn new(secret: WordWithHint) -> Result<Controller, HangmanError> {
-        let WordWithHint { word, hint } = secret;
-        let game = Hangman::new(word, ASCII_ALPHABET)?;
-        Ok(Controller { game, hint })
+    pub(crate) fn new(
+        fetched: Fetched,
+        settings: Settings,
+        themes: BTreeMap<String, ThemeSpec>,
+    ) -> anyhow::Result<Controller> {
+        let Fetched {
+            secret,
+            list,
+            filter,
+            mut rng,
+            source,
+        } = fetched;
+        let mut deck = match &list {
+            Some(list) if settings.sequence.is_some() => list.get().shuffled(&filter, &mut rng),
+            _ => Vec::new(),
+        };
+        deck.retain(|w| w.word != secret.word);
+        let flavors = StdRng::from_rng(&mut rng)?;
+        let (game, clues, truncated) = start_word(secret, &settings)?;
+        Ok(Controller {
+            initial: game.clone(),
+            game,
+            clues,
+            hint_index: 0,
+            settings,
+            themes,
+            truncated,
+            solver: OnceCell::new(),
+            assist: settings.assist,
+            list,
+            filter,
+            rng,
+            deck,
+            solved: 0,
+            rounds: Vec::new(),
+            definition: None,
+            clock: Clock::start(),
+            guess_times: Vec::new(),
+            countdown: settings.timer.map(Countdown::start),
+            flavor: 0,
+            flavors,
+            source,
+        })
     }
 
-    pub(crate) fn run(mut self) -> anyhow::Result<()> {
-        let content = Content {
-            hint: self.hint.clone(),
-            gallows: self.game.gallows(),
-            guess_options: self.guess_options(),
-            word_display: self.word_display(),
-            message: Message::Start,
-        };
-        let mut screen = Screen::new(io::stdout(), content)?;
+    /// Play the game in the terminal.  Returns the outcome of each word
+    /// played, in order; if the user quits partway through a word, that word
+    /// has no outcome.
+    pub(crate) fn run(mut self) -> anyhow::Result<Vec<Fate>> {
+        let mut word_display = self.word_display();
+        let mut message = self.start_message();
+        require_terminal()?;
+        let mut screen = Screen::new(
+            io::stdout(),
+            self.content(word_display.clone(), message),
+            self.settings.theme,
+            std::mem::take(&mut self.themes),
+            self.settings.mouse,
+            self.settings.cursor_keys,
+        )?;
         screen.draw()?;
-        while let Some(guess) = screen.read_guess()? {
-            let r = self.game.guess(guess);
-            let mut word_display = self.word_display();
-            let message = match r {
-                Response::GoodGuess { guess, count, won } => {
-                    for cd in &mut word_display {
-                        if *cd == CharDisplay::Plain(guess) {
-                            *cd = CharDisplay::Highlighted(guess);
+        let mut fates = Vec::new();
+        loop {
+            match screen.read_input()? {
+                input @ (Input::Guess(_) | Input::GuessWord(_) | Input::TimeUp) => {
+                    (word_display, message) = match input {
+                        Input::Guess(guess) => self.guess(guess),
+                        Input::GuessWord(word) => self.guess_word(&word),
+                        _ => self.time_out(),
+                    };
+                    self.flavor = self.flavors.gen();
+                    screen.update(self.content(word_display.clone(), message))?;
+                    if message == Message::Solved {
+                        if matches!(self.settings.sequence, Some(Sequence::Endless(_))) {
+                            fates.push(Fate::Won);
+                        }
+                        if screen.prompt()? {
+                            break;
+                        }
+                    } else if let Some(fate) = self.game.fate() {
+                        fates.push(fate);
+                        let Some(Sequence::Match(total)) = self.settings.sequence else {
+                            screen.pause()?;
+                            break;
+                        };
+                        if screen.prompt()? {
+                            break;
+                        }
+                        if self.rounds.len() >= total.get() {
+                            screen.show_summary(&MatchSummary {
+                                rounds: std::mem::take(&mut self.rounds),
+                            })?;
+                            screen.pause()?;
+                            break;
                         }
-                    }
-                    if won {
-                        Message::Won
                     } else {
-                        Message::GoodGuess { guess, count }
+                        continue;
                     }
+                    self.next_word()?;
+                    word_display = self.word_display();
+                    message = self.start_message();
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                Input::ToggleAssist => {
+                    self.assist = !self.assist;
+                    screen.notify(on_off("Assist mode", self.assist));
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                Input::NextHint if self.hint_index + 1 < self.clues.hints.len() => {
+                    self.hint_index += 1;
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                Input::NewWord if self.can_skip() => {
+                    self.next_word()?;
+                    word_display = self.word_display();
+                    message = self.start_message();
+                    screen.notify("New word drawn");
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                Input::NextHint | Input::NewWord | Input::Enter | Input::Backspace => {
+                    screen.beep()?;
                 }
-                Response::BadGuess {
-                    lost: Some(Lost { word }),
-                    ..
-                } => {
-                    for (ch, cd) in std::iter::zip(word, &mut word_display) {
-                        if *cd == CharDisplay::Blank {
-                            *cd = CharDisplay::Highlighted(ch);
+                Input::Help => {
+                    let paused = Instant::now();
+                    screen.show_help(&self.help())?;
+                    self.resume(paused);
+                }
+                Input::Unfocused => {
+                    let paused = Instant::now();
+                    screen.wait_for_focus(self.settings.hide_unfocused)?;
+                    self.resume(paused);
+                }
+                Input::Suspend => {
+                    let paused = Instant::now();
+                    screen.suspend()?;
+                    self.resume(paused);
+                }
+                Input::Pause => {
+                    let paused = Instant::now();
+                    let choice = screen.pause_menu(self.can_skip(), true)?;
+                    let quit = choice == MenuChoice::Quit && screen.confirm_quit()?;
+                    self.resume(paused);
+                    match choice {
+                        MenuChoice::Resume => (),
+                        MenuChoice::NewWord => {
+                            self.next_word()?;
+                            word_display = self.word_display();
+                            message = self.start_message();
+                            screen.notify("New word drawn");
+                        }
+                        MenuChoice::ToggleAssist => {
+                            self.assist = !self.assist;
+                            screen.notify(on_off("Assist mode", self.assist));
+                        }
+                        MenuChoice::ToggleBigWord => {
+                            self.settings.big_word = !self.settings.big_word;
+                            screen.notify(on_off("Large letters", self.settings.big_word));
                         }
+                        MenuChoice::Quit if quit => break,
+                        MenuChoice::Quit => (),
+                    }
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                Input::Quit => {
+                    let paused = Instant::now();
+                    let quit = screen.confirm_quit()?;
+                    self.resume(paused);
+                    if quit {
+                        break;
+                    }
+                }
+            }
+        }
+        drop(screen);
+        self.report();
+        Ok(fates)
+    }
+
+    /// Play the game with a line-based interface, printing the state of the
+    /// game to standard output after each guess and reading guesses a line
+    /// at a time from standard input, for dumb terminals, screen readers, and
+    /// pipes.  Each character on a line is guessed in turn.  Reaching the end
+    /// of input quits the game.  Returns the outcome of each word played, as
+    /// with [`Controller::run()`].
+    pub(crate) fn run_teletype(mut self) -> anyhow::Result<Vec<Fate>> {
+        // Images can't be printed a line at a time
+        self.settings.graphics = None;
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout();
+        let mut word_display = self.word_display();
+        let mut message = self.start_message();
+        self.print_state(&word_display, message);
+        let mut fates = Vec::new();
+        loop {
+            write!(stdout, "Your guess: ")?;
+            stdout.flush()?;
+            let mut line = String::new();
+            if stdin.read_line(&mut line)? == 0 {
+                println!();
+                break;
+            }
+            for guess in line.chars().filter(|ch| !ch.is_whitespace()) {
+                (word_display, message) = self.guess(guess);
+                if message == Message::Solved || self.game.fate().is_some() {
+                    break;
+                }
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            println!();
+            self.print_state(&word_display, message);
+            if message == Message::Solved {
+                if matches!(self.settings.sequence, Some(Sequence::Endless(_))) {
+                    fates.push(Fate::Won);
+                }
+            } else if let Some(fate) = self.game.fate() {
+                fates.push(fate);
+                let Some(Sequence::Match(total)) = self.settings.sequence else {
+                    break;
+                };
+                if self.rounds.len() >= total.get() {
+                    println!();
+                    let summary = MatchSummary {
+                        rounds: std::mem::take(&mut self.rounds),
+                    };
+                    for ln in summary.lines() {
+                        println!("{ln}");
                     }
-                    Message::Lost
-                }
-                Response::BadGuess { guess, lost: None } => Message::BadGuess { guess },
-                Response::AlreadyGuessed { guess } => Message::AlreadyGuessed { guess },
-                Response::InvalidGuess { guess } => Message::InvalidGuess { guess },
-                // This can't happen the way we're using the game, but we
-                // should at least do something reasonable.
-                Response::GameOver => Message::InvalidGuess { guess },
+                    break;
+                }
+            } else {
+                continue;
+            }
+            self.next_word()?;
+            word_display = self.word_display();
+            message = self.start_message();
+            println!();
+            self.print_state(&word_display, message);
+        }
+        self.report();
+        Ok(fates)
+    }
+
+    /// Print the figure, hint, word, and misses so far followed by `message`,
+    /// for the line-based interface
+    fn print_state(&self, word_display: &[CharDisplay], message: Message) {
+        if self.settings.accessible {
+            self.describe_state(word_display, message);
+            return;
+        }
+        for ln in figure_text(self.figure()) {
+            println!("{ln}");
+        }
+        println!();
+        if let Some(hint) = self.clues.hints.get(self.hint_index) {
+            println!("Hint: {hint}");
+        }
+        println!("Word: {}", word_text(word_display, self.settings.blank));
+        let wrong = self.game.wrong_guesses().collect::<Vec<_>>();
+        if !wrong.is_empty() {
+            println!(
+                "Misses: {}",
+                wrong
+                    .into_iter()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
+        if let Some(n) = self
+            .game
+            .misses_remaining()
+            .filter(|_| self.game.fate().is_none())
+        {
+            println!("Misses left: {n}");
+        }
+        println!("{message}");
+        if self.game.fate().is_some() {
+            let stats = Stats {
+                guesses: self.game.history().len(),
+                misses: self.game.misses(),
+            };
+            println!("{stats}");
+        }
+        if let Some(definition) = &self.definition {
+            println!("{definition}");
+        }
+    }
+
+    /// Print the state of the game in words, without relying on pictures or
+    /// layout, for `--accessible`
+    fn describe_state(&self, word_display: &[CharDisplay], message: Message) {
+        let mut sentences = vec![message.to_string()];
+        let starting = matches!(message, Message::Start | Message::StartTruncated { .. });
+        if starting {
+            sentences.push(match word_display.len() {
+                1 => String::from("The word has 1 character."),
+                n => format!("The word has {n} characters."),
+            });
+        }
+        match self
+            .game
+            .misses_remaining()
+            .filter(|_| self.game.fate().is_none())
+        {
+            Some(1) => sentences.push(String::from("1 miss remaining.")),
+            Some(n) => sentences.push(format!("{n} misses remaining.")),
+            None => (),
+        }
+        sentences.push(format!("Revealed: {}.", word_speech(word_display)));
+        println!("{}", sentences.join(" "));
+        if starting {
+            if let Some(hint) = self.clues.hints.get(self.hint_index) {
+                println!("Hint: {hint}");
+            }
+        }
+        let wrong = self.game.wrong_guesses().collect::<Vec<_>>();
+        if !wrong.is_empty() && self.game.fate().is_none() {
+            println!(
+                "Wrong guesses so far: {}.",
+                wrong
+                    .into_iter()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        if self.game.fate().is_some() {
+            let stats = Stats {
+                guesses: self.game.history().len(),
+                misses: self.game.misses(),
             };
-            let content = Content {
-                hint: self.hint.clone(),
-                gallows: self.game.gallows(),
-                guess_options: self.guess_options(),
+            println!("{stats}");
+        }
+        if let Some(definition) = &self.definition {
+            println!("{definition}");
+        }
+    }
+
+    /// Print what's left to say once the user is done playing: the analysis
+    /// of their guesses, if requested, or the secret word if they quit
+    /// partway through and asked to see it
+    fn report(&self) {
+        if self.settings.analyze && self.game.fate().is_some() {
+            println!(
+                "{}",
+                Analysis::new(&self.initial, &self.game, self.solver())
+            );
+        }
+        if self.settings.reveal_on_quit && self.game.fate().is_none() {
+            print_revealed(&self.game);
+        }
+    }
+
+    /// Stop the time since `paused`, during which the game was paused, from
+    /// counting against the user
+    fn resume(&mut self, paused: Instant) {
+        let delay = paused.elapsed();
+        self.clock.postpone(delay);
+        if let Some(countdown) = &mut self.countdown {
+            countdown.postpone(delay);
+        }
+    }
+
+    /// Returns the contents of the help overlay
+    fn help(&self) -> Help {
+        let mut keys = self.settings.guessing_keys();
+        if self.clues.hints.len() > 1 {
+            keys.push(("+", "Reveal the next hint"));
+        }
+        keys.push(("Tab", "Turn assist mode on or off"));
+        keys.extend(Help::common_keys());
+        let mut rules = vec![match self.settings.max_misses {
+            Some(n) => format!("You lose after {n} incorrect guesses"),
+            None => String::from("Incorrect guesses never lose the game"),
+        }];
+        rules.push(match self.settings.sequence {
+            None => String::from("Mode: a single word"),
+            Some(Sequence::Endless(GallowsCarry::Persist)) => {
+                String::from("Mode: endless, with misses carried over between words")
+            }
+            Some(Sequence::Endless(GallowsCarry::Reset)) => {
+                String::from("Mode: endless, with a fresh gallows for each word")
+            }
+            Some(Sequence::Lightning(n)) => {
+                format!("Mode: lightning round of {n} words sharing one gallows")
+            }
+            Some(Sequence::Match(n)) => format!("Mode: match of {n} rounds"),
+        });
+        if self.settings.coop {
+            rules.push(String::from("Two players take turns guessing letters"));
+        }
+        if let Some(timer) = self.settings.timer {
+            rules.push(format!(
+                "Each guess must be made within {} seconds",
+                timer.as_secs()
+            ));
+        }
+        Help {
+            keys,
+            rules,
+            source: Some(self.source.clone()),
+        }
+    }
+
+    /// Returns whether the user can abandon the current word for a new one
+    /// from the word list.  This isn't allowed for the word of the day or in
+    /// lightning rounds & matches, where it would let the user skip words.
+    fn can_skip(&self) -> bool {
+        self.list.is_some()
+            && !self.settings.daily
+            && matches!(self.settings.sequence, None | Some(Sequence::Endless(_)))
+    }
+
+    fn start_message(&self) -> Message {
+        if self.truncated {
+            Message::StartTruncated {
+                max: self.settings.length_limit.max,
+            }
+        } else {
+            Message::Start
+        }
+    }
+
+    /// Draw a new secret word from the word list after the current one has
+    /// been solved, carrying over the gallows if so configured.  Words are not
+    /// repeated until every word accepted by the filter has been played.
+    fn next_word(&mut self) -> anyhow::Result<()> {
+        if self.deck.is_empty() {
+            if let Some(list) = &self.list {
+                self.deck = list.get().shuffled(&self.filter, &mut self.rng);
+            }
+        }
+        let Some(secret) = self.deck.pop() else {
+            anyhow::bail!("No more words to play");
+        };
+        let (mut game, clues, truncated) = start_word(secret, &self.settings)?;
+        if self.settings.sequence.map(Sequence::carry) == Some(GallowsCarry::Persist) {
+            game.carry_misses(self.game.misses());
+        }
+        self.initial = game.clone();
+        self.game = game;
+        self.clues = clues;
+        self.hint_index = 0;
+        self.definition = None;
+        self.truncated = truncated;
+        self.clock = Clock::start();
+        self.guess_times.clear();
+        self.countdown = self.settings.timer.map(Countdown::start);
+        Ok(())
+    }
+
+    /// Process a guess, returning the resulting word display and message
+    fn guess(&mut self, guess: char) -> (Vec<CharDisplay>, Message) {
+        let r = self.game.guess_by(self.turn(), guess);
+        if matches!(r, Response::GoodGuess { .. } | Response::BadGuess { .. }) {
+            self.guess_times.push(self.clock.elapsed());
+            self.countdown = self.settings.timer.map(Countdown::start);
+        }
+        let mut word_display = self.word_display();
+        let message = match r {
+            Response::GoodGuess { guess, count, won } => {
+                highlight(&mut word_display, guess);
+                if won {
+                    self.won_message()
+                } else {
+                    Message::GoodGuess { guess, count }
+                }
+            }
+            Response::BadGuess {
+                lost: Some(Lost { word }),
+                ..
+            } => {
+                reveal(&mut word_display, word);
+                self.lost_message()
+            }
+            Response::BadGuess { guess, lost: None } => {
+                if self.settings.kids {
+                    Message::TryAgain { guess }
+                } else {
+                    Message::BadGuess { guess }
+                }
+            }
+            Response::AlreadyGuessed { guess } => Message::AlreadyGuessed { guess },
+            Response::InvalidGuess { guess } => Message::InvalidGuess { guess },
+            // This can't happen the way we're using the game, but we
+            // should at least do something reasonable.
+            Response::GameOver => Message::InvalidGuess { guess },
+        };
+        self.check_fate();
+        (word_display, message)
+    }
+
+    /// Process a guess at the whole word, returning the resulting word display
+    /// and message
+    fn guess_word(&mut self, word: &str) -> (Vec<CharDisplay>, Message) {
+        let r = self.game.guess_word(word);
+        if r != WordResponse::GameOver {
+            self.countdown = self.settings.timer.map(Countdown::start);
+        }
+        let mut word_display = self.word_display();
+        let message = match r {
+            WordResponse::Correct => self.won_message(),
+            WordResponse::Incorrect {
+                lost: Some(Lost { word }),
+            } => {
+                reveal(&mut word_display, word);
+                self.lost_message()
+            }
+            WordResponse::Incorrect { lost: None } | WordResponse::GameOver => Message::WrongWord,
+        };
+        self.check_fate();
+        (word_display, message)
+    }
+
+    /// Count a miss against the user for not guessing before the countdown
+    /// ran out, returning the resulting word display and message
+    fn time_out(&mut self) -> (Vec<CharDisplay>, Message) {
+        self.countdown = self.settings.timer.map(Countdown::start);
+        let mut word_display = self.word_display();
+        let message = match self.game.time_out() {
+            Some(Lost { word }) => {
+                reveal(&mut word_display, word);
+                self.lost_message()
+            }
+            None => Message::TimeUp,
+        };
+        self.check_fate();
+        (word_display, message)
+    }
+
+    /// Count the word just won as solved and return the appropriate message
+    fn won_message(&mut self) -> Message {
+        self.solved += 1;
+        match self.settings.sequence {
+            Some(Sequence::Endless(_)) => Message::Solved,
+            Some(Sequence::Lightning(total)) if self.solved < total.get() => Message::Solved,
+            Some(Sequence::Match(_)) => Message::RoundWon {
+                points: self.points(),
+            },
+            _ => Message::Won,
+        }
+    }
+
+    fn lost_message(&self) -> Message {
+        if matches!(self.settings.sequence, Some(Sequence::Match(_))) {
+            Message::RoundLost
+        } else {
+            Message::Lost
+        }
+    }
+
+    /// If the last guess ended the game, record its result
+    fn check_fate(&mut self) {
+        if let (Some(Sequence::Match(_)), Some(fate)) = (self.settings.sequence, self.game.fate()) {
+            self.rounds.push(RoundResult {
+                word: secret_word(&self.game, &fate),
+                won: fate == Fate::Won,
+                points: self.points(),
+            });
+        }
+        if self.game.fate().is_some() {
+            self.definition = definition(&self.game, &self.settings);
+            self.clock.stop();
+        }
+    }
+
+    /// Returns the number of points earned for the current word, less the
+    /// cost of any extra hints revealed
+    fn points(&self) -> usize {
+        score(&self.game).saturating_sub(self.hint_index * self.settings.hint_cost)
+    }
+
+    fn content(&self, word_display: Vec<CharDisplay>, message: Message) -> Content {
+        Content {
+            hint: self.clues.hints.get(self.hint_index).cloned(),
+            hint_position: (self.clues.hints.len() > 1)
+                .then_some((self.hint_index + 1, self.clues.hints.len())),
+            category: self.clues.category.clone(),
+            part_of_speech: self.clues.part_of_speech.clone(),
+            tally: self.tally(),
+            suggestion: self.suggestion(),
+            candidates: self.candidates(),
+            lives: self.lives(),
+            wrong: Some(self.game.wrong_guesses().collect()),
+            flavor: self.flavor,
+            definition: self.definition.clone(),
+            stats: self.game.fate().is_some().then(|| Stats {
+                guesses: self.game.history().len(),
+                misses: self.game.misses(),
+            }),
+            status: self.settings.status_bar.then(|| Status {
+                clock: self.clock,
+                guesses: self.game.history().len(),
+                misses_remaining: self.game.misses_remaining(),
+            }),
+            countdown: self.countdown.filter(|_| self.game.fate().is_none()),
+            title: Title {
+                mode: None,
+                won: self.game.fate().map(|fate| fate == Fate::Won),
+                misses_remaining: self.game.misses_remaining(),
+            },
+            history: self
+                .game
+                .history()
+                .iter()
+                .copied()
+                .zip(self.guess_times.iter().copied())
+                .collect(),
+            ..self
+                .settings
+                .content(self.figure(), self.guess_options(), word_display, message)
+        }
+    }
+
+    /// Returns the player whose turn it is to guess
+    fn turn(&self) -> usize {
+        if self.settings.coop {
+            self.game.history().len() % COOP_PLAYERS
+        } else {
+            0
+        }
+    }
+
+    fn tally(&self) -> Option<Tally> {
+        if self.settings.sequence.is_none() && self.settings.coop {
+            return Some(if self.game.fate().is_some() {
+                Tally::Credits(
+                    (0..COOP_PLAYERS)
+                        .map(|player| Credit::for_player(self.game.history(), player))
+                        .collect(),
+                )
+            } else {
+                Tally::Turn {
+                    player: self.turn(),
+                }
+            });
+        }
+        match self.settings.sequence? {
+            Sequence::Endless(_) => Some(Tally::Solved(self.solved)),
+            Sequence::Lightning(total) => Some(Tally::Word {
+                // While the final word is still shown after being solved,
+                // don't count past the total
+                number: (self.solved + 1).min(total.get()),
+                total: total.get(),
+            }),
+            Sequence::Match(total) => Some(Tally::Round {
+                // Once a round ends, it continues to be shown as the current
+                // round until the next one starts
+                number: self.rounds.len() + usize::from(self.game.fate().is_none()),
+                total: total.get(),
+                score: self.rounds.iter().map(|r| r.points).sum(),
+            }),
+        }
+    }
+
+    fn lives(&self) -> Option<(usize, usize)> {
+        let remaining = self.game.misses_remaining()?;
+        Some((remaining, remaining + self.game.misses()))
+    }
+
+    fn candidates(&self) -> Option<usize> {
+        (self.settings.cheat_meter && !self.solver().is_empty())
+            .then(|| self.solver().candidates(&self.game).count())
+    }
+
+    fn suggestion(&self) -> Option<char> {
+        if self.assist && self.game.fate().is_none() {
+            self.solver().suggest(&self.game)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the solver for the word list, building it if this is the
+    /// first time it's needed
+    fn solver(&self) -> &Solver {
+        self.solver.get_or_init(|| {
+            self.list
+                .as_ref()
+                .map(|list| Solver::new(list.get()))
+                .unwrap_or_default()
+        })
+    }
+
+    fn figure(&self) -> Figure {
+        if self.settings.kids {
+            Figure::Dancer(self.game.misses())
+        } else {
+            self.settings.figure(self.game.gallows())
+        }
+    }
+
+    fn guess_options(&self) -> Vec<GuessOption> {
+        guess_options(&self.game)
+    }
+
+    fn word_display(&self) -> Vec<CharDisplay> {
+        word_display(&self.game)
+    }
+}
+
+/// Returns the complete secret word of `game`, which has ended with `fate`
+fn secret_word(game: &Hangman, fate: &Fate) -> String {
+    match fate {
+        Fate::Won => game.known_letters().iter().flatten().collect(),
+        Fate::Lost(Lost { word }) => word.iter().collect(),
+    }
+}
+
+/// Returns a definition of the secret word of `game` to show once the game is
+/// over: the bundled one if there is one, or else (if enabled) one looked up
+/// online
+fn definition(game: &Hangman, settings: &Settings) -> Option<String> {
+    let word = secret_word(game, &game.fate()?);
+    bundled_definition(&word).or_else(|| online_definition(&word, settings))
+}
+
+#[cfg(feature = "definitions")]
+fn bundled_definition(word: &str) -> Option<String> {
+    crate::definitions::define(word).map(String::from)
+}
+
+#[cfg(not(feature = "definitions"))]
+fn bundled_definition(_word: &str) -> Option<String> {
+    None
+}
+
+#[cfg(feature = "dictionary")]
+fn online_definition(word: &str, settings: &Settings) -> Option<String> {
+    settings
+        .define
+        .then(|| crate::dictionary::lookup_definition(word))
+        .flatten()
+}
+
+#[cfg(not(feature = "dictionary"))]
+fn online_definition(_word: &str, _settings: &Settings) -> Option<String> {
+    None
+}
+
+/// Information about the secret word shown to the user during the game
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Clues {
+    /// The word's hints, which are revealed one at a time
+    hints: Vec<String>,
+    category: Option<String>,
+    /// The word's part of speech, shown alongside the hint
+    part_of_speech: Option<String>,
+}
+
+/// Set up a game for the secret word `secret`, returning the game, the clues
+/// to show, and whether the word was truncated to fit the length limit
+fn start_word(secret: WordWithHint, settings: &Settings) -> anyhow::Result<(Hangman, Clues, bool)> {
+    let WordWithHint {
+        mut word,
+        hint,
+        category,
+        part_of_speech,
+        more_hints,
+        ..
+    } = secret;
+    let truncated = settings.length_limit.apply(&mut word)?;
+    let mut game = Hangman::new(word, ASCII_ALPHABET)?;
+    game.set_max_misses(settings.max_misses);
+    let clues = if settings.show_hint {
+        Clues {
+            hints: hint.into_iter().chain(more_hints).collect(),
+            category,
+            part_of_speech,
+        }
+    } else {
+        Clues {
+            category,
+            ..Clues::default()
+        }
+    };
+    Ok((game, clues, truncated))
+}
+
+/// Controller for a game in which the user and the computer take turns
+/// guessing letters in the same secret word, each with their own gallows
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct DuelController {
+    duel: Duel,
+    /// The clues to the secret word, of which only the first hint is shown
+    clues: Clues,
+    settings: Settings,
+    /// The themes defined in the configuration file, for `:theme`
+    themes: BTreeMap<String, ThemeSpec>,
+    truncated: bool,
+    solver: Solver,
+    assist: bool,
+    /// A definition of the secret word, once the duel is over
+    definition: Option<String>,
+    /// Which phrasing of the current message to show, picked at random for
+    /// each round
+    flavor: usize,
+    /// The random number generator for picking `flavor`
+    flavors: StdRng,
+    /// A description of where the secret word came from
+    source: String,
+}
+
+impl DuelController {
+    pub(crate) fn new(
+        fetched: Fetched,
+        settings: Settings,
+        themes: BTreeMap<String, ThemeSpec>,
+    ) -> anyhow::Result<DuelController> {
+        let Fetched {
+            secret,
+            list,
+            rng: flavors,
+            source,
+            ..
+        } = fetched;
+        let (game, clues, truncated) = start_word(secret, &settings)?;
+        let max_misses = settings.max_misses.unwrap_or(DEFAULT_MAX_MISSES);
+        Ok(DuelController {
+            duel: Duel::new(game, max_misses),
+            clues,
+            settings,
+            themes,
+            truncated,
+            solver: list
+                .as_ref()
+                .map(|list| Solver::new(list.get()))
+                .unwrap_or_default(),
+            assist: settings.assist,
+            definition: None,
+            flavor: 0,
+            flavors,
+            source,
+        })
+    }
+
+    /// Play the duel in the terminal.  Returns the outcome from the user's
+    /// point of view, or `None` if the user quit before the duel ended.
+    pub(crate) fn run(mut self) -> anyhow::Result<Option<Fate>> {
+        let mut word_display = word_display(self.duel.game());
+        let mut message = if self.truncated {
+            Message::StartTruncated {
+                max: self.settings.length_limit.max,
+            }
+        } else {
+            Message::Start
+        };
+        require_terminal()?;
+        let mut screen = Screen::new(
+            io::stdout(),
+            self.content(word_display.clone(), message),
+            self.settings.theme,
+            std::mem::take(&mut self.themes),
+            self.settings.mouse,
+            self.settings.cursor_keys,
+        )?;
+        screen.draw()?;
+        loop {
+            match screen.read_input()? {
+                Input::Guess(guess) => {
+                    (word_display, message) = self.round(guess);
+                    self.flavor = self.flavors.gen();
+                    if self.duel.winner().is_some() {
+                        self.definition = definition(self.duel.game(), &self.settings);
+                    }
+                    screen.update(self.content(word_display.clone(), message))?;
+                    if self.duel.winner().is_some() {
+                        screen.pause()?;
+                        break;
+                    }
+                }
+                Input::ToggleAssist => {
+                    self.assist = !self.assist;
+                    screen.notify(on_off("Assist mode", self.assist));
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                // Both sides guess one letter per round
+                Input::GuessWord(_)
+                | Input::NextHint
+                | Input::NewWord
+                | Input::Enter
+                | Input::Backspace => screen.beep()?,
+                // Guesses aren't timed in duels
+                Input::TimeUp => (),
+                Input::Help => screen.show_help(&self.help())?,
+                Input::Unfocused => screen.wait_for_focus(self.settings.hide_unfocused)?,
+                Input::Suspend => screen.suspend()?,
+                Input::Pause => {
+                    match screen.pause_menu(false, true)? {
+                        MenuChoice::Resume | MenuChoice::NewWord => (),
+                        MenuChoice::ToggleAssist => {
+                            self.assist = !self.assist;
+                            screen.notify(on_off("Assist mode", self.assist));
+                        }
+                        MenuChoice::ToggleBigWord => {
+                            self.settings.big_word = !self.settings.big_word;
+                            screen.notify(on_off("Large letters", self.settings.big_word));
+                        }
+                        MenuChoice::Quit => {
+                            if screen.confirm_quit()? {
+                                break;
+                            }
+                        }
+                    }
+                    screen.update(self.content(word_display.clone(), message))?;
+                }
+                Input::Quit => {
+                    if screen.confirm_quit()? {
+                        break;
+                    }
+                }
+            }
+        }
+        drop(screen);
+        if self.settings.reveal_on_quit && self.duel.winner().is_none() {
+            print_revealed(self.duel.game());
+        }
+        Ok(self.duel.fate())
+    }
+
+    /// Returns the contents of the help overlay
+    fn help(&self) -> Help {
+        let mut keys = self.settings.guessing_keys();
+        keys.push(("Tab", "Turn assist mode on or off"));
+        keys.extend(Help::common_keys());
+        let max_misses = self.settings.max_misses.unwrap_or(DEFAULT_MAX_MISSES);
+        Help {
+            keys,
+            rules: vec![
+                String::from("Mode: duel against the computer"),
+                String::from("You & the computer take turns guessing letters"),
+                format!("Each player loses after {max_misses} incorrect guesses"),
+            ],
+            source: Some(self.source.clone()),
+        }
+    }
+
+    /// Process a guess by the user followed, if the duel is still on, by a
+    /// guess from the computer, returning the resulting word display and
+    /// message
+    fn round(&mut self, guess: char) -> (Vec<CharDisplay>, Message) {
+        let yours = match self.duel.guess(Side::Human, guess) {
+            Response::GoodGuess { guess, won, .. } => {
+                let mut word_display = word_display(self.duel.game());
+                highlight(&mut word_display, guess);
+                if won {
+                    return (word_display, Message::Won);
+                }
+                self.last_guess()
+            }
+            Response::BadGuess {
+                lost: Some(Lost { word }),
+                ..
+            } => {
+                let mut word_display = word_display(self.duel.game());
+                reveal(&mut word_display, word);
+                return (word_display, Message::Lost);
+            }
+            Response::BadGuess { lost: None, .. } => self.last_guess(),
+            Response::AlreadyGuessed { guess } => {
+                return (
+                    word_display(self.duel.game()),
+                    Message::AlreadyGuessed { guess },
+                )
+            }
+            Response::InvalidGuess { guess } => {
+                return (
+                    word_display(self.duel.game()),
+                    Message::InvalidGuess { guess },
+                )
+            }
+            Response::GameOver => {
+                return (
+                    word_display(self.duel.game()),
+                    Message::InvalidGuess { guess },
+                )
+            }
+        };
+        let mut word_display = word_display(self.duel.game());
+        if yours.is_hit() {
+            highlight(&mut word_display, yours.letter);
+        }
+        let Some(theirs) = self.solver.suggest(self.duel.game()) else {
+            // There is always an unguessed letter in the word while the duel
+            // is on, so this can't happen.
+            return (word_display, Message::Start);
+        };
+        let message = match self.duel.guess(Side::Computer, theirs) {
+            Response::GoodGuess {
+                guess, won: true, ..
+            } => {
+                highlight(&mut word_display, guess);
+                Message::ComputerWon { guess }
+            }
+            Response::GoodGuess { guess, .. } => {
+                highlight(&mut word_display, guess);
+                Message::DuelRound {
+                    yours,
+                    theirs: self.last_guess(),
+                }
+            }
+            Response::BadGuess {
+                guess,
+                lost: Some(Lost { word }),
+            } => {
+                reveal(&mut word_display, word);
+                Message::ComputerHanged { guess }
+            }
+            Response::BadGuess { lost: None, .. } => Message::DuelRound {
+                yours,
+                theirs: self.last_guess(),
+            },
+            // The computer only guesses unguessed letters from the alphabet,
+            // so this can't happen.
+            _ => Message::InvalidGuess { guess: theirs },
+        };
+        (word_display, message)
+    }
+
+    /// Returns the most recent guess recorded in the duel's history
+    fn last_guess(&self) -> Guess {
+        self.duel
+            .game()
+            .history()
+            .last()
+            .copied()
+            .expect("history should not be empty after a correct or incorrect guess")
+    }
+
+    fn content(&self, word_display: Vec<CharDisplay>, message: Message) -> Content {
+        let game = self.duel.game();
+        Content {
+            hint: self.clues.hints.first().cloned(),
+            category: self.clues.category.clone(),
+            part_of_speech: self.clues.part_of_speech.clone(),
+            opponent: Some(self.settings.figure(self.duel.gallows(Side::Computer))),
+            suggestion: (self.assist && self.duel.winner().is_none())
+                .then(|| self.solver.suggest(game))
+                .flatten(),
+            candidates: (self.settings.cheat_meter && !self.solver.is_empty())
+                .then(|| self.solver.candidates(game).count()),
+            flavor: self.flavor,
+            definition: self.definition.clone(),
+            title: Title {
+                mode: Some("duel"),
+                won: self.duel.winner().map(|side| side == Side::Human),
+                misses_remaining: Some(self.duel.misses_remaining(Side::Human)),
+            },
+            ..self.settings.content(
+                self.settings.figure(self.duel.gallows(Side::Human)),
+                guess_options(game),
                 word_display,
                 message,
-            };
-            screen.update(content)?;
-            if self.game.fate().is_some() {
+            )
+        }
+    }
+}
+
+/// Controller for a game of reverse Hangman, in which the user thinks of a
+/// word and the computer tries to guess it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ReverseController {
+    /// The game, once the user has entered the length of their word
+    game: Option<ReverseGame>,
+    settings: Settings,
+    /// The themes defined in the configuration file, for `:theme`
+    themes: BTreeMap<String, ThemeSpec>,
+    solver: Solver,
+    /// The computer's current guess, awaiting the user's answer
+    guess: Option<char>,
+    /// The text that the user has typed in response to the current prompt
+    input: String,
+    message: Message,
+}
+
+impl ReverseController {
+    /// The maximum number of characters that can be typed at a prompt
+    const MAX_INPUT: usize = 40;
+
+    /// Create a controller in which the computer draws on the words in `list`
+    /// when choosing its guesses
+    pub(crate) fn new(
+        list: &WordList,
+        settings: Settings,
+        themes: BTreeMap<String, ThemeSpec>,
+    ) -> ReverseController {
+        ReverseController {
+            game: None,
+            settings,
+            themes,
+            solver: Solver::new(list),
+            guess: None,
+            input: String::new(),
+            message: Message::ReverseStart,
+        }
+    }
+
+    /// Play the game in the terminal
+    pub(crate) fn run(mut self) -> anyhow::Result<()> {
+        require_terminal()?;
+        let mut screen = Screen::new(
+            io::stdout(),
+            self.content(),
+            self.settings.theme,
+            std::mem::take(&mut self.themes),
+            self.settings.mouse,
+            // The letter grid shows the computer's guesses, and Enter is
+            // needed for submitting answers
+            false,
+        )?;
+        screen.draw()?;
+        loop {
+            match screen.read_input()? {
+                Input::Guess(ch) if self.input.len() < Self::MAX_INPUT => {
+                    if ch.is_ascii_digit() || ch == ' ' || ch == ',' {
+                        self.input.push(ch);
+                    } else if self.input.is_empty()
+                        && self.guess.is_some()
+                        && ch.eq_ignore_ascii_case(&'n')
+                    {
+                        // "n" for "no" is a synonym for an empty answer
+                        self.submit();
+                    } else {
+                        screen.beep()?;
+                        continue;
+                    }
+                }
+                // Pasted answers are entered as though typed
+                Input::GuessWord(text)
+                    if self.input.len() + text.len() <= Self::MAX_INPUT
+                        && text
+                            .chars()
+                            .all(|ch| ch.is_ascii_digit() || ch == ' ' || ch == ',') =>
+                {
+                    self.input.push_str(&text);
+                }
+                Input::Backspace => {
+                    if self.input.pop().is_none() {
+                        screen.beep()?;
+                        continue;
+                    }
+                }
+                Input::Enter => self.submit(),
+                Input::Guess(_)
+                | Input::GuessWord(_)
+                | Input::ToggleAssist
+                | Input::NextHint
+                | Input::NewWord => {
+                    screen.beep()?;
+                    continue;
+                }
+                // The user's answers aren't timed
+                Input::TimeUp => continue,
+                Input::Help => {
+                    screen.show_help(&self.help())?;
+                    continue;
+                }
+                Input::Unfocused => {
+                    screen.wait_for_focus(self.settings.hide_unfocused)?;
+                    continue;
+                }
+                Input::Suspend => {
+                    screen.suspend()?;
+                    continue;
+                }
+                Input::Pause => match screen.pause_menu(false, false)? {
+                    MenuChoice::Resume | MenuChoice::NewWord | MenuChoice::ToggleAssist => (),
+                    MenuChoice::ToggleBigWord => {
+                        self.settings.big_word = !self.settings.big_word;
+                        screen.notify(on_off("Large letters", self.settings.big_word));
+                    }
+                    MenuChoice::Quit => {
+                        if screen.confirm_quit()? {
+                            break;
+                        }
+                    }
+                },
+                Input::Quit => {
+                    if screen.confirm_quit()? {
+                        break;
+                    }
+                    continue;
+                }
+            }
+            screen.update(self.content())?;
+            if self.message.is_game_over() {
                 screen.pause()?;
                 break;
             }
@@ -76,22 +1505,181 @@ impl Controller {
         Ok(())
     }
 
-    fn guess_options(&self) -> Vec<Option<char>> {
-        self.game
-            .guessed()
-            .iter()
-            .map(|(&ch, &b)| (!b).then_some(ch))
-            .collect()
+    /// Returns the contents of the help overlay
+    fn help(&self) -> Help {
+        let mut keys = vec![
+            ("0-9", "Type the positions of the letter in your word"),
+            ("Space, ,", "Separate the positions"),
+            ("N", "Answer that the letter isn't in your word"),
+            ("Enter", "Submit your answer"),
+            ("Backspace", "Delete the last character typed"),
+        ];
+        keys.extend(Help::common_keys());
+        let max_misses = self.settings.max_misses.unwrap_or(DEFAULT_MAX_MISSES);
+        Help {
+            keys,
+            rules: vec![
+                String::from("Mode: reverse, in which the computer guesses your word"),
+                format!("The computer loses after {max_misses} incorrect guesses"),
+            ],
+            source: None,
+        }
     }
 
-    fn word_display(&self) -> Vec<CharDisplay> {
-        self.game
-            .known_letters()
-            .iter()
-            .map(|&opt| match opt {
-                Some(ch) => CharDisplay::Plain(ch),
-                None => CharDisplay::Blank,
-            })
-            .collect()
+    /// Process the text that the user has typed in response to the current
+    /// prompt
+    fn submit(&mut self) {
+        let input = std::mem::take(&mut self.input);
+        let (Some(game), Some(guess)) = (&mut self.game, self.guess) else {
+            let max = self.settings.length_limit.max;
+            match input.trim().parse::<usize>() {
+                Ok(length @ 1..) if length <= max => {
+                    let max_misses = self.settings.max_misses.unwrap_or(DEFAULT_MAX_MISSES);
+                    self.game = Some(ReverseGame::new(length, ASCII_ALPHABET, max_misses));
+                    self.next_guess();
+                }
+                _ => self.message = Message::ReverseBadLength { max },
+            }
+            return;
+        };
+        let Ok(positions) = input
+            .split([' ', ','])
+            .filter(|s| !s.is_empty())
+            .map(str::parse::<usize>)
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            self.message = Message::ReverseNotNumbers { guess };
+            return;
+        };
+        if let Err(error) = game.answer(guess, &positions) {
+            self.message = Message::ReverseBadAnswer { guess, error };
+            return;
+        }
+        match game.winner() {
+            Some(Side::Computer) => self.message = Message::ReverseSolved,
+            Some(Side::Human) => self.message = Message::ReverseHanged,
+            None => self.next_guess(),
+        }
+    }
+
+    /// Have the computer choose its next guess and ask the user about it
+    fn next_guess(&mut self) {
+        let Some(game) = &mut self.game else {
+            return;
+        };
+        self.guess = self.solver.suggest(game);
+        if let Some(guess) = self.guess {
+            self.message = Message::ReverseAsk { guess };
+        } else {
+            game.concede();
+            self.message = Message::ReverseStumped;
+        }
+    }
+
+    fn content(&self) -> Content {
+        let (figure, guess_options, word_display) = match &self.game {
+            Some(game) => (
+                game.gallows(),
+                guess_options(game),
+                game.known_letters()
+                    .iter()
+                    .map(|&opt| match opt {
+                        Some(ch) if Some(ch) == self.guess => CharDisplay::Highlighted(ch),
+                        Some(ch) => CharDisplay::Plain(ch),
+                        None => CharDisplay::Blank,
+                    })
+                    .collect(),
+            ),
+            None => (
+                Gallows::Start,
+                ASCII_ALPHABET.chars().map(GuessOption::Open).collect(),
+                Vec::new(),
+            ),
+        };
+        Content {
+            candidates: self
+                .settings
+                .cheat_meter
+                .then(|| {
+                    self.game
+                        .as_ref()
+                        .map(|game| self.solver.candidates(game).count())
+                })
+                .flatten(),
+            input: (!self.message.is_game_over()).then(|| self.input.clone()),
+            title: Title {
+                mode: Some("reverse"),
+                won: self
+                    .game
+                    .as_ref()
+                    .and_then(ReverseGame::winner)
+                    .map(|side| side == Side::Human),
+                misses_remaining: self.game.as_ref().map(ReverseGame::misses_remaining),
+            },
+            ..self.settings.content(
+                self.settings.figure(figure),
+                guess_options,
+                word_display,
+                self.message,
+            )
+        }
+    }
+}
+
+/// Returns the letters of `game`'s alphabet, with those that have already
+/// been guessed replaced by `None`
+fn guess_options<P: Puzzle>(game: &P) -> Vec<GuessOption> {
+    game.guessed()
+        .iter()
+        .map(|(&ch, &b)| {
+            if b {
+                GuessOption::Guessed(ch)
+            } else {
+                GuessOption::Open(ch)
+            }
+        })
+        .collect()
+}
+
+/// Returns the secret word of `game` as currently revealed
+fn word_display(game: &Hangman) -> Vec<CharDisplay> {
+    game.known_letters()
+        .iter()
+        .map(|&opt| match opt {
+            Some(ch) => CharDisplay::Plain(ch),
+            None => CharDisplay::Blank,
+        })
+        .collect()
+}
+
+/// Returns a notification that the setting named `name` was turned on or off
+fn on_off(name: &str, on: bool) -> String {
+    format!("{name} {}", if on { "on" } else { "off" })
+}
+
+/// Print the secret word of `game`, which the user quit without finishing
+fn print_revealed(game: &Hangman) {
+    let Lost { word } = game.reveal();
+    println!("The word was: {}", word.into_iter().collect::<String>());
+}
+
+/// Highlight the occurrences of `guess` (in any case) in `word_display`
+fn highlight(word_display: &mut [CharDisplay], guess: char) {
+    for cd in word_display {
+        if let CharDisplay::Plain(ch) = *cd {
+            if normalize_char(ch) == guess {
+                *cd = CharDisplay::Highlighted(ch);
+            }
+        }
+    }
+}
+
+/// Fill in the blanks in `word_display` with the corresponding characters of
+/// `word`, highlighted
+fn reveal(word_display: &mut [CharDisplay], word: Vec<char>) {
+    for (ch, cd) in std::iter::zip(word, word_display) {
+        if *cd == CharDisplay::Blank {
+            *cd = CharDisplay::Highlighted(ch);
+        }
     }
 }
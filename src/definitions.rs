@@ -0,0 +1,43 @@
+/// The bundled definitions, one per line as a word and its definition
+/// separated by a tab, with `#` comments
+static DEFINITIONS: &str = include_str!("definitions.txt");
+
+/// Returns the bundled definition of `word`, if there is one, ignoring case
+pub(crate) fn define(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    DEFINITIONS
+        .lines()
+        .filter(|ln| !ln.starts_with('#'))
+        .find_map(|ln| {
+            let (w, definition) = ln.split_once('\t')?;
+            (w == word).then_some(definition)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::Pool;
+
+    #[test]
+    fn test_define() {
+        assert_eq!(
+            define("Zebra"),
+            Some("An African wild horse with black & white stripes")
+        );
+        assert_eq!(define("xylophone"), None);
+    }
+
+    #[test]
+    fn test_builtin_words_defined() {
+        for pool in [Pool::Standard, Pool::Kids] {
+            for w in pool.list().iter() {
+                assert!(
+                    define(w.word.as_ref()).is_some(),
+                    "{:?} has no definition",
+                    w.word
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use crate::words::{Word, WordFilter, WordWithHint};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// API returning a JSON array of random English words
+static RANDOM_WORDS_URL: &str = "https://random-word-api.herokuapp.com/word";
+
+/// API returning dictionary entries for the English word appended to the URL
+static DEFINITIONS_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en";
+
+/// The number of random words to request at once, so that one accepted by the
+/// word filter can usually be found with a single request
+const BATCH_SIZE: usize = 20;
+
+/// How long to wait on a request to either API, so that a stalled connection
+/// can't hold up the start or end of a game for long
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fetch a random word accepted by `filter` from an online dictionary, with
+/// its definition (if one can be found) as the hint
+pub(crate) fn random_word(filter: &WordFilter) -> anyhow::Result<WordWithHint> {
+    random_word_from(RANDOM_WORDS_URL, DEFINITIONS_URL, filter)
+}
+
+fn random_word_from(
+    words_url: &str,
+    definitions_url: &str,
+    filter: &WordFilter,
+) -> anyhow::Result<WordWithHint> {
+    let src = ureq::get(words_url)
+        .timeout(TIMEOUT)
+        .query("number", &BATCH_SIZE.to_string())
+        .call()
+        .context("failed to fetch random words")?
+        .into_string()
+        .context("failed to read random words")?;
+    let words =
+        serde_json::from_str::<Vec<String>>(&src).context("failed to parse random words")?;
+    let Some(mut secret) = words
+        .into_iter()
+        .filter_map(|w| w.parse::<Word>().ok())
+        .map(WordWithHint::from)
+        .find(|w| filter.accepts(w))
+    else {
+        anyhow::bail!("No random words from dictionary meet the selection criteria");
+    };
+    // A word without a hint is still playable, so failing to find a definition
+    // isn't an error.
+    secret.hint = definition(definitions_url, secret.word.as_ref()).unwrap_or_default();
+    Ok(secret)
+}
+
+/// Look up a definition of `word` to show once the game is over, consulting
+/// the definitions looked up in previous games first.  Any failure, such as
+/// not being able to reach the dictionary, results in `None`.
+pub(crate) fn lookup_definition(word: &str) -> Option<String> {
+    lookup_definition_with_cache(DEFINITIONS_URL, word, DefinitionCache::path().as_deref())
+}
+
+fn lookup_definition_with_cache(
+    definitions_url: &str,
+    word: &str,
+    cache_path: Option<&Path>,
+) -> Option<String> {
+    let key = word.to_lowercase();
+    let mut cache = cache_path
+        .and_then(DefinitionCache::load)
+        .unwrap_or_default();
+    if let Some(found) = cache.0.get(&key) {
+        return found.clone();
+    }
+    // Don't cache failures to reach the dictionary, so that the word can be
+    // looked up again once back online
+    let found = definition(definitions_url, word).ok()?;
+    if let Some(path) = cache_path {
+        cache.0.insert(key, found.clone());
+        // Failing to save the cache only means looking the word up again
+        // next time
+        let _ = cache.save(path);
+    }
+    found
+}
+
+/// Look up the first definition of `word` that doesn't give the word away.
+/// Returns `None` if the dictionary doesn't have the word.
+fn definition(definitions_url: &str, word: &str) -> anyhow::Result<Option<String>> {
+    let url = format!("{definitions_url}/{}", encode_segment(word));
+    let response = match ureq::get(&url).timeout(TIMEOUT).call() {
+        Ok(r) => r,
+        Err(ureq::Error::Status(404, _)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let src = response.into_string()?;
+    let entries = serde_json::from_str::<Vec<Entry>>(&src)?;
+    let word = word.to_lowercase();
+    Ok(entries
+        .into_iter()
+        .flat_map(|e| e.meanings)
+        .flat_map(|m| m.definitions)
+        .map(|d| d.definition)
+        .find(|d| !d.to_lowercase().contains(&word)))
+}
+
+/// Percent-encode `s` for use as a single segment of a URL's path
+fn encode_segment(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(char::from(b));
+        } else {
+            write!(encoded, "%{b:02X}").expect("writing to a String should not fail");
+        }
+    }
+    encoded
+}
+
+/// Definitions (or the lack thereof) of words looked up at the end of previous
+/// games, stored as JSON in the user's cache directory and keyed by lowercase
+/// word
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+struct DefinitionCache(BTreeMap<String, Option<String>>);
+
+impl DefinitionCache {
+    /// Returns the path at which the cache is stored, or `None` if the
+    /// user's cache directory could not be determined
+    fn path() -> Option<PathBuf> {
+        Some(dirs::cache_dir()?.join("hangman").join("definitions.json"))
+    }
+
+    /// Read the cache from `path`, returning `None` if it does not exist or
+    /// cannot be read
+    fn load(path: &Path) -> Option<DefinitionCache> {
+        let src = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&src).ok()
+    }
+
+    /// Write the cache to `path`, creating parent directories as needed
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// The parts of a dictionary entry returned by the definitions API that we
+/// care about
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+struct Entry {
+    meanings: Vec<Meaning>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+struct Meaning {
+    definitions: Vec<Definition>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+struct Definition {
+    definition: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::serve;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_random_word() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let words = serve(&listener, "200 OK", "", r#"["ox","lantern","zephyr"]"#);
+            let definitions = serve(
+                &listener,
+                "200 OK",
+                "",
+                concat!(
+                    r#"[{"word":"lantern","meanings":[{"definitions":["#,
+                    r#"{"definition":"A lantern, as used by sailors."},"#,
+                    r#"{"definition":"A case for carrying a light."}"#,
+                    r#"]}]}]"#,
+                ),
+            );
+            (words, definitions)
+        });
+        let filter = WordFilter {
+            min_length: Some(4),
+            ..WordFilter::default()
+        };
+        let secret =
+            random_word_from(&format!("{base}/word"), &format!("{base}/en"), &filter).unwrap();
+        let (words, definitions) = server.join().unwrap();
+        assert_eq!(secret.word.as_ref(), "lantern");
+        assert_eq!(secret.hint.as_deref(), Some("A case for carrying a light."));
+        assert_eq!(words[0], "GET /word?number=20 HTTP/1.1");
+        assert_eq!(definitions[0], "GET /en/lantern HTTP/1.1");
+    }
+
+    #[test]
+    fn test_missing_definition() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            serve(&listener, "200 OK", "", r#"["lantern"]"#);
+            serve(&listener, "404 Not Found", "", "{}");
+        });
+        let secret = random_word_from(
+            &format!("{base}/word"),
+            &format!("{base}/en"),
+            &WordFilter::default(),
+        )
+        .unwrap();
+        server.join().unwrap();
+        assert_eq!(secret.word.as_ref(), "lantern");
+        assert_eq!(secret.hint, None);
+    }
+
+    #[test]
+    fn test_lookup_definition_cached() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let cache_path = tmpdir.path().join("definitions.json");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/en", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            serve(
+                &listener,
+                "200 OK",
+                "",
+                r#"[{"meanings":[{"definitions":[{"definition":"A light."}]}]}]"#,
+            );
+            serve(&listener, "404 Not Found", "", "{}");
+        });
+        assert_eq!(
+            lookup_definition_with_cache(&base, "Lantern", Some(&cache_path)),
+            Some(String::from("A light."))
+        );
+        assert_eq!(
+            lookup_definition_with_cache(&base, "xyzzy", Some(&cache_path)),
+            None
+        );
+        server.join().unwrap();
+        // Both lookups are now answered from the cache without contacting
+        // the server
+        assert_eq!(
+            lookup_definition_with_cache(&base, "lantern", Some(&cache_path)),
+            Some(String::from("A light."))
+        );
+        assert_eq!(
+            lookup_definition_with_cache(&base, "xyzzy", Some(&cache_path)),
+            None
+        );
+        let cache = DefinitionCache::load(&cache_path).unwrap();
+        assert_eq!(cache.0.len(), 2);
+    }
+
+    #[test]
+    fn test_lookup_definition_encoded() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/en", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || serve(&listener, "404 Not Found", "", "{}"));
+        assert_eq!(lookup_definition_with_cache(&base, "AC/DC?", None), None);
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /en/AC%2FDC%3F HTTP/1.1");
+    }
+
+    #[test]
+    fn test_encode_segment() {
+        assert_eq!(encode_segment("lantern"), "lantern");
+        assert_eq!(encode_segment("ice cream"), "ice%20cream");
+        assert_eq!(encode_segment("what#not"), "what%23not");
+        assert_eq!(encode_segment("café"), "caf%C3%A9");
+    }
+
+    #[test]
+    fn test_lookup_definition_offline() {
+        // Nothing is listening on this port once the listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/en", listener.local_addr().unwrap());
+        drop(listener);
+        let tmpdir = tempfile::tempdir().unwrap();
+        let cache_path = tmpdir.path().join("definitions.json");
+        assert_eq!(
+            lookup_definition_with_cache(&base, "lantern", Some(&cache_path)),
+            None
+        );
+        assert!(!cache_path.exists());
+    }
+}
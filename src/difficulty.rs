@@ -0,0 +1,94 @@
+use crate::model::DEFAULT_MAX_MISSES;
+use crate::words::{FrequencyTier, Pool, WordFilter};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// A named difficulty level selectable with `--difficulty`
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns the built-in settings for the difficulty level.  These can be
+    /// overridden in the configuration file.
+    pub(crate) fn preset(self) -> Preset {
+        match self {
+            Difficulty::Easy => Preset {
+                min_length: Some(3),
+                max_length: Some(6),
+                misses: 8,
+                show_hint: true,
+                pool: Pool::Kids,
+                tier: FrequencyTier::Common,
+            },
+            Difficulty::Normal => Preset {
+                min_length: None,
+                max_length: None,
+                misses: DEFAULT_MAX_MISSES,
+                show_hint: true,
+                pool: Pool::Standard,
+                tier: FrequencyTier::All,
+            },
+            Difficulty::Hard => Preset {
+                min_length: Some(4),
+                max_length: None,
+                misses: 4,
+                show_hint: false,
+                pool: Pool::Standard,
+                tier: FrequencyTier::Rare,
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for Difficulty {
+    type Err = ParseDifficultyError;
+
+    fn from_str(s: &str) -> Result<Difficulty, ParseDifficultyError> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(ParseDifficultyError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"difficulty must be "easy", "normal", or "hard""#)]
+pub(crate) struct ParseDifficultyError;
+
+/// The settings bundled by a [`Difficulty`] level
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Preset {
+    /// Minimum length (in characters) of words selected from a word list
+    pub(crate) min_length: Option<usize>,
+    /// Maximum length (in characters) of words selected from a word list
+    pub(crate) max_length: Option<usize>,
+    /// Number of incorrect guesses after which the user loses
+    pub(crate) misses: usize,
+    /// Whether to show the word's hint (if any)
+    pub(crate) show_hint: bool,
+    /// Which builtin word list to use when no other word source is given
+    pub(crate) pool: Pool,
+    /// Which frequency tier to take words from when the word list is ranked
+    /// by frequency (see `--ranked`)
+    pub(crate) tier: FrequencyTier,
+}
+
+impl Preset {
+    /// Returns `selection` restricted to words within the preset's length
+    /// range
+    pub(crate) fn word_filter(&self, selection: WordFilter) -> WordFilter {
+        WordFilter {
+            min_length: self.min_length,
+            max_length: self.max_length,
+            ..selection
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use crate::model::Gallows;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use std::ffi::OsStr;
+use std::fmt::Write;
+
+/// A protocol with which a terminal can display images
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Graphics {
+    /// The Kitty graphics protocol, supported by Kitty, Ghostty, & others
+    Kitty,
+    /// Sixel graphics, supported by foot, mlterm, iTerm2, & others
+    Sixel,
+}
+
+impl Graphics {
+    /// Determine from the environment which graphics protocol, if any, the
+    /// terminal supports
+    pub(crate) fn detect() -> Option<Graphics> {
+        Graphics::from_env(
+            std::env::var_os("TERM").as_deref(),
+            std::env::var_os("TERM_PROGRAM").as_deref(),
+            std::env::var_os("KITTY_WINDOW_ID").is_some(),
+        )
+    }
+
+    fn from_env(
+        term: Option<&OsStr>,
+        term_program: Option<&OsStr>,
+        kitty_window: bool,
+    ) -> Option<Graphics> {
+        let term = term.map(OsStr::to_string_lossy).unwrap_or_default();
+        let term_program = term_program.map(OsStr::to_string_lossy).unwrap_or_default();
+        if kitty_window
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || matches!(&*term_program, "WezTerm" | "ghostty")
+        {
+            Some(Graphics::Kitty)
+        } else if term.starts_with("foot")
+            || term.starts_with("mlterm")
+            || term.contains("sixel")
+            || term_program == "iTerm.app"
+        {
+            Some(Graphics::Sixel)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the size in pixels at which to draw a figure that takes up
+    /// `columns` × `rows` cells.  Kitty scales images to fit the cells they're
+    /// placed in, but Sixel images are drawn at their actual size, so they're
+    /// kept small enough to fit in the cells on most terminals.
+    pub(crate) fn image_size(self, columns: usize, rows: usize) -> (usize, usize) {
+        match self {
+            Graphics::Kitty => (columns * 10, rows * 20),
+            Graphics::Sixel => (columns * 8, rows * 16),
+        }
+    }
+
+    /// Returns the escape sequence for drawing `bitmap` at the cursor
+    /// position, scaled to `columns` × `rows` cells where possible, with its
+    /// plain pixels in the color `plain` and its highlighted pixels in the
+    /// color `marked`
+    pub(crate) fn encode(
+        self,
+        bitmap: &Bitmap,
+        columns: usize,
+        rows: usize,
+        plain: (u8, u8, u8),
+        marked: (u8, u8, u8),
+    ) -> String {
+        match self {
+            Graphics::Kitty => bitmap.kitty(columns, rows, plain, marked),
+            Graphics::Sixel => bitmap.sixel(plain, marked),
+        }
+    }
+
+    /// Returns the escape sequence for removing any images previously drawn
+    /// with this protocol that text drawn over them wouldn't replace
+    pub(crate) fn clear(self) -> &'static str {
+        match self {
+            Graphics::Kitty => "\x1B_Ga=d,d=A,q=2\x1B\\",
+            Graphics::Sixel => "",
+        }
+    }
+}
+
+/// A line segment between two points on the 80×100 grid on which figures are
+/// described
+type Segment = ((usize, usize), (usize, usize));
+
+/// A grid of pixels on which a figure is drawn as line segments, each pixel
+/// either blank or set, and set pixels either plain or highlighted
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Option<bool>>,
+}
+
+impl Bitmap {
+    /// The width & height of the grid on which segments are described
+    const GRID: (usize, usize) = (80, 100);
+
+    /// The bit for each dot in a Braille character, indexed by row & then
+    /// column
+    const BRAILLE_BITS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    fn new(width: usize, height: usize) -> Bitmap {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![None; width * height],
+        }
+    }
+
+    /// Draw the gallows in the state `gallows` on a bitmap of the given size,
+    /// with lines `thickness` pixels thick.  If `highlight` is true, the most
+    /// recently added body part is highlighted.
+    pub(crate) fn gallows(
+        gallows: Gallows,
+        highlight: bool,
+        width: usize,
+        height: usize,
+        thickness: usize,
+    ) -> Bitmap {
+        static GALLOWS: [Segment; 5] = [
+            ((0, 96), (50, 96)),
+            ((10, 96), (10, 4)),
+            ((10, 4), (60, 4)),
+            ((10, 20), (26, 4)),
+            ((60, 4), (60, 20)),
+        ];
+        static HEAD: [Segment; 8] = [
+            ((60, 20), (67, 23)),
+            ((67, 23), (70, 30)),
+            ((70, 30), (67, 37)),
+            ((67, 37), (60, 40)),
+            ((60, 40), (53, 37)),
+            ((53, 37), (50, 30)),
+            ((50, 30), (53, 23)),
+            ((53, 23), (60, 20)),
+        ];
+        static PARTS: [&[Segment]; Gallows::PARTS] = [
+            &HEAD,
+            &[((60, 40), (60, 68))],
+            &[((60, 48), (46, 60))],
+            &[((60, 48), (74, 60))],
+            &[((60, 68), (48, 88))],
+            &[((60, 68), (72, 88))],
+        ];
+        let (parts, _) = gallows.progress();
+        let mut bitmap = Bitmap::new(width, height);
+        for &segment in &GALLOWS {
+            bitmap.draw(segment, false, thickness);
+        }
+        for (k, &part) in PARTS.iter().enumerate().take(parts) {
+            for &segment in part {
+                bitmap.draw(segment, highlight && k + 1 == parts, thickness);
+            }
+        }
+        bitmap
+    }
+
+    /// Draw a line segment described on the 80×100 grid, scaled to the size
+    /// of the bitmap
+    fn draw(&mut self, (start, end): Segment, highlight: bool, thickness: usize) {
+        let scale = |(x, y): (usize, usize)| {
+            (
+                x * self.width.saturating_sub(thickness) / Bitmap::GRID.0,
+                y * self.height.saturating_sub(thickness) / Bitmap::GRID.1,
+            )
+        };
+        let (x0, y0) = scale(start);
+        let (x1, y1) = scale(end);
+        let steps = x0.abs_diff(x1).max(y0.abs_diff(y1)).max(1);
+        let lerp = |a: usize, b: usize, t: usize| {
+            if a <= b {
+                a + (b - a) * t / steps
+            } else {
+                a - (a - b) * t / steps
+            }
+        };
+        for t in 0..=steps {
+            let (x, y) = (lerp(x0, x1, t), lerp(y0, y1, t));
+            for dy in 0..thickness {
+                for dx in 0..thickness {
+                    self.set(x + dx, y + dy, highlight);
+                }
+            }
+        }
+    }
+
+    /// Set the pixel at column `x` & row `y`.  A pixel stays highlighted once
+    /// any highlighted line has been drawn through it.
+    fn set(&mut self, x: usize, y: usize, highlight: bool) {
+        if x < self.width && y < self.height {
+            let pixel = &mut self.pixels[y * self.width + x];
+            *pixel = Some(highlight || pixel.unwrap_or_default());
+        }
+    }
+
+    /// Returns the pixel at column `x` & row `y`, or `None` if it's blank or
+    /// out of bounds
+    fn get(&self, x: usize, y: usize) -> Option<bool> {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x]
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bitmap as lines of Braille characters, each of which shows
+    /// a block of 2×4 pixels, with characters containing highlighted pixels
+    /// in `marked_style` and the rest in `style`
+    pub(crate) fn braille(&self, style: Style, marked_style: Style) -> Vec<Line<'static>> {
+        (0..self.height)
+            .step_by(4)
+            .map(|y| {
+                let mut spans = Vec::new();
+                for x in (0..self.width).step_by(2) {
+                    let mut dots = 0u8;
+                    let mut marked = false;
+                    for (dy, bits) in Bitmap::BRAILLE_BITS.iter().enumerate() {
+                        for (dx, &bit) in bits.iter().enumerate() {
+                            if let Some(m) = self.get(x + dx, y + dy) {
+                                dots |= bit;
+                                marked |= m;
+                            }
+                        }
+                    }
+                    let ch = match dots {
+                        0 => ' ',
+                        d => char::from_u32(0x2800 + u32::from(d)).unwrap_or(' '),
+                    };
+                    let ch_style = if marked { marked_style } else { style };
+                    push_styled(&mut spans, ch.encode_utf8(&mut [0; 4]), ch_style);
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+
+    /// Returns the escape sequences for drawing the bitmap with the Kitty
+    /// graphics protocol, scaled to `columns` × `rows` cells.  The image is
+    /// sent as RGBA pixels, base64-encoded & split into chunks of no more
+    /// than 4096 bytes, as the protocol requires.
+    fn kitty(
+        &self,
+        columns: usize,
+        rows: usize,
+        plain: (u8, u8, u8),
+        marked: (u8, u8, u8),
+    ) -> String {
+        let mut rgba = Vec::with_capacity(self.pixels.len() * 4);
+        for &pixel in &self.pixels {
+            rgba.extend(match pixel {
+                Some(false) => [plain.0, plain.1, plain.2, 0xFF],
+                Some(true) => [marked.0, marked.1, marked.2, 0xFF],
+                None => [0; 4],
+            });
+        }
+        let payload = STANDARD.encode(rgba);
+        let mut chunks = payload.as_bytes().chunks(4096).peekable();
+        let mut out = String::new();
+        let mut first = true;
+        while let Some(chunk) = chunks.next() {
+            out.push_str("\x1B_G");
+            if first {
+                write!(
+                    out,
+                    "a=T,f=32,s={},v={},c={columns},r={rows},C=1,q=2,",
+                    self.width, self.height
+                )
+                .expect("writing to a String should not fail");
+                first = false;
+            }
+            let more = u8::from(chunks.peek().is_some());
+            write!(out, "m={more};").expect("writing to a String should not fail");
+            out.push_str(&String::from_utf8_lossy(chunk));
+            out.push_str("\x1B\\");
+        }
+        out
+    }
+
+    /// Returns the escape sequence for drawing the bitmap as a Sixel image.
+    /// Blank pixels are drawn in the terminal's background color so that the
+    /// image completely covers any image previously drawn in the same place.
+    fn sixel(&self, plain: (u8, u8, u8), marked: (u8, u8, u8)) -> String {
+        let percent = |c: u8| u16::from(c) * 100 / 255;
+        let mut out = format!("\x1BP0;0;0q\"1;1;{};{}", self.width, self.height);
+        for (register, (r, g, b)) in [(1, plain), (2, marked)] {
+            write!(
+                out,
+                "#{register};2;{};{};{}",
+                percent(r),
+                percent(g),
+                percent(b)
+            )
+            .expect("writing to a String should not fail");
+        }
+        for band in (0..self.height).step_by(6) {
+            if band > 0 {
+                out.push('-');
+            }
+            let mut first = true;
+            for (register, highlighted) in [(1, false), (2, true)] {
+                let sixels = (0..self.width)
+                    .map(|x| {
+                        (0..6).fold(0u8, |bits, dy| {
+                            if self.get(x, band + dy) == Some(highlighted) {
+                                bits | (1 << dy)
+                            } else {
+                                bits
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                if sixels.iter().all(|&bits| bits == 0) {
+                    continue;
+                }
+                if !first {
+                    out.push('$');
+                }
+                first = false;
+                write!(out, "#{register}").expect("writing to a String should not fail");
+                let mut run: Option<(u8, usize)> = None;
+                for bits in sixels.into_iter().map(Some).chain([None]) {
+                    match (run, bits) {
+                        (Some((b, n)), Some(bits)) if b == bits => run = Some((b, n + 1)),
+                        _ => {
+                            if let Some((b, n)) = run {
+                                let ch = char::from(63 + b);
+                                if n > 3 {
+                                    write!(out, "!{n}{ch}")
+                                        .expect("writing to a String should not fail");
+                                } else {
+                                    out.extend(std::iter::repeat_n(ch, n));
+                                }
+                            }
+                            run = bits.map(|b| (b, 1));
+                        }
+                    }
+                }
+            }
+        }
+        out.push_str("\x1B\\");
+        out
+    }
+}
+
+/// Append `s` to `spans` in the given style, extending the last span if it
+/// has the same style
+pub(crate) fn push_styled(spans: &mut Vec<Span<'static>>, s: &str, style: Style) {
+    match spans.last_mut() {
+        Some(span) if span.style == style => span.content.to_mut().push_str(s),
+        _ => spans.push(Span::styled(String::from(s), style)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    #[test]
+    fn test_detect_graphics() {
+        let s = |s| Some(OsStr::new(s));
+        for (term, term_program, kitty_window, graphics) in [
+            (s("xterm-kitty"), None, false, Some(Graphics::Kitty)),
+            (s("xterm-256color"), None, true, Some(Graphics::Kitty)),
+            (
+                s("xterm-256color"),
+                s("WezTerm"),
+                false,
+                Some(Graphics::Kitty),
+            ),
+            (s("foot"), None, false, Some(Graphics::Sixel)),
+            (
+                s("xterm-256color"),
+                s("iTerm.app"),
+                false,
+                Some(Graphics::Sixel),
+            ),
+            (s("xterm-256color"), s("Apple_Terminal"), false, None),
+            (None, None, false, None),
+        ] {
+            assert_eq!(
+                Graphics::from_env(term, term_program, kitty_window),
+                graphics
+            );
+        }
+    }
+
+    #[test]
+    fn test_bitmap_braille() {
+        let bitmap = Bitmap::gallows(Gallows::AddHead, true, 20, 40, 1);
+        let lines = bitmap.braille(Style::new(), Style::new().add_modifier(Modifier::BOLD));
+        assert_eq!(lines.len(), 10);
+        assert!(lines.iter().all(|line| line.width() == 10));
+        assert!(lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .any(|span| span.style == Style::new().add_modifier(Modifier::BOLD)));
+    }
+
+    #[test]
+    fn test_kitty() {
+        let bitmap = Bitmap::gallows(Gallows::Start, false, 80, 100, 2);
+        let out = Graphics::Kitty.encode(&bitmap, 8, 5, (255, 255, 255), (255, 0, 0));
+        assert!(out.starts_with("\x1B_Ga=T,f=32,s=80,v=100,c=8,r=5,C=1,q=2,m=1;"));
+        assert!(out.ends_with("\x1B\\"));
+        let chunks = out
+            .split("\x1B\\")
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+        // 80×100 RGBA pixels take up 42,668 bytes in base64
+        assert_eq!(chunks.len(), 11);
+        assert!(chunks.last().unwrap().starts_with("\x1B_Gm=0;"));
+    }
+
+    #[test]
+    fn test_sixel() {
+        let mut bitmap = Bitmap::new(5, 7);
+        bitmap.set(0, 0, false);
+        bitmap.set(1, 6, true);
+        for x in 0..5 {
+            bitmap.set(x, 1, false);
+        }
+        assert_eq!(
+            bitmap.sixel((255, 255, 255), (255, 0, 0)),
+            "\x1BP0;0;0q\"1;1;5;7#1;2;100;100;100#2;2;100;0;0#1B!4A-#2?@???\x1B\\"
+        );
+    }
+}
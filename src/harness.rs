@@ -0,0 +1,269 @@
+//! Helpers for driving the `hangman` binary under a pseudoterminal in
+//! end-to-end tests.
+//!
+//! This library is only populated when the `test-harness` feature is enabled.
+//! A [`Session`] is started with a [`SessionBuilder`]; keys can then be sent
+//! to the program, and the contents of the emulated terminal screen can be
+//! captured as [`Frame`]s.
+#![cfg(feature = "test-harness")]
+
+use anyhow::{bail, Context};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait between checks of the screen when waiting for something
+/// to appear
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The default amount of time to wait for something to appear on the screen
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Builder for configuring & spawning a [`Session`]
+#[derive(Clone, Debug)]
+pub struct SessionBuilder {
+    program: OsString,
+    args: Vec<OsString>,
+    env: Vec<(OsString, OsString)>,
+    rows: u16,
+    columns: u16,
+}
+
+impl SessionBuilder {
+    /// Prepare to run the program at `program` in an 80×24 terminal
+    pub fn new<S: AsRef<OsStr>>(program: S) -> SessionBuilder {
+        SessionBuilder {
+            program: program.as_ref().to_owned(),
+            args: Vec::new(),
+            env: Vec::new(),
+            rows: 24,
+            columns: 80,
+        }
+    }
+
+    /// Add a command-line argument
+    pub fn arg<S: AsRef<OsStr>>(mut self, arg: S) -> SessionBuilder {
+        self.args.push(arg.as_ref().to_owned());
+        self
+    }
+
+    /// Add multiple command-line arguments
+    pub fn args<I, S>(mut self, args: I) -> SessionBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args
+            .extend(args.into_iter().map(|s| s.as_ref().to_owned()));
+        self
+    }
+
+    /// Set an environment variable for the program
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> SessionBuilder {
+        self.env
+            .push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+        self
+    }
+
+    /// Set the size of the emulated terminal
+    pub fn size(mut self, rows: u16, columns: u16) -> SessionBuilder {
+        self.rows = rows;
+        self.columns = columns;
+        self
+    }
+
+    /// Start the program under a new pseudoterminal
+    pub fn spawn(self) -> anyhow::Result<Session> {
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: self.rows,
+                cols: self.columns,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .context("failed to open pseudoterminal")?;
+        let mut cmd = CommandBuilder::new(&self.program);
+        cmd.args(&self.args);
+        cmd.env("TERM", "xterm-256color");
+        for (key, value) in &self.env {
+            cmd.env(key, value);
+        }
+        let child = pair
+            .slave
+            .spawn_command(cmd)
+            .context("failed to spawn program")?;
+        drop(pair.slave);
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .context("failed to read from pseudoterminal")?;
+        let writer = pair
+            .master
+            .take_writer()
+            .context("failed to write to pseudoterminal")?;
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(self.rows, self.columns, 0)));
+        let parser2 = Arc::clone(&parser);
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n @ 1..) = reader.read(&mut buf) {
+                if let Ok(mut p) = parser2.lock() {
+                    p.process(&buf[..n]);
+                }
+            }
+        });
+        Ok(Session {
+            _master: pair.master,
+            writer,
+            child,
+            parser,
+        })
+    }
+}
+
+/// A running instance of a program attached to a pseudoterminal
+pub struct Session {
+    // Kept so that the pseudoterminal stays open for the life of the session
+    _master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    parser: Arc<Mutex<vt100::Parser>>,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Session {
+    /// Send the given text to the program as though it were typed
+    pub fn send_keys(&mut self, keys: &str) -> anyhow::Result<()> {
+        self.send_bytes(keys.as_bytes())
+    }
+
+    /// Send a single press of the Escape key
+    pub fn send_escape(&mut self) -> anyhow::Result<()> {
+        self.send_bytes(b"\x1B")
+    }
+
+    /// Send raw bytes to the program's terminal input
+    pub fn send_bytes(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        self.writer
+            .write_all(bytes)
+            .and_then(|()| self.writer.flush())
+            .context("failed to write to pseudoterminal")
+    }
+
+    /// Capture the current contents of the terminal screen
+    pub fn frame(&self) -> Frame {
+        let parser = match self.parser.lock() {
+            Ok(p) => p,
+            Err(e) => e.into_inner(),
+        };
+        let screen = parser.screen();
+        let (_, columns) = screen.size();
+        Frame {
+            lines: screen
+                .rows(0, columns)
+                .map(|ln| ln.trim_end().to_owned())
+                .collect(),
+        }
+    }
+
+    /// Returns true iff the program has switched the terminal to the
+    /// alternate screen
+    pub fn in_alternate_screen(&self) -> bool {
+        let parser = match self.parser.lock() {
+            Ok(p) => p,
+            Err(e) => e.into_inner(),
+        };
+        parser.screen().alternate_screen()
+    }
+
+    /// Returns the process ID of the program, if known
+    pub fn process_id(&self) -> Option<u32> {
+        self.child.process_id()
+    }
+
+    /// Wait up to `timeout` for a frame satisfying `pred` to appear on the
+    /// screen, and return it
+    pub fn wait_for_frame<F>(&self, timeout: Duration, mut pred: F) -> anyhow::Result<Frame>
+    where
+        F: FnMut(&Frame) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let frame = self.frame();
+            if pred(&frame) {
+                return Ok(frame);
+            }
+            if Instant::now() >= deadline {
+                bail!("timed out waiting for expected screen; last frame:\n{frame}");
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Wait up to [`DEFAULT_TIMEOUT`] for `text` to appear on the screen, and
+    /// return the frame containing it
+    pub fn wait_for_text(&self, text: &str) -> anyhow::Result<Frame> {
+        self.wait_for_frame(DEFAULT_TIMEOUT, |frame| frame.contains(text))
+            .with_context(|| format!("{text:?} did not appear on screen"))
+    }
+
+    /// Wait up to `timeout` for the program to exit, and return whether it
+    /// exited successfully
+    pub fn wait_for_exit(&mut self, timeout: Duration) -> anyhow::Result<bool> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = self
+                .child
+                .try_wait()
+                .context("failed to check program status")?
+            {
+                return Ok(status.success());
+            }
+            if Instant::now() >= deadline {
+                bail!("timed out waiting for program to exit");
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(None)) {
+            let _ = self.child.kill();
+        }
+    }
+}
+
+/// A snapshot of the contents of the terminal screen, with trailing
+/// whitespace removed from each line
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    pub lines: Vec<String>,
+}
+
+impl Frame {
+    /// Returns true iff `text` appears on some line of the frame
+    pub fn contains(&self, text: &str) -> bool {
+        self.lines.iter().any(|ln| ln.contains(text))
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ln in &self.lines {
+            writeln!(f, "{ln}")?;
+        }
+        Ok(())
+    }
+}
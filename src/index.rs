@@ -0,0 +1,360 @@
+use crate::model::ASCII_ALPHABET;
+use crate::words::{ListOptions, WordFilter, WordFormat, WordList, WordWithHint};
+use anyhow::Context;
+use rand::Rng;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Cursor, ErrorKind, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// The bytes at the start of every index file
+const MAGIC: &[u8; 8] = b"HMIDX001";
+
+/// The length of an index file's header: the magic bytes followed by five
+/// 64-bit fields
+const HEADER_LEN: u64 = 48;
+
+/// The number of randomly-chosen entries to try before concluding that the
+/// word filter is too strict for sampling to work
+const MAX_ATTEMPTS: usize = 100;
+
+/// A sidecar index for a large word list file, stored next to it as
+/// `<FILE>.idx`.  The index records the byte offset of every entry in the
+/// file, so that a random entry can be read with a single seek instead of
+/// parsing the whole file.
+///
+/// An index file consists of a header — the magic bytes, followed by the size
+/// & modification time (seconds and nanoseconds) of the word list when it was
+/// indexed, the word list's format, and the number of entries — and then the
+/// entries' offsets, all as little-endian `u64`s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct WordIndex {
+    path: PathBuf,
+    index_path: PathBuf,
+    /// The contents of the index, if they're kept in memory for this run
+    /// because the index file couldn't be written
+    data: Option<Vec<u8>>,
+    /// The format of the word list: either `Csv` or `Plain`
+    format: WordFormat,
+    count: u64,
+}
+
+impl WordIndex {
+    /// Open the index for the word list at `path`, building it first if it
+    /// doesn't exist or is out of date.  TOML word lists cannot be indexed.
+    pub(crate) fn open(path: &Path, format: WordFormat) -> anyhow::Result<WordIndex> {
+        let stamp = Stamp::of(path)?;
+        let mut index_path = OsString::from(path);
+        index_path.push(".idx");
+        let index_path = PathBuf::from(index_path);
+        if let Some((stored_format, count)) = read_header(&index_path, stamp)? {
+            if format == WordFormat::Auto || format == stored_format {
+                return Ok(WordIndex {
+                    path: path.to_owned(),
+                    index_path,
+                    data: None,
+                    format: stored_format,
+                    count,
+                });
+            }
+        }
+        let (format, offsets) = scan(path, format)?;
+        let mut data = Vec::with_capacity(offsets.len() * 8 + 48);
+        data.extend_from_slice(MAGIC);
+        for field in [
+            stamp.len,
+            stamp.secs,
+            stamp.nanos,
+            format_code(format),
+            offsets.len() as u64,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        for offset in &offsets {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        // If the word list is somewhere we can't write to (like a system
+        // dictionary), make do without saving the index for next time
+        let data = fs::write(&index_path, &data).err().map(|_| data);
+        Ok(WordIndex {
+            path: path.to_owned(),
+            index_path,
+            data,
+            format,
+            count: offsets.len() as u64,
+        })
+    }
+
+    /// Try to select an entry at random from those in the word list accepted
+    /// by `filter`, reading only the chosen entries.  Returns `None` if no
+    /// acceptable entry was found within a limited number of tries.
+    /// Malformed entries are skipped if `options.lenient` is true and are an
+    /// error otherwise, and words with no letters to guess are skipped if
+    /// `options.skip_unguessable` is true.
+    pub(crate) fn choose<R: Rng + ?Sized>(
+        &self,
+        filter: &WordFilter,
+        options: ListOptions,
+        rng: &mut R,
+    ) -> anyhow::Result<Option<WordWithHint>> {
+        if self.count == 0 {
+            return Ok(None);
+        }
+        if let Some(data) = &self.data {
+            self.choose_with(Cursor::new(data), filter, options, rng)
+        } else {
+            let index = File::open(&self.index_path)
+                .with_context(|| format!("failed to open {}", self.index_path.display()))?;
+            self.choose_with(index, filter, options, rng)
+        }
+    }
+
+    /// Implementation of [`WordIndex::choose()`] with the index read from
+    /// `index`
+    fn choose_with<I: Read + Seek, R: Rng + ?Sized>(
+        &self,
+        mut index: I,
+        filter: &WordFilter,
+        options: ListOptions,
+        rng: &mut R,
+    ) -> anyhow::Result<Option<WordWithHint>> {
+        let mut words = BufReader::new(
+            File::open(&self.path)
+                .with_context(|| format!("failed to open {}", self.path.display()))?,
+        );
+        for _ in 0..MAX_ATTEMPTS {
+            let i = rng.gen_range(0..self.count);
+            let mut buf = [0; 8];
+            index.seek(SeekFrom::Start(HEADER_LEN + 8 * i))?;
+            index.read_exact(&mut buf)?;
+            let offset = u64::from_le_bytes(buf);
+            let mut line = String::new();
+            words.seek(SeekFrom::Start(offset))?;
+            words.read_line(&mut line)?;
+            let entry = WordList::records(&line, self.format)
+                .into_iter()
+                .next()
+                .map(|r| r.entry);
+            match entry {
+                Some(Ok(entry))
+                    if filter.accepts(&entry)
+                        && (!options.skip_unguessable
+                            || entry.word.has_letters_in(ASCII_ALPHABET)) =>
+                {
+                    return Ok(Some(entry))
+                }
+                Some(Err(e)) if !options.lenient => {
+                    return Err(e).with_context(|| {
+                        format!(
+                            "malformed entry at byte {offset} of {}",
+                            self.path.display()
+                        )
+                    })
+                }
+                _ => (),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the number of entries in the word list
+    #[cfg(test)]
+    fn len(&self) -> u64 {
+        self.count
+    }
+}
+
+/// The size & modification time of a file, used to tell whether an index is
+/// out of date
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Stamp {
+    len: u64,
+    secs: u64,
+    nanos: u64,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> anyhow::Result<Stamp> {
+        let metadata =
+            fs::metadata(path).with_context(|| format!("failed to stat {}", path.display()))?;
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        Ok(Stamp {
+            len: metadata.len(),
+            secs: mtime.as_secs(),
+            nanos: u64::from(mtime.subsec_nanos()),
+        })
+    }
+}
+
+/// Read the header of the index file at `index_path`, returning the format
+/// and number of entries of the indexed word list if the index exists and
+/// matches `stamp`
+fn read_header(index_path: &Path, stamp: Stamp) -> anyhow::Result<Option<(WordFormat, u64)>> {
+    let mut fp = match File::open(index_path) {
+        Ok(fp) => fp,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).with_context(|| format!("failed to open {}", index_path.display()))
+        }
+    };
+    let mut header = [0; 48];
+    if fp.read_exact(&mut header).is_err() || &header[..8] != MAGIC {
+        return Ok(None);
+    }
+    let mut fields = header[8..]
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap_or_default()));
+    let mut next = || fields.next().unwrap_or_default();
+    let stored = Stamp {
+        len: next(),
+        secs: next(),
+        nanos: next(),
+    };
+    let format = match next() {
+        1 => WordFormat::Csv,
+        2 => WordFormat::Plain,
+        _ => return Ok(None),
+    };
+    let count = next();
+    Ok((stored == stamp).then_some((format, count)))
+}
+
+fn format_code(format: WordFormat) -> u64 {
+    if format == WordFormat::Plain {
+        2
+    } else {
+        1
+    }
+}
+
+/// Read through the word list at `path` line by line, returning its format
+/// and the byte offsets of its entries.  With `WordFormat::Auto`, the format
+/// is determined the same way as when reading a word list normally.
+fn scan(path: &Path, format: WordFormat) -> anyhow::Result<(WordFormat, Vec<u64>)> {
+    let mut reader = BufReader::new(
+        File::open(path).with_context(|| format!("failed to open {}", path.display()))?,
+    );
+    let mut offsets = Vec::new();
+    let mut comments = Vec::new();
+    let mut plain = false;
+    let mut offset = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let n = reader
+            .read_line(&mut line)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        let trimmed = line.trim();
+        if trimmed == "[[words]]" && format == WordFormat::Auto || format == WordFormat::Toml {
+            anyhow::bail!("TOML word lists cannot be indexed");
+        }
+        if trimmed.starts_with('#') {
+            comments.push(offset);
+            plain = true;
+        } else if !trimmed.is_empty() {
+            offsets.push(offset);
+            plain |= line.contains('\t');
+        }
+        offset += n as u64;
+    }
+    let format = match format {
+        WordFormat::Auto if plain => WordFormat::Plain,
+        WordFormat::Auto | WordFormat::Toml => WordFormat::Csv,
+        other => other,
+    };
+    if format == WordFormat::Csv {
+        // '#' lines are only comments in plain text lists
+        offsets.extend(comments);
+        offsets.sort_unstable();
+    }
+    Ok((format, offsets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("words.txt");
+        fs::write(&path, "# Animals\naardvark\tAnimal\n\nzebra\tAnimal\n").unwrap();
+        let index = WordIndex::open(&path, WordFormat::Auto).unwrap();
+        assert_eq!(index.format, WordFormat::Plain);
+        assert_eq!(index.len(), 2);
+        assert!(tmpdir.path().join("words.txt.idx").exists());
+        let filter = WordFilter {
+            max_length: Some(5),
+            ..WordFilter::default()
+        };
+        let secret = index
+            .choose(&filter, ListOptions::default(), &mut rand::thread_rng())
+            .unwrap()
+            .unwrap();
+        assert_eq!(secret.word.as_ref(), "zebra");
+        assert_eq!(secret.hint.as_deref(), Some("Animal"));
+        // Reopening uses the stored index
+        assert_eq!(WordIndex::open(&path, WordFormat::Auto).unwrap(), index);
+        // Changing the word list causes the index to be rebuilt
+        fs::write(&path, "cat,Pet\n\"\"\ndog,Pet\nbird,Pet\n").unwrap();
+        let index = WordIndex::open(&path, WordFormat::Auto).unwrap();
+        assert_eq!(index.format, WordFormat::Csv);
+        assert_eq!(index.len(), 4);
+        let filter = WordFilter {
+            max_length: Some(0),
+            ..WordFilter::default()
+        };
+        assert_eq!(
+            index
+                .choose(
+                    &filter,
+                    ListOptions {
+                        lenient: true,
+                        ..ListOptions::default()
+                    },
+                    &mut rand::thread_rng()
+                )
+                .unwrap(),
+            None
+        );
+        assert!(index
+            .choose(&filter, ListOptions::default(), &mut rand::thread_rng())
+            .is_err());
+    }
+
+    #[test]
+    fn test_index_unwritable() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("words.txt");
+        fs::write(&path, "aardvark\nzebra\n").unwrap();
+        // Something that isn't a file is in the way of the index
+        fs::create_dir(tmpdir.path().join("words.txt.idx")).unwrap();
+        let index = WordIndex::open(&path, WordFormat::Auto).unwrap();
+        assert_eq!(index.len(), 2);
+        let filter = WordFilter {
+            max_length: Some(5),
+            ..WordFilter::default()
+        };
+        let secret = index
+            .choose(&filter, ListOptions::default(), &mut rand::thread_rng())
+            .unwrap()
+            .unwrap();
+        assert_eq!(secret.word.as_ref(), "zebra");
+    }
+
+    #[test]
+    fn test_index_toml() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("words.toml");
+        fs::write(&path, "[[words]]\nword = \"cat\"\n").unwrap();
+        assert!(WordIndex::open(&path, WordFormat::Auto).is_err());
+    }
+}
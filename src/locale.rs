@@ -0,0 +1,97 @@
+use thiserror::Error;
+
+/// Pairs of American & British spellings of words, one pair per line,
+/// separated by a comma
+static EN_GB_SPELLINGS: &str = include_str!("en-gb.csv");
+
+/// Regions whose English uses British rather than American spelling
+static BRITISH_REGIONS: &[&str] = &["AU", "GB", "IE", "IN", "NZ", "UK", "ZA"];
+
+/// A variety of English, determining how the words in the builtin word lists
+/// are spelled.  The builtin lists use American spelling, and other locales
+/// respell words as needed.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub(crate) enum Locale {
+    #[default]
+    EnUs,
+    EnGb,
+}
+
+impl Locale {
+    /// Determine the locale from the `LC_ALL`, `LC_MESSAGES`, and `LANG`
+    /// environment variables, in that order of precedence.  Non-English and
+    /// unrecognized locales are treated as American English.
+    pub(crate) fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|name| std::env::var(name).ok().filter(|s| !s.is_empty()))
+            .map(|s| Locale::from_posix(&s))
+            .unwrap_or_default()
+    }
+
+    /// Determine the locale from a POSIX locale name like `en_GB.UTF-8`
+    fn from_posix(name: &str) -> Locale {
+        let name = name.split(['.', '@']).next().unwrap_or_default();
+        match name.split_once('_') {
+            Some(("en", region)) if BRITISH_REGIONS.contains(&region) => Locale::EnGb,
+            _ => Locale::EnUs,
+        }
+    }
+
+    /// Returns the spelling of `word` in this locale if it differs from the
+    /// American spelling
+    pub(crate) fn respell(self, word: &str) -> Option<&'static str> {
+        match self {
+            Locale::EnUs => None,
+            Locale::EnGb => EN_GB_SPELLINGS
+                .lines()
+                .filter_map(|ln| ln.split_once(','))
+                .find(|(us, _)| us.eq_ignore_ascii_case(word))
+                .map(|(_, gb)| gb),
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = ParseLocaleError;
+
+    fn from_str(s: &str) -> Result<Locale, ParseLocaleError> {
+        match s.replace('_', "-").to_ascii_lowercase().as_str() {
+            "en-us" => Ok(Locale::EnUs),
+            "en-gb" => Ok(Locale::EnGb),
+            _ => Err(ParseLocaleError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"locale must be "en-US" or "en-GB""#)]
+pub(crate) struct ParseLocaleError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_posix() {
+        assert_eq!(Locale::from_posix("en_GB.UTF-8"), Locale::EnGb);
+        assert_eq!(Locale::from_posix("en_AU"), Locale::EnGb);
+        assert_eq!(Locale::from_posix("en_US.UTF-8"), Locale::EnUs);
+        assert_eq!(Locale::from_posix("C"), Locale::EnUs);
+        assert_eq!(Locale::from_posix("fr_FR.UTF-8"), Locale::EnUs);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("en-GB".parse::<Locale>(), Ok(Locale::EnGb));
+        assert_eq!("en_us".parse::<Locale>(), Ok(Locale::EnUs));
+        assert_eq!("en".parse::<Locale>(), Err(ParseLocaleError));
+    }
+
+    #[test]
+    fn test_respell() {
+        assert_eq!(Locale::EnGb.respell("color"), Some("colour"));
+        assert_eq!(Locale::EnGb.respell("elephant"), None);
+        assert_eq!(Locale::EnUs.respell("color"), None);
+    }
+}
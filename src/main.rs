@@ -1,59 +1,875 @@
+mod analysis;
+mod challenge;
+mod config;
 mod controller;
+#[cfg(feature = "definitions")]
+mod definitions;
+#[cfg(feature = "dictionary")]
+mod dictionary;
+mod difficulty;
+mod graphics;
+mod index;
+mod locale;
 mod model;
+mod profanity;
+#[cfg(feature = "words-url")]
+mod remote;
+mod score;
+#[cfg(unix)]
+mod signals;
+mod solver;
+mod state;
+#[cfg(all(test, any(feature = "dictionary", feature = "words-url")))]
+mod testutil;
+mod theme;
 mod view;
 mod words;
-use crate::controller::Controller;
+mod wordtools;
+use crate::challenge::Challenge;
+use crate::config::Config;
+use crate::controller::{
+    Controller, DuelController, GallowsCarry, ReverseController, Sequence, Settings,
+};
+use crate::difficulty::Difficulty;
+use crate::graphics::Graphics;
+use crate::locale::Locale;
+use crate::model::Fate;
+use crate::state::{puzzle_number, today, State};
+use crate::view::{Arrangement, Art, ColorChoice};
 use crate::words::*;
+use crate::wordtools::ListEdit;
 use lexopt::{Arg, Parser, ValueExt};
-use patharg::InputArg;
+use patharg::{InputArg, OutputArg};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::ffi::OsString;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::time::Duration;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum Command {
-    Run(WordSource),
+    Run {
+        word_source: Option<WordSource>,
+        /// Criteria for selecting a word from a word list given on the
+        /// command line
+        selection: Box<WordFilter>,
+        difficulty: Difficulty,
+        settings: Box<Settings>,
+        /// Seed for the random selection of words, for reproducing a game
+        seed: Option<u64>,
+        /// The spelling to use for the builtin word lists, if not the
+        /// system's
+        locale: Option<Locale>,
+        /// The builtin word list to use, if not the one for the difficulty
+        /// level
+        pool: Option<Pool>,
+        /// A word list from which to take hints for words that lack them
+        hints_file: Option<InputArg>,
+        /// A dictionary file that a secret word given on the command line
+        /// must appear in
+        required_dictionary: Option<InputArg>,
+        /// The name of the theme to draw the game with, if not the one set in
+        /// the configuration file
+        theme: Option<String>,
+        /// When to draw the game with colors & text attributes
+        color: ColorChoice,
+    },
+    Status {
+        porcelain: bool,
+    },
+    Challenge(Challenge),
+    WordsCheck {
+        files: Vec<InputArg>,
+        format: WordFormat,
+    },
+    WordsThemes,
+    WordsEdit {
+        op: ListEdit,
+        files: Vec<InputArg>,
+        format: WordFormat,
+        outfile: OutputArg,
+        output_format: WordFormat,
+    },
     Help,
     Version,
 }
 
 impl Command {
     fn from_parser(mut parser: Parser) -> Result<Command, lexopt::Error> {
-        let mut word_source = WordSource::default();
+        let mut word_source = None;
+        let mut selection = WordFilter::default();
+        let mut list_options = ListOptions::default();
+        let mut difficulty = Difficulty::default();
+        let mut settings = Settings::default();
+        let mut seed = None;
+        let mut theme = None;
+        let mut color = ColorChoice::default();
+        let mut locale = None;
+        let mut pool = None;
+        let mut hints_file = None;
+        let mut required_dictionary = None;
         while let Some(arg) = parser.next()? {
             match arg {
                 Arg::Short('h') | Arg::Long("help") => return Ok(Command::Help),
                 Arg::Short('V') | Arg::Long("version") => return Ok(Command::Version),
                 Arg::Short('w') | Arg::Long("word") => {
-                    word_source = WordSource::Fixed(parser.value()?.parse()?);
+                    let value = parser.value()?;
+                    word_source = Some(if value == "-" {
+                        WordSource::Prompt
+                    } else {
+                        WordSource::Fixed(value.parse::<Word>()?.into())
+                    });
+                }
+                #[cfg(feature = "words-url")]
+                Arg::Long("words-url") => {
+                    word_source = Some(WordSource::Url(
+                        parser.value()?.string()?,
+                        ListOptions::default(),
+                    ));
+                }
+                Arg::Long("words-cmd") => {
+                    word_source = Some(WordSource::Command(
+                        parser.value()?.string()?,
+                        ListOptions::default(),
+                    ));
                 }
                 Arg::Short('f') | Arg::Long("words-file") => {
-                    word_source = WordSource::File(InputArg::from_arg(parser.value()?));
+                    word_source = Some(WordSource::File(
+                        InputArg::from_arg(parser.value()?),
+                        ListOptions::default(),
+                    ));
+                }
+                Arg::Long("accessible") => {
+                    settings.teletype = true;
+                    settings.accessible = true;
+                }
+                Arg::Long("allow-profanity") => selection.allow_profanity = true,
+                Arg::Long("analyze") => settings.analyze = true,
+                Arg::Long("art") => settings.art = parser.value()?.parse()?,
+                Arg::Long("assist") => settings.assist = true,
+                Arg::Long("big-word") => settings.big_word = true,
+                Arg::Long("blank") => settings.blank = parser.value()?.parse()?,
+                Arg::Long("category") => selection.category = Some(parser.value()?.string()?),
+                Arg::Long("challenge") => {
+                    let Challenge(secret) = parser.value()?.parse()?;
+                    word_source = Some(WordSource::Fixed(secret));
+                }
+                Arg::Long("cheat-meter") => settings.cheat_meter = true,
+                Arg::Long("emoji") => settings.art = Art::Emoji,
+                Arg::Long("graphics") => settings.graphics = Graphics::detect(),
+                Arg::Long("guessed-letters") => settings.guessed = parser.value()?.parse()?,
+                Arg::Long("color") => color = parser.value()?.parse()?,
+                Arg::Long("coop") => settings.coop = true,
+                Arg::Long("cursor-keys") => settings.cursor_keys = true,
+                Arg::Long("hide") => settings.arrangement.hide.insert(parser.value()?.parse()?),
+                Arg::Long("hide-unfocused") => settings.hide_unfocused = true,
+                Arg::Long("hint-cost") => settings.hint_cost = parser.value()?.parse()?,
+                Arg::Long("hints-file") => hints_file = Some(InputArg::from_arg(parser.value()?)),
+                Arg::Long("require-dictionary") => {
+                    required_dictionary = Some(InputArg::from_arg(parser.value()?));
+                }
+                Arg::Short('d') | Arg::Long("difficulty") => {
+                    difficulty = parser.value()?.parse()?;
+                }
+                #[cfg(feature = "dictionary")]
+                Arg::Long("dictionary") => {
+                    word_source = Some(WordSource::Dictionary(BuiltinList::default()));
+                }
+                #[cfg(feature = "dictionary")]
+                Arg::Long("define") => settings.define = true,
+                Arg::Long("duel") => settings.duel = true,
+                Arg::Long("endless") => {
+                    settings.sequence = Some(Sequence::Endless(match parser.optional_value() {
+                        Some(value) => value.parse()?,
+                        None => GallowsCarry::default(),
+                    }));
+                }
+                Arg::Long("lightning") => {
+                    settings.sequence = Some(Sequence::Lightning(parser.value()?.parse()?));
+                }
+                Arg::Long("rounds") => {
+                    settings.sequence = Some(Sequence::Match(parser.value()?.parse()?));
+                }
+                Arg::Long("format") => list_options.format = parser.value()?.parse()?,
+                Arg::Long("kids") => settings.kids = true,
+                Arg::Long("letter-layout") => settings.letter_layout = parser.value()?.parse()?,
+                Arg::Long("indexed") => list_options.indexed = true,
+                Arg::Long("lenient") => list_options.lenient = true,
+                Arg::Long("ranked") => list_options.ranked = true,
+                Arg::Long("skip-unguessable") => list_options.skip_unguessable = true,
+                Arg::Long("locale") => locale = Some(parser.value()?.parse()?),
+                Arg::Long("max-length") => {
+                    let max = parser.value()?.parse::<NonZeroUsize>()?;
+                    settings.length_limit.max = max.get();
+                }
+                Arg::Long("match") => selection.pattern = Some(parser.value()?.parse()?),
+                Arg::Long("long-words") => {
+                    settings.length_limit.policy = parser.value()?.parse()?;
+                }
+                Arg::Long("mouse") => settings.mouse = true,
+                Arg::Long("no-tui") => settings.teletype = true,
+                Arg::Long("reveal-on-quit") => settings.reveal_on_quit = true,
+                Arg::Long("reverse") => settings.reverse = true,
+                Arg::Long("seed") => seed = Some(parser.value()?.parse()?),
+                Arg::Long("status-bar") => settings.status_bar = true,
+                Arg::Long("wrap-group") => settings.wrap_group = parser.value()?.parse()?,
+                Arg::Long("theme") => theme = Some(parser.value()?.string()?),
+                Arg::Long("timer") => {
+                    let secs = parser.value()?.parse::<NonZeroU64>()?;
+                    settings.timer = Some(Duration::from_secs(secs.get()));
+                }
+                #[cfg(unix)]
+                Arg::Long("word-fd") => {
+                    let fd = parser.value()?.parse::<u32>()?;
+                    // Descriptor 0 is standard input, and needs to be known
+                    // as such for the checks against reading guesses from it
+                    let infile = if fd == 0 {
+                        InputArg::Stdin
+                    } else {
+                        InputArg::from_arg(format!("/dev/fd/{fd}"))
+                    };
+                    word_source = Some(WordSource::SecretFile(infile));
+                }
+                Arg::Long("word-file") => {
+                    word_source = Some(WordSource::SecretFile(InputArg::from_arg(parser.value()?)));
+                }
+                Arg::Long("daily" | "word-of-the-day") => {
+                    word_source = Some(WordSource::OfTheDay(BuiltinList::default()));
+                }
+                Arg::Long("wordlist") => pool = Some(parser.value()?.parse()?),
+                Arg::Long("word-difficulty") => {
+                    selection.difficulty = Some(parser.value()?.parse()?);
+                }
+                Arg::Value(value) if value == "status" => {
+                    return Command::status_from_parser(parser)
+                }
+                Arg::Value(value) if value == "challenge" => {
+                    return Command::challenge_from_parser(parser)
+                }
+                Arg::Value(value) if value == "words" => return Command::words_from_parser(parser),
+                _ => return Err(arg.unexpected()),
+            }
+        }
+        // Choosing a builtin list counts as choosing a word source
+        if word_source.is_none() && pool.is_none() {
+            word_source = word_source_from_env()?;
+        }
+        match &mut word_source {
+            Some(WordSource::File(_, options) | WordSource::Command(_, options)) => {
+                *options = list_options;
+            }
+            #[cfg(feature = "words-url")]
+            Some(WordSource::Url(_, options)) => *options = list_options,
+            _ => (),
+        }
+        Ok(Command::Run {
+            word_source,
+            selection: Box::new(selection),
+            difficulty,
+            settings: Box::new(settings),
+            seed,
+            locale,
+            pool,
+            hints_file,
+            required_dictionary,
+            theme,
+            color,
+        })
+    }
+
+    fn status_from_parser(mut parser: Parser) -> Result<Command, lexopt::Error> {
+        let mut porcelain = false;
+        while let Some(arg) = parser.next()? {
+            match arg {
+                Arg::Short('h') | Arg::Long("help") => return Ok(Command::Help),
+                Arg::Long("porcelain") => porcelain = true,
+                _ => return Err(arg.unexpected()),
+            }
+        }
+        Ok(Command::Status { porcelain })
+    }
+
+    fn challenge_from_parser(mut parser: Parser) -> Result<Command, lexopt::Error> {
+        let mut word = None;
+        let mut hint = None;
+        while let Some(arg) = parser.next()? {
+            match arg {
+                Arg::Short('h') | Arg::Long("help") => return Ok(Command::Help),
+                Arg::Short('w') | Arg::Long("word") => {
+                    word = Some(parser.value()?.parse::<Word>()?);
                 }
+                Arg::Long("hint") => hint = Some(parser.value()?.string()?),
                 _ => return Err(arg.unexpected()),
             }
         }
-        Ok(Command::Run(word_source))
+        let Some(word) = word else {
+            return Err("the challenge subcommand requires --word".into());
+        };
+        Ok(Command::Challenge(Challenge(WordWithHint {
+            hint,
+            ..WordWithHint::from(word)
+        })))
+    }
+
+    fn words_from_parser(mut parser: Parser) -> Result<Command, lexopt::Error> {
+        match parser.next()? {
+            Some(Arg::Short('h') | Arg::Long("help")) => Ok(Command::Help),
+            Some(Arg::Value(value)) if value == "check" => Command::words_check_from_parser(parser),
+            Some(Arg::Value(value)) if value == "themes" => Ok(Command::WordsThemes),
+            Some(Arg::Value(value)) if value == "dedupe" => {
+                Command::words_edit_from_parser(parser, ListEdit::Dedupe)
+            }
+            Some(Arg::Value(value)) if value == "merge" => {
+                Command::words_edit_from_parser(parser, ListEdit::Merge)
+            }
+            Some(Arg::Value(value)) if value == "sort" => {
+                Command::words_edit_from_parser(parser, ListEdit::Sort)
+            }
+            Some(arg) => Err(arg.unexpected()),
+            None => Err("the words subcommand requires a command".into()),
+        }
+    }
+
+    fn words_check_from_parser(mut parser: Parser) -> Result<Command, lexopt::Error> {
+        let mut files = Vec::new();
+        let mut format = WordFormat::default();
+        while let Some(arg) = parser.next()? {
+            match arg {
+                Arg::Short('h') | Arg::Long("help") => return Ok(Command::Help),
+                Arg::Long("format") => format = parser.value()?.parse()?,
+                Arg::Value(value) => files.push(InputArg::from_arg(value)),
+                _ => return Err(arg.unexpected()),
+            }
+        }
+        if files.is_empty() {
+            files.push(InputArg::default());
+        }
+        Ok(Command::WordsCheck { files, format })
+    }
+
+    fn words_edit_from_parser(mut parser: Parser, op: ListEdit) -> Result<Command, lexopt::Error> {
+        let mut files = Vec::new();
+        let mut format = WordFormat::default();
+        let mut outfile = OutputArg::default();
+        let mut output_format = WordFormat::default();
+        while let Some(arg) = parser.next()? {
+            match arg {
+                Arg::Short('h') | Arg::Long("help") => return Ok(Command::Help),
+                Arg::Long("format") => format = parser.value()?.parse()?,
+                Arg::Short('o') | Arg::Long("output") => {
+                    outfile = OutputArg::from_arg(parser.value()?);
+                }
+                Arg::Long("output-format") => output_format = parser.value()?.parse()?,
+                Arg::Value(value) => files.push(InputArg::from_arg(value)),
+                _ => return Err(arg.unexpected()),
+            }
+        }
+        if files.is_empty() {
+            files.push(InputArg::default());
+        }
+        Ok(Command::WordsEdit {
+            op,
+            files,
+            format,
+            outfile,
+            output_format,
+        })
     }
 
     fn run(self) -> anyhow::Result<()> {
         match self {
-            Command::Run(word_source) => Controller::new(word_source.fetch()?)?.run()?,
+            Command::Run {
+                word_source,
+                selection,
+                difficulty,
+                settings,
+                seed,
+                locale,
+                pool,
+                hints_file,
+                required_dictionary,
+                theme,
+                color,
+            } => {
+                let mut settings = *settings;
+                let config = Config::load()?;
+                let preset = config.preset(difficulty);
+                settings.theme = color.apply(config.theme(theme.as_deref())?);
+                settings.max_misses = (!settings.kids).then_some(preset.misses);
+                settings.show_hint = preset.show_hint;
+                settings.arrangement = Arrangement {
+                    hide: config.layout.hide.union(settings.arrangement.hide),
+                    ..config.layout
+                };
+                if settings.teletype
+                    && (settings.duel || settings.reverse || settings.timer.is_some())
+                {
+                    anyhow::bail!(
+                        "--duel, --reverse, and --timer cannot be combined with --no-tui or --accessible"
+                    );
+                }
+                let builtin = BuiltinList {
+                    pool: pool.unwrap_or(if settings.kids {
+                        Pool::Kids
+                    } else {
+                        preset.pool
+                    }),
+                    locale: locale.unwrap_or_else(Locale::from_env),
+                };
+                let mut word_source = word_source.unwrap_or(WordSource::Builtin(builtin));
+                if settings.teletype
+                    && matches!(
+                        &word_source,
+                        WordSource::File(infile, _) | WordSource::SecretFile(infile)
+                            if infile.is_stdin()
+                    )
+                {
+                    // Guesses are read from standard input, which the word
+                    // list or secret word would have used up
+                    anyhow::bail!(
+                        "--no-tui and --accessible cannot be combined with reading the word list or secret word from standard input"
+                    );
+                }
+                #[cfg(feature = "dictionary")]
+                if let WordSource::Dictionary(fallback) = &mut word_source {
+                    if settings.sequence.is_some() || settings.reverse {
+                        anyhow::bail!(
+                            "--endless, --lightning, --reverse, and --rounds cannot be combined with --dictionary"
+                        );
+                    }
+                    *fallback = builtin;
+                }
+                if let WordSource::OfTheDay(fallback) = &mut word_source {
+                    if settings.sequence.is_some() || settings.reverse {
+                        anyhow::bail!(
+                            "--endless, --lightning, --reverse, and --rounds cannot be combined with --word-of-the-day"
+                        );
+                    }
+                    if pool.is_some()
+                        || selection.category.is_some()
+                        || selection.pattern.is_some()
+                        || selection.difficulty.is_some()
+                    {
+                        anyhow::bail!(
+                            "--category, --match, --word-difficulty, and --wordlist cannot be combined with --word-of-the-day"
+                        );
+                    }
+                    // Every puzzle number has to stand for the same word for
+                    // everyone, whatever their difficulty level
+                    *fallback = BuiltinList {
+                        pool: Pool::Standard,
+                        ..builtin
+                    };
+                }
+                match &mut word_source {
+                    WordSource::File(_, options) | WordSource::Command(_, options) => {
+                        options.tier = preset.tier;
+                    }
+                    #[cfg(feature = "words-url")]
+                    WordSource::Url(_, options) => options.tier = preset.tier,
+                    _ => (),
+                }
+                if matches!(
+                    word_source,
+                    WordSource::File(
+                        _,
+                        ListOptions {
+                            indexed: true,
+                            ranked: true,
+                            ..
+                        }
+                    )
+                ) {
+                    anyhow::bail!("--indexed cannot be combined with --ranked");
+                }
+                if settings.sequence.is_some()
+                    && matches!(
+                        word_source,
+                        WordSource::File(_, ListOptions { indexed: true, .. })
+                    )
+                {
+                    anyhow::bail!(
+                        "--endless, --lightning, and --rounds cannot be combined with --indexed"
+                    );
+                }
+                if settings.sequence.is_some()
+                    && matches!(
+                        word_source,
+                        WordSource::Fixed(_) | WordSource::Prompt | WordSource::SecretFile(_)
+                    )
+                {
+                    anyhow::bail!(
+                        "--endless, --lightning, and --rounds cannot be combined with {FIXED_WORD}"
+                    );
+                }
+                let lexicon = match required_dictionary {
+                    Some(infile) => {
+                        if !matches!(
+                            word_source,
+                            WordSource::Fixed(_) | WordSource::Prompt | WordSource::SecretFile(_)
+                        ) {
+                            anyhow::bail!(
+                                "--require-dictionary can only be combined with {FIXED_WORD}"
+                            );
+                        }
+                        Some(Lexicon::from_file(infile)?)
+                    }
+                    None => None,
+                };
+                if settings.reverse {
+                    let list = match word_source {
+                        WordSource::Builtin(builtin) => builtin.list(),
+                        WordSource::File(infile, options) => WordList::from_file(infile, options)?,
+                        WordSource::Command(command, options) => {
+                            WordList::from_command(&command, options)?
+                        }
+                        #[cfg(feature = "words-url")]
+                        WordSource::Url(url, options) => WordList::from_url(&url, options)?,
+                        WordSource::Fixed(_) | WordSource::Prompt | WordSource::SecretFile(_) => {
+                            anyhow::bail!("--reverse cannot be combined with {FIXED_WORD}")
+                        }
+                        WordSource::OfTheDay(_) => {
+                            unreachable!(
+                                "--word-of-the-day with --reverse should have been rejected"
+                            )
+                        }
+                        #[cfg(feature = "dictionary")]
+                        WordSource::Dictionary(_) => {
+                            unreachable!("--dictionary with --reverse should have been rejected")
+                        }
+                    };
+                    return ReverseController::new(&list, settings, config.themes).run();
+                }
+                let daily = matches!(word_source, WordSource::OfTheDay(_));
+                settings.daily = daily;
+                let filter = preset.word_filter(*selection);
+                let rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(seed),
+                    None => StdRng::from_entropy(),
+                };
+                let mut fetched = word_source.fetch(&filter, rng)?;
+                if lexicon.is_some_and(|lexicon| !lexicon.contains(&fetched.secret.word)) {
+                    anyhow::bail!("Secret word is not in the dictionary");
+                }
+                if let Some(infile) = hints_file {
+                    fetched.fill_hints(&Hints::from_file(infile)?);
+                }
+                let fates = if settings.duel {
+                    Vec::from_iter(DuelController::new(fetched, settings, config.themes)?.run()?)
+                } else if settings.teletype {
+                    Controller::new(fetched, settings, config.themes)?.run_teletype()?
+                } else {
+                    Controller::new(fetched, settings, config.themes)?.run()?
+                };
+                if !fates.is_empty() {
+                    if daily {
+                        println!(
+                            "Hangman daily #{}: {}",
+                            puzzle_number(today()),
+                            if fates[0] == Fate::Won {
+                                "solved"
+                            } else {
+                                "not solved"
+                            }
+                        );
+                    }
+                    let mut state = State::load()?;
+                    for fate in fates {
+                        state.record_game(fate == Fate::Won);
+                    }
+                    if daily {
+                        state.last_daily = Some(today());
+                    }
+                    state.save()?;
+                }
+            }
+            Command::Status { porcelain } => {
+                let state = State::load()?;
+                if porcelain {
+                    println!("{:#}", state.status(today()));
+                } else {
+                    println!("{}", state.status(today()));
+                }
+            }
             Command::Help => {
-                println!("Usage: hangman [-f <FILE>|-w <WORD>]");
+                println!("Usage: hangman [<options>]");
+                println!("       hangman status [--porcelain]");
+                println!("       hangman challenge --word <WORD> [--hint <HINT>]");
+                println!("       hangman words themes");
+                println!("       hangman words check [--format <FORMAT>] [<FILE> ...]");
+                println!("       hangman words <dedupe|merge|sort> [--format <FORMAT>]");
+                println!("             [--output-format <FORMAT>] [-o <OUTFILE>] [<FILE> ...]");
                 println!();
                 println!("Play Hangman in your terminal");
                 println!();
                 println!("Options:");
+                println!("  --accessible      Like --no-tui, but describe the game in words for");
+                println!("                    screen readers instead of drawing it");
+                println!();
+                println!("  --allow-profanity Allow profane words to be selected from word lists");
+                println!("                    and the online dictionary");
+                println!();
+                println!("  --analyze         After the game ends, print an analysis comparing");
+                println!("                    your guesses to those suggested by assist mode");
+                println!();
+                println!("  --art <gallows|snowman|flower|boat|emoji|braille>");
+                println!("                    Draw a melting snowman, wilting flower, or sinking");
+                println!("                    boat instead of the gallows, or draw the gallows");
+                println!("                    with emoji or with Braille characters that scale");
+                println!("                    to the terminal [default: gallows]");
+                println!();
+                println!("  --assist          Start with assist mode on, in which the letter most");
+                println!("                    likely to be in the word is highlighted.  Assist");
+                println!("                    mode can be toggled during play with Tab.");
+                println!();
+                println!("  --big-word        Draw the secret word in large letters when the");
+                println!("                    terminal is big enough");
+                println!();
+                println!("  --blank <CHAR>    Draw <CHAR> in place of each unrevealed letter of");
+                println!("                    the secret word [default: _]");
+                println!();
+                println!("  --category <NAME>");
+                println!("                    Only select words in the given category from the");
+                println!("                    word list");
+                println!();
+                println!("  --challenge <CODE>");
+                println!("                    Play the puzzle encoded in a challenge code");
+                println!();
+                println!("  --cheat-meter     Show how many words in the word list are still");
+                println!("                    consistent with what you know about the secret word");
+                println!();
+                println!("  --color <auto|always|never>");
+                println!("                    When to draw the game with colors & text attributes");
+                println!("                    [default: auto]");
+                println!();
+                println!("  --coop            Co-op mode: two players take turns guessing letters");
+                println!("                    in the same word");
+                println!();
+                println!("  --cursor-keys     Select letters in the letter grid with the arrow");
+                println!("                    keys and guess them with Enter");
+                println!();
+                println!("  --daily           Alias for --word-of-the-day");
+                println!();
+                #[cfg(feature = "dictionary")]
+                {
+                    println!("  --define          Look up the secret word in an online dictionary");
+                    println!("                    at the end of the game and show its definition");
+                    println!();
+                }
+                println!("  -d <LEVEL>, --difficulty <LEVEL>");
+                println!(
+                    "                    Set the difficulty to \"easy\", \"normal\" (default),"
+                );
+                println!("                    or \"hard\"");
+                println!();
+                #[cfg(feature = "dictionary")]
+                {
+                    println!("  --dictionary      Fetch a random word and its definition from an");
+                    println!("                    online dictionary");
+                    println!();
+                }
+                println!(
+                    "  --duel            Take turns with the computer guessing letters in the"
+                );
+                println!("                    same word; whoever reveals the last letter wins");
+                println!();
+                println!("  --emoji           Draw the gallows with emoji; same as --art emoji");
+                println!();
+                println!("  --endless[=<persist|reset>]");
+                println!("                    After each word is solved, play another, until you");
+                println!("                    lose.  The gallows either carries over between");
+                println!("                    words (default) or is reset for each word.");
+                println!();
                 println!("  -f <FILE>, --words-file <FILE>");
                 println!("                    Select a word at random from <FILE>");
+                println!("                    [default: $HANGMAN_WORDS_FILE]");
+                println!();
+                println!("  --format <auto|csv|plain|toml>");
+                println!("                    Set the format of the words file: CSV, plain text");
+                println!("                    with one word per line, or TOML.  By default, the");
+                println!("                    format is detected from the file's contents.");
+                println!();
+                println!(
+                    "  --graphics        On terminals that support the Kitty graphics protocol"
+                );
+                println!("                    or Sixel, draw the gallows as an image");
+                println!();
+                println!("  --guessed-letters <dim|strike|blank>");
+                println!("                    Draw letters that have already been guessed dimmed");
+                println!("                    or struck through in the letter grid, or leave gaps");
+                println!("                    in their place [default: dim]");
+                println!();
+                println!("  --hide <hint|gallows|letters|message|history>");
+                println!("                    Leave the given part of the game display out,");
+                println!("                    letting the rest take its place; may be given more");
+                println!("                    than once");
+                println!();
+                println!("  --hide-unfocused  Hide the game board while the game is paused");
+                println!("                    because the terminal window lost focus");
+                println!();
+                println!("  --hint-cost <N>   In a match, deduct <N> points from a round's score");
+                println!("                    for each extra hint revealed with + [default: 0]");
+                println!();
+                println!("  --hints-file <FILE>");
+                println!("                    Take hints for words that have none from the word");
+                println!("                    list <FILE>");
+                println!();
+                println!("  --indexed         Pick a word from the words file using an index");
+                println!("                    stored in <FILE>.idx, building it if needed, rather");
+                println!("                    than reading the whole file");
+                println!();
+                println!("  --kids            Kids mode: you can't lose, and the secret word is");
+                println!("                    chosen from a list of simple words by default");
+                println!();
+                println!("  --lenient         Skip malformed entries in word lists instead of");
+                println!("                    failing");
+                println!();
+                println!("  --letter-layout <alphabetical|qwerty|azerty>");
+                println!("                    Arrange the letter grid in alphabetical order or");
+                println!("                    like the keys on a QWERTY or AZERTY keyboard");
+                println!("                    [default: alphabetical]");
+                println!();
+                println!("  --lightning <N>   Lightning round: solve <N> words in a row with a");
+                println!("                    single gallows shared between them");
+                println!();
+                println!("  --locale <en-US|en-GB>");
+                println!("                    Spell words from the builtin word lists the");
+                println!("                    American or British way [default: from $LANG]");
+                println!();
+                println!("  --long-words <error|truncate>");
+                println!("                    What to do if the secret word is longer than the");
+                println!("                    maximum length: refuse to play (default) or play");
+                println!("                    with the word truncated");
+                println!();
+                println!("  --match <REGEX>   Only select words matching the given regular");
+                println!("                    expression (ignoring case) from the word list");
+                println!();
+                println!("  --max-length <N>  Set the maximum length of the secret word [default:");
+                println!("                    {}]", LengthLimit::DEFAULT_MAX);
+                println!();
+                println!("  --mouse           Click letters in the letter grid to guess them");
+                println!();
+                println!("  --no-tui          Print the game a line at a time and read guesses");
+                println!("                    from standard input instead of taking over the");
+                println!("                    terminal");
+                println!();
+                println!("  --ranked          Treat the word list as ordered from most to least");
+                println!("                    common and take words from the frequency tier for");
+                println!("                    the difficulty level");
+                println!();
+                println!("  --require-dictionary <FILE>");
+                println!("                    Refuse a secret word given with -w unless it's");
+                println!("                    listed in the dictionary <FILE>");
+                println!();
+                println!("  --reveal-on-quit  When quitting partway through a game, print the");
+                println!("                    secret word after leaving the game screen");
+                println!();
+                println!(
+                    "  --reverse         Reverse Hangman: think of a word and let the computer"
+                );
+                println!("                    guess it.  With -f, the computer draws on <FILE>.");
+                println!();
+                println!("  --rounds <N>      Play a match of <N> rounds, scoring points for each");
+                println!("                    word solved");
+                println!();
+                println!("  --seed <N>        Seed the random selection of words with <N> so that");
+                println!("                    the same words can be chosen again");
+                println!();
+                println!("  --skip-unguessable");
+                println!("                    Skip words in word lists that have no letters to");
+                println!("                    guess instead of failing when one is chosen");
+                println!();
+                println!("  --status-bar      Show the time spent on the current word, the number");
+                println!("                    of guesses made, and the number of misses left at");
+                println!("                    the bottom of the screen");
+                println!();
+                println!("  --theme <NAME>    Draw the game with the named color theme");
+                println!();
+                println!(
+                    "  --timer <SECONDS> Allow <SECONDS> seconds for each guess; if time runs"
+                );
+                println!("                    out, a miss is counted against you");
                 println!();
                 println!("  -w <WORD>, --word <WORD>");
                 println!(
                     "                    Use <WORD> as the secret word.  Good for testing and"
                 );
-                println!("                    playing against others.");
+                println!("                    playing against others.  If <WORD> is \"-\", the");
+                println!("                    word is read from the terminal without being shown.");
+                println!("                    [default: $HANGMAN_WORD]");
+                println!();
+                println!("  --word-difficulty <easy|medium|hard>");
+                println!("                    Only select words with the given difficulty rating");
+                println!("                    from the word list, estimating ratings for words");
+                println!("                    that lack them");
+                println!();
+                #[cfg(unix)]
+                {
+                    println!("  --word-fd <N>     Read the secret word from file descriptor <N>");
+                    println!();
+                }
+                println!("  --word-file <FILE>");
+                println!("                    Read the secret word from the first line of <FILE>");
+                println!();
+                println!("  --word-of-the-day Play the word of the day, the same for everyone");
+                println!("                    playing that day, and print the puzzle number");
+                println!("                    at the end for sharing your result");
+                println!();
+                println!("  --wordlist <NAME> Select the word from the named builtin word list");
+                println!("                    (see `hangman words themes`)");
+                println!();
+                println!("  --words-cmd <COMMAND>");
+                println!("                    Select a word at random from the output of the");
+                println!("                    given shell command");
+                println!();
+                #[cfg(feature = "words-url")]
+                {
+                    println!(
+                        "  --words-url <URL> Select a word at random from the word list at <URL>"
+                    );
+                    println!();
+                }
+                println!("  --wrap-group <N>  When the secret word is too wide for the terminal,");
+                println!("                    only break it between lines after a multiple of <N>");
+                println!("                    characters [default: 1]");
                 println!();
                 println!("  -h, --help        Display this help message and exit");
                 println!("  -V, --version     Show the program version and exit");
+                println!();
+                println!("Subcommands:");
+                println!("  status            Show win streak & daily puzzle status");
+                println!("                    and exit.  With --porcelain, print a");
+                println!("                    single compact line for use in shell");
+                println!("                    prompts.");
+                println!();
+                println!("  challenge         Print a challenge code encoding the given word");
+                println!("                    (and hint, if any) for another player to use");
+                println!("                    with --challenge");
+                println!();
+                println!("  words check       Report problems in word lists, such as malformed");
+                println!("                    entries, words with letters that can't be guessed,");
+                println!("                    and duplicate words");
+                println!();
+                println!("  words dedupe      Remove duplicate words from word lists, merging");
+                println!("                    their hints, categories, & difficulties");
+                println!();
+                println!("  words merge       Combine word lists into one sorted list without");
+                println!("                    duplicates");
+                println!();
+                println!("  words sort        Sort word lists alphabetically");
+                println!();
+                println!("  words themes      List the builtin word lists available to --wordlist");
             }
+            Command::Challenge(challenge) => println!("{challenge}"),
+            Command::WordsCheck { files, format } => wordtools::check(files, format)?,
+            Command::WordsThemes => wordtools::themes(),
+            Command::WordsEdit {
+                op,
+                files,
+                format,
+                outfile,
+                output_format,
+            } => wordtools::edit(op, files, format, &outfile, output_format)?,
             Command::Version => {
                 println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
             }
@@ -62,6 +878,28 @@ impl Command {
     }
 }
 
+/// The ways of choosing the secret word in advance, for error messages about
+/// options that can't be combined with them
+const FIXED_WORD: &str =
+    "a fixed secret word (--word, --word-file, --word-fd, --challenge, or HANGMAN_WORD)";
+
+/// Returns the word source set by the `HANGMAN_WORD` or `HANGMAN_WORDS_FILE`
+/// environment variable, if either is set to a nonempty value.
+/// `HANGMAN_WORD` takes precedence.
+fn word_source_from_env() -> Result<Option<WordSource>, lexopt::Error> {
+    let getenv = |name| std::env::var_os(name).filter(|s: &OsString| !s.is_empty());
+    if let Some(word) = getenv("HANGMAN_WORD") {
+        Ok(Some(WordSource::Fixed(word.parse::<Word>()?.into())))
+    } else if let Some(path) = getenv("HANGMAN_WORDS_FILE") {
+        Ok(Some(WordSource::File(
+            InputArg::from_arg(path),
+            ListOptions::default(),
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     Command::from_parser(Parser::from_env())?.run()
 }
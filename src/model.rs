@@ -6,6 +6,10 @@ use thiserror::Error;
 /// argument to [`Hangman::new()`]
 pub(crate) static ASCII_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
+/// The default number of incorrect guesses that the user can make before
+/// losing, equal to the number of body parts in the gallows drawing
+pub(crate) const DEFAULT_MAX_MISSES: usize = Gallows::PARTS;
+
 /// The state of the hangman's gallows in a game of Hangman
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub(crate) enum Gallows {
@@ -29,6 +33,26 @@ impl Gallows {
     /// Alias for the final `Gallows` state
     pub(crate) const END: Gallows = Gallows::AddRightLeg;
 
+    /// The number of body parts in a complete gallows drawing
+    pub(crate) const PARTS: usize = 6;
+
+    /// Returns how far along the gallows is as a pair of the number of body
+    /// parts drawn so far and the total number of body parts, allowing
+    /// renderers to draw the gallows (or a substitute) without matching on
+    /// every variant
+    pub(crate) fn progress(self) -> (usize, usize) {
+        let parts = match self {
+            Gallows::Start => 0,
+            Gallows::AddHead => 1,
+            Gallows::AddTorso => 2,
+            Gallows::AddLeftArm => 3,
+            Gallows::AddRightArm => 4,
+            Gallows::AddLeftLeg => 5,
+            Gallows::AddRightLeg => 6,
+        };
+        (parts, Gallows::PARTS)
+    }
+
     /// Return the next gallows state, if any
     pub(crate) fn succ(self) -> Option<Gallows> {
         match self {
@@ -41,6 +65,32 @@ impl Gallows {
             Gallows::AddRightLeg => None,
         }
     }
+
+    /// Return the previous gallows state, if any
+    pub(crate) fn pred(self) -> Option<Gallows> {
+        let (parts, _) = self.progress();
+        parts.checked_sub(1).map(Gallows::after)
+    }
+
+    /// Returns the gallows state after `n` body parts have been added,
+    /// saturating at [`Gallows::END`]
+    fn after(n: usize) -> Gallows {
+        std::iter::successors(Some(Gallows::Start), |&g| g.succ())
+            .nth(n)
+            .unwrap_or(Gallows::END)
+    }
+
+    /// Returns the gallows state to display after `misses` incorrect guesses
+    /// out of a maximum of `max`.  Body parts are added in proportion to the
+    /// fraction of the maximum used up, with the final part only being added
+    /// once `misses` reaches `max`.
+    fn for_misses(misses: usize, max: usize) -> Gallows {
+        if misses >= max {
+            Gallows::END
+        } else {
+            Gallows::after(misses * Gallows::PARTS / max)
+        }
+    }
 }
 
 /// Outcome of a completed game of Hangman
@@ -87,6 +137,40 @@ pub(crate) enum Response {
     GameOver,
 }
 
+/// Outcome of a guess at the whole secret word in Hangman
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum WordResponse {
+    /// The guess was the secret word, which is now fully revealed
+    Correct,
+    /// The guess was not the secret word and counted as an incorrect guess
+    Incorrect {
+        /// `Some` iff the user lost the game with this guess
+        lost: Option<Lost>,
+    },
+    /// [`Hangman::guess_word()`] was called after the game ended
+    GameOver,
+}
+
+/// A record of a single guess in a game of Hangman
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Guess {
+    /// The guessed character, converted to uppercase if ASCII
+    pub(crate) letter: char,
+    /// The number of occurrences of the guess in the secret word; zero if the
+    /// guess was incorrect
+    pub(crate) count: usize,
+    /// The player who made the guess, counting from 0.  This is always 0 in a
+    /// single-player game.
+    pub(crate) player: usize,
+}
+
+impl Guess {
+    /// Returns true iff the guessed character was in the secret word
+    pub(crate) fn is_hit(&self) -> bool {
+        self.count > 0
+    }
+}
+
 /// Details on a game that the user lost
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Lost {
@@ -97,15 +181,18 @@ pub(crate) struct Lost {
 /// A game of Hangman.
 ///
 /// Text provided to a `Hangman` instance — be it the word or alphabet provided
-/// on construction or a character supplied as a guess — is normalized by
-/// converting lowercase ASCII letters to uppercase.  No other normalization is
-/// performed.
+/// on construction or a character supplied as a guess — is normalized for
+/// comparison by converting lowercase ASCII letters to uppercase.  No other
+/// normalization is performed.  The characters of the secret word are
+/// revealed as originally given, e.g., a guess of "d" in `McDonald` reveals
+/// "D" and "d".
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Hangman {
     /// Mapping from normalized characters in the alphabet to whether they've
     /// been guessed (true) or not (false)
     letters: BTreeMap<char, bool>,
     gallows: Gallows,
+    /// The characters of the secret word, in their original case
     word: Vec<char>,
     /// A representation of the characters in the word known by the user.
     /// `known_letters` is the same length as `word`.  At each index `i`,
@@ -114,6 +201,14 @@ pub(crate) struct Hangman {
     /// because the character is not in the game's alphabet and thus was
     /// revealed from the start) and `None` otherwise.
     known_letters: Vec<Option<char>>,
+    /// The number of incorrect guesses made so far
+    misses: usize,
+    /// The number of incorrect guesses after which the user loses, or `None`
+    /// if the game cannot be lost
+    max_misses: Option<usize>,
+    /// The guesses made so far that were in the alphabet and not previously
+    /// guessed, in order
+    history: Vec<Guess>,
     fate: Option<Fate>,
 }
 
@@ -132,10 +227,10 @@ impl Hangman {
             .chars()
             .map(|c| (normalize_char(c), false))
             .collect();
-        let word: Vec<char> = word.as_ref().chars().map(normalize_char).collect();
+        let word: Vec<char> = word.as_ref().chars().collect();
         let known_letters: Vec<_> = word
             .iter()
-            .map(|&c| (!letters.contains_key(&c)).then_some(c))
+            .map(|&c| (!letters.contains_key(&normalize_char(c))).then_some(c))
             .collect();
         if known_letters.iter().all(Option::is_some) {
             return Err(HangmanError::NoAlphabet);
@@ -145,10 +240,34 @@ impl Hangman {
             gallows: Gallows::Start,
             word,
             known_letters,
+            misses: 0,
+            max_misses: Some(DEFAULT_MAX_MISSES),
+            history: Vec::new(),
             fate: None,
         })
     }
 
+    /// Set the number of incorrect guesses after which the user loses.  If
+    /// `max` is `None`, incorrect guesses are counted but never advance the
+    /// gallows, making it impossible to lose.
+    ///
+    /// This should be called before any guesses are made.
+    pub(crate) fn set_max_misses(&mut self, max: Option<usize>) {
+        self.max_misses = max;
+    }
+
+    /// Start the game as though `misses` incorrect guesses had already been
+    /// made, as when the gallows carries over from a previous word.
+    ///
+    /// This should be called after [`Hangman::set_max_misses()`] and before
+    /// any guesses are made.
+    pub(crate) fn carry_misses(&mut self, misses: usize) {
+        self.misses = misses;
+        if let Some(max) = self.max_misses {
+            self.gallows = Gallows::for_misses(misses, max);
+        }
+    }
+
     /// Process a guess at a character in the secret word.
     ///
     /// If `guess` is ASCII, it is handled case-insensitively.
@@ -156,6 +275,14 @@ impl Hangman {
     /// If the game has ended (i.e., if [`Hangman::fate()`] is returning
     /// `Some`), this method will return [`Response::GameOver`].
     pub(crate) fn guess(&mut self, guess: char) -> Response {
+        self.guess_by(0, guess)
+    }
+
+    /// Process a guess made by the given player (counting from 0) in a game
+    /// with multiple players.  The player is recorded in the game's
+    /// [history](Hangman::history()); otherwise, this is the same as
+    /// [`Hangman::guess()`].
+    pub(crate) fn guess_by(&mut self, player: usize, guess: char) -> Response {
         if self.fate().is_some() {
             return Response::GameOver;
         }
@@ -165,7 +292,7 @@ impl Hangman {
             Some(b @ false) => {
                 let mut count = 0;
                 for (&wch, known) in self.word.iter().zip(self.known_letters.iter_mut()) {
-                    if wch == guess {
+                    if normalize_char(wch) == guess {
                         debug_assert!(
                             known.is_none(),
                             "Newly-guessed letter should not have already been revealed"
@@ -175,6 +302,11 @@ impl Hangman {
                     }
                 }
                 *b = true;
+                self.history.push(Guess {
+                    letter: guess,
+                    count,
+                    player,
+                });
                 if count > 0 {
                     let won = if self.known_letters.iter().all(Option::is_some) {
                         self.fate = Some(Fate::Won);
@@ -184,16 +316,7 @@ impl Hangman {
                     };
                     Response::GoodGuess { guess, count, won }
                 } else {
-                    if let Some(g) = self.gallows.succ() {
-                        self.gallows = g;
-                    }
-                    let lost = (self.gallows == Gallows::END).then(|| {
-                        let about = Lost {
-                            word: self.word.clone(),
-                        };
-                        self.fate = Some(Fate::Lost(about.clone()));
-                        about
-                    });
+                    let lost = self.add_miss();
                     Response::BadGuess { guess, lost }
                 }
             }
@@ -201,6 +324,65 @@ impl Hangman {
         }
     }
 
+    /// Process a guess at the whole secret word.  If `word` matches the
+    /// secret word (comparing ASCII letters case-insensitively), the rest of
+    /// the word is revealed and the user wins; otherwise, the guess counts as
+    /// a single incorrect guess.  Either way, no letters are recorded in the
+    /// game's [history](Hangman::history()).
+    ///
+    /// If the game has ended, this method returns [`WordResponse::GameOver`].
+    pub(crate) fn guess_word(&mut self, word: &str) -> WordResponse {
+        if self.fate().is_some() {
+            return WordResponse::GameOver;
+        }
+        if word
+            .chars()
+            .map(normalize_char)
+            .eq(self.word.iter().copied().map(normalize_char))
+        {
+            for (&wch, known) in self.word.iter().zip(self.known_letters.iter_mut()) {
+                if let Some(b) = self.letters.get_mut(&normalize_char(wch)) {
+                    *b = true;
+                }
+                *known = Some(wch);
+            }
+            self.fate = Some(Fate::Won);
+            WordResponse::Correct
+        } else {
+            WordResponse::Incorrect {
+                lost: self.add_miss(),
+            }
+        }
+    }
+
+    /// Count an incorrect guess against the user without any letter being
+    /// guessed, as when they run out of time to make a guess.  Returns `Some`
+    /// iff the user lost the game as a result.
+    ///
+    /// If the game has already ended, this does nothing and returns `None`.
+    pub(crate) fn time_out(&mut self) -> Option<Lost> {
+        if self.fate().is_some() {
+            return None;
+        }
+        self.add_miss()
+    }
+
+    /// Record an incorrect guess, advancing the gallows.  Returns `Some` iff
+    /// the user lost the game as a result.
+    fn add_miss(&mut self) -> Option<Lost> {
+        self.misses += 1;
+        if let Some(max) = self.max_misses {
+            self.gallows = Gallows::for_misses(self.misses, max);
+        }
+        (self.gallows == Gallows::END).then(|| {
+            let about = Lost {
+                word: self.word.clone(),
+            };
+            self.fate = Some(Fate::Lost(about.clone()));
+            about
+        })
+    }
+
     /// Returns a mapping from characters in the game's alphabet (with
     /// lowercase ASCII letters converted to uppercase) to either `true` (if
     /// the character has been guessed by the user) or `false` (if the user
@@ -214,8 +396,34 @@ impl Hangman {
         self.gallows
     }
 
-    /// Returns the secret word as revealed to the user so far, with lowercase
-    /// ASCII letters converted to uppercase.  Each element of the slice is
+    /// Returns the number of incorrect guesses made so far
+    pub(crate) fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the number of incorrect guesses that the user can still make
+    /// without losing, or `None` if the game cannot be lost
+    pub(crate) fn misses_remaining(&self) -> Option<usize> {
+        self.max_misses.map(|max| max.saturating_sub(self.misses))
+    }
+
+    /// Returns the guesses made so far (excluding repeated guesses and guesses
+    /// outside the alphabet), in the order they were made
+    pub(crate) fn history(&self) -> &[Guess] {
+        &self.history
+    }
+
+    /// Returns the incorrectly-guessed letters, in the order they were
+    /// guessed
+    pub(crate) fn wrong_guesses(&self) -> impl Iterator<Item = char> + '_ {
+        self.history
+            .iter()
+            .filter(|guess| !guess.is_hit())
+            .map(|guess| guess.letter)
+    }
+
+    /// Returns the secret word as revealed to the user so far, with
+    /// characters in their original case.  Each element of the slice is
     /// either `Some(ch)` (if `ch` was previously guessed successfully by the
     /// user or if `ch` is a character in the secret word that does not appear
     /// in the alphabet and thus was revealed from the start) or `None` (if the
@@ -229,6 +437,288 @@ impl Hangman {
     pub(crate) fn fate(&self) -> Option<Fate> {
         self.fate.clone()
     }
+
+    /// Returns the secret word in its entirety, for showing to a user who
+    /// gives up on the game.  The game itself is unaffected.
+    pub(crate) fn reveal(&self) -> Lost {
+        Lost {
+            word: self.word.clone(),
+        }
+    }
+}
+
+/// One of the two participants in a [`Duel`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Side {
+    Human,
+    Computer,
+}
+
+impl Side {
+    /// Returns the number of the side as a player in the duel's
+    /// [history](Hangman::history())
+    pub(crate) fn player(self) -> usize {
+        match self {
+            Side::Human => 0,
+            Side::Computer => 1,
+        }
+    }
+
+    /// Returns the other side
+    pub(crate) fn opponent(self) -> Side {
+        match self {
+            Side::Human => Side::Computer,
+            Side::Computer => Side::Human,
+        }
+    }
+}
+
+/// A game of Hangman in which two sides take turns guessing letters in the
+/// same secret word.  Each side has its own gallows, advanced only by its own
+/// incorrect guesses.  The side that reveals the final letter wins; a side
+/// whose gallows is completed loses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Duel {
+    /// The shared game, configured with unlimited misses so that its own
+    /// gallows never advances
+    game: Hangman,
+    max_misses: usize,
+    human_misses: usize,
+    computer_misses: usize,
+    winner: Option<Side>,
+}
+
+impl Duel {
+    /// Create a duel over the secret word in `game` (which should not have
+    /// had any guesses made yet) in which each side loses after `max_misses`
+    /// incorrect guesses
+    pub(crate) fn new(mut game: Hangman, max_misses: usize) -> Duel {
+        game.set_max_misses(None);
+        Duel {
+            game,
+            max_misses,
+            human_misses: 0,
+            computer_misses: 0,
+            winner: None,
+        }
+    }
+
+    /// Process a guess by `side`.  The returned [`Response`] is from the point
+    /// of view of the guessing side: `won` is true if `side` revealed the
+    /// final letter, and `lost` is `Some` if `side` completed its own
+    /// gallows.
+    ///
+    /// If the duel has ended, this method returns [`Response::GameOver`].
+    pub(crate) fn guess(&mut self, side: Side, guess: char) -> Response {
+        if self.winner.is_some() {
+            return Response::GameOver;
+        }
+        match self.game.guess_by(side.player(), guess) {
+            r @ Response::GoodGuess { won, .. } => {
+                if won {
+                    self.winner = Some(side);
+                }
+                r
+            }
+            Response::BadGuess { guess, .. } => {
+                let misses = match side {
+                    Side::Human => &mut self.human_misses,
+                    Side::Computer => &mut self.computer_misses,
+                };
+                *misses += 1;
+                let lost = (*misses >= self.max_misses).then(|| {
+                    self.winner = Some(side.opponent());
+                    Lost {
+                        word: self.game.word.clone(),
+                    }
+                });
+                Response::BadGuess { guess, lost }
+            }
+            r => r,
+        }
+    }
+
+    /// Returns the shared game, for inspecting the letters guessed and the
+    /// word as revealed so far
+    pub(crate) fn game(&self) -> &Hangman {
+        &self.game
+    }
+
+    /// Returns the current state of the given side's gallows
+    pub(crate) fn gallows(&self, side: Side) -> Gallows {
+        Gallows::for_misses(self.misses(side), self.max_misses)
+    }
+
+    /// Returns the number of incorrect guesses that the given side can still
+    /// make before losing
+    pub(crate) fn misses_remaining(&self, side: Side) -> usize {
+        self.max_misses.saturating_sub(self.misses(side))
+    }
+
+    fn misses(&self, side: Side) -> usize {
+        match side {
+            Side::Human => self.human_misses,
+            Side::Computer => self.computer_misses,
+        }
+    }
+
+    /// If the duel has ended, returns `Some(side)`, where `side` is the
+    /// winner.  Otherwise, returns `None`.
+    pub(crate) fn winner(&self) -> Option<Side> {
+        self.winner
+    }
+
+    /// If the duel has ended, returns its outcome from the human's point of
+    /// view.  Otherwise, returns `None`.
+    pub(crate) fn fate(&self) -> Option<Fate> {
+        self.winner.map(|side| match side {
+            Side::Human => Fate::Won,
+            Side::Computer => Fate::Lost(Lost {
+                word: self.game.word.clone(),
+            }),
+        })
+    }
+}
+
+/// A game of Hangman played in reverse: the user thinks of a secret word, and
+/// the computer guesses letters in it.  The word itself is never known to the
+/// program; instead, the user answers each guess with the positions at which
+/// the letter occurs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ReverseGame {
+    /// Mapping from normalized characters in the alphabet to whether they've
+    /// been guessed (true) or not (false)
+    letters: BTreeMap<char, bool>,
+    /// The letters of the secret word revealed by the user so far
+    known_letters: Vec<Option<char>>,
+    misses: usize,
+    max_misses: usize,
+    winner: Option<Side>,
+}
+
+impl ReverseGame {
+    /// Create a game in which the computer guesses characters from `alphabet`
+    /// in a secret word of `length` characters, losing after `max_misses`
+    /// incorrect guesses
+    pub(crate) fn new(length: usize, alphabet: &str, max_misses: usize) -> ReverseGame {
+        ReverseGame {
+            letters: alphabet
+                .chars()
+                .map(|c| (normalize_char(c), false))
+                .collect(),
+            known_letters: vec![None; length],
+            misses: 0,
+            max_misses,
+            winner: None,
+        }
+    }
+
+    /// Record the user's answer to a guess of `guess` by the computer.
+    /// `positions` are the 1-based indices in the secret word at which
+    /// `guess` occurs; if it is empty, the guess was incorrect.
+    ///
+    /// If any position is out of range or has already been filled in, an
+    /// error is returned and the game is left unchanged.
+    pub(crate) fn answer(&mut self, guess: char, positions: &[usize]) -> Result<(), AnswerError> {
+        let guess = normalize_char(guess);
+        for &position in positions {
+            match position
+                .checked_sub(1)
+                .and_then(|i| self.known_letters.get(i))
+            {
+                Some(None) => (),
+                Some(Some(_)) => return Err(AnswerError::AlreadyKnown { position }),
+                None => {
+                    return Err(AnswerError::OutOfRange {
+                        position,
+                        length: self.known_letters.len(),
+                    })
+                }
+            }
+        }
+        self.letters.insert(guess, true);
+        if positions.is_empty() {
+            self.misses += 1;
+            if self.misses >= self.max_misses {
+                self.winner = Some(Side::Human);
+            }
+        } else {
+            for &position in positions {
+                self.known_letters[position - 1] = Some(guess);
+            }
+            if self.known_letters.iter().all(Option::is_some) {
+                self.winner = Some(Side::Computer);
+            }
+        }
+        Ok(())
+    }
+
+    /// End the game with the user as the winner, as when the computer runs out
+    /// of letters to guess
+    pub(crate) fn concede(&mut self) {
+        self.winner = Some(Side::Human);
+    }
+
+    /// Returns the current state of the computer's gallows
+    pub(crate) fn gallows(&self) -> Gallows {
+        Gallows::for_misses(self.misses, self.max_misses)
+    }
+
+    /// Returns the number of incorrect guesses that the computer can still
+    /// make before losing
+    pub(crate) fn misses_remaining(&self) -> usize {
+        self.max_misses.saturating_sub(self.misses)
+    }
+
+    /// If the game has ended, returns `Some(side)`, where `side` is the
+    /// winner: [`Side::Computer`] if the computer filled in the whole word,
+    /// [`Side::Human`] if the computer's gallows was completed.  Otherwise,
+    /// returns `None`.
+    pub(crate) fn winner(&self) -> Option<Side> {
+        self.winner
+    }
+}
+
+impl Puzzle for ReverseGame {
+    fn known_letters(&self) -> &[Option<char>] {
+        &self.known_letters
+    }
+
+    fn guessed(&self) -> &BTreeMap<char, bool> {
+        &self.letters
+    }
+}
+
+/// Error returned by [`ReverseGame::answer()`] when the user gives an invalid
+/// position
+#[derive(Copy, Clone, Debug, Eq, Error, PartialEq)]
+pub(crate) enum AnswerError {
+    #[error("position {position} is outside the word (1 to {length})")]
+    OutOfRange { position: usize, length: usize },
+    #[error("position {position} has already been filled in")]
+    AlreadyKnown { position: usize },
+}
+
+/// The state of a game as visible to the guesser, i.e., everything that a
+/// [`Solver`](crate::solver::Solver) can take into account
+pub(crate) trait Puzzle {
+    /// Returns the secret word as revealed so far, with `None` for each
+    /// character that has yet to be guessed
+    fn known_letters(&self) -> &[Option<char>];
+
+    /// Returns a mapping from characters in the alphabet to whether they've
+    /// been guessed
+    fn guessed(&self) -> &BTreeMap<char, bool>;
+}
+
+impl Puzzle for Hangman {
+    fn known_letters(&self) -> &[Option<char>] {
+        Hangman::known_letters(self)
+    }
+
+    fn guessed(&self) -> &BTreeMap<char, bool> {
+        Hangman::guessed(self)
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, Error, PartialEq)]
@@ -237,7 +727,9 @@ pub(crate) enum HangmanError {
     NoAlphabet,
 }
 
-fn normalize_char(c: char) -> char {
+/// Normalize a character for comparison by converting lowercase ASCII letters
+/// to uppercase
+pub(crate) fn normalize_char(c: char) -> char {
     c.to_ascii_uppercase()
 }
 
@@ -245,6 +737,286 @@ fn normalize_char(c: char) -> char {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_gallows_progress() {
+        assert_eq!(Gallows::Start.progress(), (0, 6));
+        assert_eq!(Gallows::AddLeftArm.progress(), (3, 6));
+        assert_eq!(Gallows::END.progress(), (6, 6));
+        assert_eq!(Gallows::for_misses(2, 4).progress(), (3, 6));
+    }
+
+    #[test]
+    fn test_preserve_case() {
+        let mut game = Hangman::new("McDonald".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        assert_eq!(
+            game.guess('d'),
+            Response::GoodGuess {
+                guess: 'D',
+                count: 2,
+                won: false
+            }
+        );
+        assert_eq!(
+            game.known_letters(),
+            [None, None, Some('D'), None, None, None, None, Some('d')]
+        );
+        for ch in "MCONAL".chars() {
+            game.guess(ch);
+        }
+        assert_eq!(
+            game.known_letters()
+                .iter()
+                .copied()
+                .collect::<Option<String>>(),
+            Some(String::from("McDonald"))
+        );
+        assert_eq!(game.fate(), Some(Fate::Won));
+    }
+
+    #[test]
+    fn test_carry_misses() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.set_max_misses(Some(3));
+        game.carry_misses(2);
+        assert_eq!(game.gallows(), Gallows::AddRightArm);
+        assert!(matches!(
+            game.guess('x'),
+            Response::BadGuess { lost: Some(_), .. }
+        ));
+    }
+
+    #[test]
+    fn test_time_out() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.set_max_misses(Some(2));
+        assert_eq!(game.time_out(), None);
+        assert_eq!(game.misses(), 1);
+        assert_eq!(game.gallows(), Gallows::AddLeftArm);
+        assert!(game.history().is_empty());
+        assert_eq!(
+            game.time_out(),
+            Some(Lost {
+                word: vec!['c', 'a', 't']
+            })
+        );
+        assert!(matches!(game.fate(), Some(Fate::Lost(_))));
+        assert_eq!(game.time_out(), None);
+        assert_eq!(game.misses(), 2);
+    }
+
+    #[test]
+    fn test_guess_word() {
+        let mut game = Hangman::new("McDonald".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.set_max_misses(Some(2));
+        game.guess('d');
+        assert_eq!(
+            game.guess_word("mcdonnell"),
+            WordResponse::Incorrect { lost: None }
+        );
+        assert_eq!(game.misses(), 1);
+        assert_eq!(game.guess_word("MCDONALD"), WordResponse::Correct);
+        assert_eq!(game.fate(), Some(Fate::Won));
+        assert_eq!(
+            game.known_letters(),
+            "McDonald".chars().map(Some).collect::<Vec<_>>()
+        );
+        assert_eq!(game.guessed().get(&'M'), Some(&true));
+        assert_eq!(game.history().len(), 1);
+        assert_eq!(game.guess_word("McDonald"), WordResponse::GameOver);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.guess('a');
+        assert_eq!(
+            game.reveal(),
+            Lost {
+                word: vec!['c', 'a', 't']
+            }
+        );
+        assert_eq!(game.fate(), None);
+    }
+
+    #[test]
+    fn test_unlimited_misses() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.set_max_misses(None);
+        for guess in "BDEFGHIJKLMNOPQRS".chars() {
+            assert_eq!(game.guess(guess), Response::BadGuess { guess, lost: None });
+        }
+        assert_eq!(game.misses(), 17);
+        assert_eq!(game.gallows(), Gallows::Start);
+        assert_eq!(game.fate(), None);
+        game.guess('c');
+        game.guess('a');
+        assert!(matches!(
+            game.guess('t'),
+            Response::GoodGuess { won: true, .. }
+        ));
+        assert_eq!(game.fate(), Some(Fate::Won));
+    }
+
+    #[test]
+    fn test_history() {
+        let mut game = Hangman::new("abacus".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.guess('a');
+        game.guess('e');
+        game.guess('A');
+        game.guess('!');
+        game.guess('s');
+        assert_eq!(
+            game.history(),
+            [
+                Guess {
+                    letter: 'A',
+                    count: 2,
+                    player: 0
+                },
+                Guess {
+                    letter: 'E',
+                    count: 0,
+                    player: 0
+                },
+                Guess {
+                    letter: 'S',
+                    count: 1,
+                    player: 0
+                },
+            ]
+        );
+        game.guess('t');
+        assert_eq!(game.wrong_guesses().collect::<Vec<_>>(), ['E', 'T']);
+    }
+
+    #[test]
+    fn test_duel_reveal_last_letter() {
+        let game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        let mut duel = Duel::new(game, 6);
+        duel.guess(Side::Human, 'c');
+        duel.guess(Side::Computer, 'e');
+        duel.guess(Side::Human, 'x');
+        duel.guess(Side::Computer, 'a');
+        assert_eq!(duel.gallows(Side::Human), Gallows::AddHead);
+        assert_eq!(duel.gallows(Side::Computer), Gallows::AddHead);
+        assert_eq!(duel.winner(), None);
+        assert_eq!(
+            duel.guess(Side::Human, 't'),
+            Response::GoodGuess {
+                guess: 'T',
+                count: 1,
+                won: true
+            }
+        );
+        assert_eq!(duel.winner(), Some(Side::Human));
+        assert_eq!(duel.guess(Side::Computer, 'z'), Response::GameOver);
+    }
+
+    #[test]
+    fn test_duel_hanged() {
+        let game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        let mut duel = Duel::new(game, 2);
+        duel.guess(Side::Human, 'c');
+        duel.guess(Side::Computer, 'e');
+        duel.guess(Side::Human, 'a');
+        assert_eq!(
+            duel.guess(Side::Computer, 'i'),
+            Response::BadGuess {
+                guess: 'I',
+                lost: Some(Lost {
+                    word: vec!['c', 'a', 't']
+                })
+            }
+        );
+        assert_eq!(duel.gallows(Side::Computer), Gallows::END);
+        assert_eq!(duel.gallows(Side::Human), Gallows::Start);
+        assert_eq!(duel.misses_remaining(Side::Computer), 0);
+        assert_eq!(duel.misses_remaining(Side::Human), 2);
+        assert_eq!(duel.winner(), Some(Side::Human));
+    }
+
+    #[test]
+    fn test_reverse_solved() {
+        let mut game = ReverseGame::new(3, ASCII_ALPHABET, 2);
+        game.answer('e', &[]).unwrap();
+        assert_eq!(game.misses_remaining(), 1);
+        game.answer('a', &[2]).unwrap();
+        assert_eq!(
+            game.answer('t', &[4]),
+            Err(AnswerError::OutOfRange {
+                position: 4,
+                length: 3
+            })
+        );
+        assert_eq!(
+            game.answer('t', &[3, 2]),
+            Err(AnswerError::AlreadyKnown { position: 2 })
+        );
+        assert_eq!(game.guessed().get(&'T'), Some(&false));
+        game.answer('t', &[3]).unwrap();
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.gallows(), Gallows::for_misses(1, 2));
+        game.answer('c', &[1]).unwrap();
+        assert_eq!(game.known_letters(), [Some('C'), Some('A'), Some('T')]);
+        assert_eq!(game.winner(), Some(Side::Computer));
+    }
+
+    #[test]
+    fn test_reverse_hanged() {
+        let mut game = ReverseGame::new(3, ASCII_ALPHABET, 2);
+        game.answer('e', &[]).unwrap();
+        game.answer('a', &[]).unwrap();
+        assert_eq!(game.gallows(), Gallows::END);
+        assert_eq!(game.winner(), Some(Side::Human));
+    }
+
+    #[test]
+    fn test_fewer_misses() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.set_max_misses(Some(3));
+        assert_eq!(
+            game.guess('x'),
+            Response::BadGuess {
+                guess: 'X',
+                lost: None
+            }
+        );
+        assert_eq!(game.gallows(), Gallows::AddTorso);
+        game.guess('y');
+        assert_eq!(game.gallows(), Gallows::AddRightArm);
+        assert_eq!(
+            game.guess('z'),
+            Response::BadGuess {
+                guess: 'Z',
+                lost: Some(Lost {
+                    word: vec!['c', 'a', 't']
+                })
+            }
+        );
+        assert_eq!(game.gallows(), Gallows::END);
+    }
+
+    #[test]
+    fn test_more_misses() {
+        let stages = (0..=8)
+            .map(|misses| Gallows::for_misses(misses, 8))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stages,
+            [
+                Gallows::Start,
+                Gallows::Start,
+                Gallows::AddHead,
+                Gallows::AddTorso,
+                Gallows::AddLeftArm,
+                Gallows::AddLeftArm,
+                Gallows::AddRightArm,
+                Gallows::AddLeftLeg,
+                Gallows::AddRightLeg,
+            ]
+        );
+    }
+
     #[test]
     fn test_gallows_end() {
         let iter = std::iter::successors(Some(Gallows::Start), |&g| g.succ());
@@ -0,0 +1,43 @@
+/// The bundled list of words to reject, one per line, with `#` comments
+static DENY_LIST: &str = include_str!("profanity.txt");
+
+/// Returns true iff any of the words in `s` (i.e., its maximal runs of
+/// letters) is on the deny-list, ignoring case.  Only whole words are
+/// matched, so that innocent words containing a profanity (e.g., "class")
+/// are left alone.
+pub(crate) fn is_profane(s: &str) -> bool {
+    s.split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .any(|w| {
+            let w = w.to_lowercase();
+            DENY_LIST
+                .lines()
+                .filter(|ln| !ln.starts_with('#'))
+                .any(|ln| ln == w)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_profane() {
+        assert!(is_profane("bullshit"));
+        assert!(is_profane("Bullshit Bingo"));
+        assert!(is_profane("holy-crap"));
+        assert!(!is_profane("class"));
+        assert!(!is_profane("Scunthorpe"));
+        assert!(!is_profane("elephant"));
+    }
+
+    #[test]
+    fn test_deny_list_normalized() {
+        for ln in DENY_LIST.lines().filter(|ln| !ln.starts_with('#')) {
+            assert!(
+                !ln.is_empty() && ln.chars().all(char::is_lowercase),
+                "deny-list entry {ln:?} will never match"
+            );
+        }
+    }
+}
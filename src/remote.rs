@@ -0,0 +1,161 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Download the document at `url`, keeping a copy in the user's cache
+/// directory.  If a cached copy exists, the server is asked to send the
+/// document only if it has changed since then, and the cached copy is used if
+/// the server cannot be reached.
+pub(crate) fn fetch(url: &str) -> anyhow::Result<String> {
+    fetch_with_cache(url, CachedDocument::path(url).as_deref())
+}
+
+fn fetch_with_cache(url: &str, cache_path: Option<&Path>) -> anyhow::Result<String> {
+    let cached = match cache_path {
+        Some(path) => CachedDocument::load(path).filter(|c| c.url == url),
+        None => None,
+    };
+    let mut request = ureq::get(url);
+    if let Some(c) = &cached {
+        if let Some(etag) = &c.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &c.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+    }
+    let response = match (request.call(), cached) {
+        (Ok(r), Some(c)) if r.status() == 304 => return Ok(c.body),
+        (Ok(r), _) => r,
+        // The server couldn't be reached, so make do with what we have
+        (Err(ureq::Error::Transport(_)), Some(c)) => return Ok(c.body),
+        (Err(e), _) => {
+            return Err(e).with_context(|| format!("failed to fetch word list from {url}"))
+        }
+    };
+    let etag = response.header("ETag").map(String::from);
+    let last_modified = response.header("Last-Modified").map(String::from);
+    let body = response
+        .into_string()
+        .with_context(|| format!("failed to read word list from {url}"))?;
+    if let Some(path) = cache_path {
+        if etag.is_some() || last_modified.is_some() {
+            // Failing to save the cache only means downloading the whole
+            // document again next time
+            let _ = CachedDocument {
+                url: url.to_owned(),
+                etag,
+                last_modified,
+                body: body.clone(),
+            }
+            .save(path);
+        }
+    }
+    Ok(body)
+}
+
+/// A downloaded document stored as JSON in the user's cache directory, along
+/// with the information needed to check whether it is still current
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct CachedDocument {
+    url: String,
+    /// The value of the `ETag` header sent with the document
+    etag: Option<String>,
+    /// The value of the `Last-Modified` header sent with the document
+    last_modified: Option<String>,
+    body: String,
+}
+
+impl CachedDocument {
+    /// Returns the path at which to cache the document at `url`, or `None` if
+    /// the user's cache directory could not be determined
+    fn path(url: &str) -> Option<PathBuf> {
+        Some(
+            dirs::cache_dir()?
+                .join("hangman")
+                .join("words")
+                .join(format!("{:016x}.json", fnv1a(url))),
+        )
+    }
+
+    /// Read a cached document from `path`, returning `None` if it does not
+    /// exist or cannot be read (in which case it's as though nothing was
+    /// cached)
+    fn load(path: &Path) -> Option<CachedDocument> {
+        let src = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&src).ok()
+    }
+
+    /// Write the document to `path`, creating parent directories as needed
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create cache directory {}", parent.display())
+            })?;
+        }
+        let src = serde_json::to_string(self).context("failed to serialize cached word list")?;
+        fs::write(path, src)
+            .with_context(|| format!("failed to write cached word list {}", path.display()))
+    }
+}
+
+/// Compute the 64-bit FNV-1a hash of `s`.  Unlike the hashers in `std`, this
+/// is guaranteed to give the same result across Rust versions, so it can be
+/// used to name files.
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::serve;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_revalidate_and_fall_back() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let cache_path = tmpdir.path().join("words.json");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/words.txt", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let first = serve(&listener, "200 OK", "ETag: \"v1\"\r\n", "cat\n");
+            let second = serve(&listener, "304 Not Modified", "", "");
+            (first, second)
+        });
+        assert_eq!(fetch_with_cache(&url, Some(&cache_path)).unwrap(), "cat\n");
+        assert_eq!(fetch_with_cache(&url, Some(&cache_path)).unwrap(), "cat\n");
+        let (first, second) = server.join().unwrap();
+        assert!(!first.iter().any(|h| h.starts_with("If-None-Match")));
+        assert!(second.contains(&String::from("If-None-Match: \"v1\"")));
+        // The server is gone now, so the cached copy is used.
+        assert_eq!(fetch_with_cache(&url, Some(&cache_path)).unwrap(), "cat\n");
+    }
+
+    #[test]
+    fn test_broken_cache() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let corrupt = tmpdir.path().join("corrupt.json");
+        fs::write(&corrupt, "{\"url\": ").unwrap();
+        // A cache path whose parent is a file can't be written to
+        let unwritable = corrupt.join("words.json");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/words.txt", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            serve(&listener, "200 OK", "ETag: \"v1\"\r\n", "cat\n");
+            serve(&listener, "200 OK", "ETag: \"v1\"\r\n", "dog\n");
+        });
+        assert_eq!(fetch_with_cache(&url, Some(&corrupt)).unwrap(), "cat\n");
+        assert_eq!(fetch_with_cache(&url, Some(&unwritable)).unwrap(), "dog\n");
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,55 @@
+use crate::model::{Fate, Hangman};
+
+/// Points awarded for each correct guess in a won game
+pub(crate) const POINTS_PER_HIT: usize = 10;
+
+/// Points awarded for each incorrect guess that the user could still have
+/// made when they won
+pub(crate) const POINTS_PER_SPARE_MISS: usize = 5;
+
+/// Returns the number of points earned in `game`.
+///
+/// A game that has not been won is worth nothing.  A won game earns
+/// [`POINTS_PER_HIT`] for each correct guess plus [`POINTS_PER_SPARE_MISS`]
+/// for each incorrect guess left in reserve (none if misses are unlimited).
+pub(crate) fn score(game: &Hangman) -> usize {
+    if game.fate() != Some(Fate::Won) {
+        return 0;
+    }
+    let hits = game.history().iter().filter(|g| g.is_hit()).count();
+    let spare = game.misses_remaining().unwrap_or(0);
+    hits * POINTS_PER_HIT + spare * POINTS_PER_SPARE_MISS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ASCII_ALPHABET;
+
+    #[test]
+    fn test_score_won() {
+        let mut game = Hangman::new("abacus".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        for ch in "aexbcus".chars() {
+            game.guess(ch);
+        }
+        assert_eq!(game.fate(), Some(Fate::Won));
+        assert_eq!(score(&game), 5 * 10 + 4 * 5);
+    }
+
+    #[test]
+    fn test_score_unfinished() {
+        let mut game = Hangman::new("abacus".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.guess('a');
+        assert_eq!(score(&game), 0);
+    }
+
+    #[test]
+    fn test_score_unlimited_misses() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.set_max_misses(None);
+        for ch in "xcat".chars() {
+            game.guess(ch);
+        }
+        assert_eq!(score(&game), 30);
+    }
+}
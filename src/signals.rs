@@ -0,0 +1,88 @@
+use signal_hook::consts::{SIGHUP, SIGTERM, SIGTSTP};
+use signal_hook::flag;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// A signal received while the game screen was up
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Caught {
+    /// SIGTSTP, sent by something other than the user pressing Ctrl-Z (which
+    /// the game receives as a key press, as the terminal is in raw mode)
+    Stop,
+    /// SIGTERM or SIGHUP, named by the field
+    Terminate(&'static str),
+}
+
+/// The flags set by the signal handlers, which are installed the first time
+/// the game screen is brought up
+#[derive(Debug)]
+struct Flags {
+    /// Set when SIGTSTP is received
+    stop: Arc<AtomicBool>,
+    /// The number of the terminating signal most recently received, or 0
+    terminate: Arc<AtomicUsize>,
+    /// Whether the game screen is down, in which case the signals just have
+    /// their default effects
+    idle: Arc<AtomicBool>,
+}
+
+impl Flags {
+    fn get() -> &'static Flags {
+        static FLAGS: OnceLock<Flags> = OnceLock::new();
+        FLAGS.get_or_init(|| {
+            let flags = Flags {
+                stop: Arc::default(),
+                terminate: Arc::default(),
+                idle: Arc::new(AtomicBool::new(true)),
+            };
+            // If a signal's handlers can't be installed, it keeps its default
+            // effect even while the screen is up.
+            if flag::register_conditional_default(SIGTSTP, Arc::clone(&flags.idle)).is_ok() {
+                let _ = flag::register(SIGTSTP, Arc::clone(&flags.stop));
+            }
+            for signal in [SIGTERM, SIGHUP] {
+                if flag::register_conditional_default(signal, Arc::clone(&flags.idle)).is_ok() {
+                    let _ = flag::register_usize(
+                        signal,
+                        Arc::clone(&flags.terminate),
+                        usize::try_from(signal).unwrap_or_default(),
+                    );
+                }
+            }
+            flags
+        })
+    }
+}
+
+/// Start catching SIGTSTP, SIGTERM, and SIGHUP, to be reported by [`caught()`]
+/// instead of taking effect immediately
+pub(crate) fn arm() {
+    let flags = Flags::get();
+    flags.stop.store(false, Ordering::SeqCst);
+    flags.terminate.store(0, Ordering::SeqCst);
+    flags.idle.store(false, Ordering::SeqCst);
+}
+
+/// Let SIGTSTP, SIGTERM, and SIGHUP take effect immediately again
+pub(crate) fn disarm() {
+    Flags::get().idle.store(true, Ordering::SeqCst);
+}
+
+/// Returns the most pressing signal received since [`arm()`] was called or
+/// the last time the signal was returned
+pub(crate) fn caught() -> Option<Caught> {
+    let flags = Flags::get();
+    match flags.terminate.load(Ordering::SeqCst) {
+        0 => flags
+            .stop
+            .swap(false, Ordering::SeqCst)
+            .then_some(Caught::Stop),
+        n if i32::try_from(n) == Ok(SIGHUP) => Some(Caught::Terminate("SIGHUP")),
+        _ => Some(Caught::Terminate("SIGTERM")),
+    }
+}
+
+/// Stop the process as though by SIGTSTP, returning once it's continued
+pub(crate) fn stop() -> std::io::Result<()> {
+    signal_hook::low_level::emulate_default_handler(SIGTSTP)
+}
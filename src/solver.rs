@@ -0,0 +1,148 @@
+use crate::model::{normalize_char, Puzzle};
+use crate::words::WordList;
+use std::collections::{BTreeMap, HashMap};
+
+/// The letters of the English alphabet in descending order of frequency in
+/// typical English text, used to rank letters when no dictionary words are
+/// available
+static ENGLISH_FREQUENCY: &str = "ETAOINSHRDLCUMWFGYPBVKJXQZ";
+
+/// Letter-guessing strategy based on letter frequencies, optionally informed
+/// by a dictionary of possible secret words
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Solver {
+    /// Dictionary words, normalized in the same way as
+    /// [`Hangman`](crate::model::Hangman) normalizes
+    /// the secret word, indexed by length in characters
+    words: HashMap<usize, Vec<Vec<char>>>,
+}
+
+impl Solver {
+    /// Create a solver that draws on the words in `list`
+    pub(crate) fn new(list: &WordList) -> Solver {
+        Solver::from_words(
+            list.iter()
+                .map(|w| w.word.as_ref().chars().map(normalize_char).collect()),
+        )
+    }
+
+    fn from_words<I: IntoIterator<Item = Vec<char>>>(iter: I) -> Solver {
+        let mut words = HashMap::<usize, Vec<Vec<char>>>::new();
+        for w in iter {
+            words.entry(w.len()).or_default().push(w);
+        }
+        Solver { words }
+    }
+
+    /// Returns true iff the solver has no dictionary words
+    pub(crate) fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns an iterator over the dictionary words that are consistent with
+    /// everything the user knows about the secret word in `game`: the revealed
+    /// characters and the letters already guessed
+    pub(crate) fn candidates<'a, P: Puzzle>(
+        &'a self,
+        game: &'a P,
+    ) -> impl Iterator<Item = &'a [char]> + 'a {
+        let pattern = game.known_letters();
+        let guessed = game.guessed();
+        self.words
+            .get(&pattern.len())
+            .into_iter()
+            .flatten()
+            .filter(move |word| {
+                std::iter::zip(word.iter(), pattern).all(|(ch, known)| match known {
+                    Some(k) => *ch == normalize_char(*k),
+                    None => guessed.get(ch) == Some(&false),
+                })
+            })
+            .map(Vec::as_slice)
+    }
+
+    /// Suggest the best letter for the user to guess next in `game`, or `None`
+    /// if there are no letters left to guess.
+    ///
+    /// If any dictionary words are consistent with the game so far, the
+    /// unguessed letter occurring in the most such words is suggested.
+    /// Otherwise (or in case of a tie), letters are ranked by their frequency
+    /// in English.
+    pub(crate) fn suggest<P: Puzzle>(&self, game: &P) -> Option<char> {
+        let mut counts = game
+            .guessed()
+            .iter()
+            .filter(|&(_, &guessed)| !guessed)
+            .map(|(&ch, _)| (ch, 0usize))
+            .collect::<BTreeMap<_, _>>();
+        for word in self.candidates(game) {
+            let mut seen = word.to_vec();
+            seen.sort_unstable();
+            seen.dedup();
+            for ch in seen {
+                if let Some(n) = counts.get_mut(&ch) {
+                    *n += 1;
+                }
+            }
+        }
+        counts
+            .into_iter()
+            .max_by_key(|&(ch, n)| (n, std::cmp::Reverse(english_rank(ch))))
+            .map(|(ch, _)| ch)
+    }
+}
+
+/// Returns the position of `ch` in [`ENGLISH_FREQUENCY`], with characters
+/// outside the English alphabet ranked after all English letters
+fn english_rank(ch: char) -> usize {
+    ENGLISH_FREQUENCY
+        .chars()
+        .position(|c| c == ch)
+        .unwrap_or(ENGLISH_FREQUENCY.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Hangman, ASCII_ALPHABET};
+
+    fn solver(words: &[&str]) -> Solver {
+        Solver::from_words(words.iter().map(|w| w.chars().collect()))
+    }
+
+    #[test]
+    fn test_suggest_without_dictionary() {
+        let mut game = Hangman::new("quiz".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        let solver = Solver::default();
+        assert_eq!(solver.suggest(&game), Some('E'));
+        game.guess('e');
+        assert_eq!(solver.suggest(&game), Some('T'));
+    }
+
+    #[test]
+    fn test_suggest_from_candidates() {
+        let mut game = Hangman::new("cat".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        let solver = solver(&["CAT", "BAT", "COT", "DOG", "HORSE"]);
+        assert_eq!(solver.suggest(&game), Some('T'));
+        game.guess('t');
+        game.guess('o');
+        assert_eq!(
+            solver.candidates(&game).collect::<Vec<_>>(),
+            [['C', 'A', 'T'], ['B', 'A', 'T']]
+        );
+        assert_eq!(solver.suggest(&game), Some('A'));
+        game.guess('a');
+        assert_eq!(solver.suggest(&game), Some('C'));
+    }
+
+    #[test]
+    fn test_candidates_exclude_revealed_letters() {
+        let mut game = Hangman::new("aba".parse().unwrap(), ASCII_ALPHABET).unwrap();
+        game.guess('a');
+        let solver = solver(&["ABA", "AAA", "ACA"]);
+        assert_eq!(
+            solver.candidates(&game).collect::<Vec<_>>(),
+            [['A', 'B', 'A'], ['A', 'C', 'A']]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use time::{Date, OffsetDateTime};
+
+/// Persistent record of the user's play history, stored as JSON in the
+/// user's local data directory
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub(crate) struct State {
+    /// Number of games played to completion
+    pub(crate) played: u32,
+    /// Number of games won
+    pub(crate) won: u32,
+    /// Number of consecutive games won, ending with the most recent game
+    pub(crate) streak: u32,
+    /// Longest winning streak ever achieved
+    pub(crate) best_streak: u32,
+    /// The (UTC) date on which the daily puzzle was last played, if ever
+    pub(crate) last_daily: Option<Date>,
+}
+
+impl State {
+    /// Returns the path to the state file, or `None` if the user's local data
+    /// directory could not be determined
+    pub(crate) fn path() -> Option<PathBuf> {
+        Some(dirs::data_local_dir()?.join("hangman").join("state.json"))
+    }
+
+    /// Read the state file.  If the file does not exist (or its location
+    /// cannot be determined), a default `State` is returned.
+    pub(crate) fn load() -> anyhow::Result<State> {
+        let Some(path) = State::path() else {
+            return Ok(State::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(src) => serde_json::from_str(&src)
+                .with_context(|| format!("failed to parse state file {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(State::default()),
+            Err(e) => {
+                Err(e).with_context(|| format!("failed to read state file {}", path.display()))
+            }
+        }
+    }
+
+    /// Write the state to the state file, creating parent directories as
+    /// needed.  If the file's location cannot be determined, nothing is
+    /// written.
+    pub(crate) fn save(&self) -> anyhow::Result<()> {
+        let Some(path) = State::path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create state directory {}", parent.display())
+            })?;
+        }
+        let src = serde_json::to_string_pretty(self).context("failed to serialize state")?;
+        fs::write(&path, src + "\n")
+            .with_context(|| format!("failed to write state file {}", path.display()))
+    }
+
+    /// Update the statistics to account for a completed game
+    pub(crate) fn record_game(&mut self, won: bool) {
+        self.played = self.played.saturating_add(1);
+        if won {
+            self.won = self.won.saturating_add(1);
+            self.streak = self.streak.saturating_add(1);
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+    }
+
+    /// Returns true iff the daily puzzle has been played on the given date
+    pub(crate) fn played_daily_on(&self, date: Date) -> bool {
+        self.last_daily == Some(date)
+    }
+
+    /// Returns a [`Status`] summarizing the state as of the given date
+    pub(crate) fn status(&self, today: Date) -> Status<'_> {
+        Status { state: self, today }
+    }
+}
+
+/// Returns the current date in UTC
+pub(crate) fn today() -> Date {
+    OffsetDateTime::now_utc().date()
+}
+
+/// The Julian day number of 2024-01-01, the date of daily puzzle #1
+const FIRST_PUZZLE_DAY: i32 = 2_460_311;
+
+/// Returns the number of the daily puzzle for the given date, for players to
+/// refer to when sharing their results
+pub(crate) fn puzzle_number(date: Date) -> i32 {
+    date.to_julian_day() - FIRST_PUZZLE_DAY + 1
+}
+
+/// A summary of a [`State`] for display by the `status` subcommand.
+///
+/// The `Display` implementation produces a human-readable multiline report;
+/// the alternate form (`{:#}`) produces a single compact line suitable for
+/// embedding in shell prompts and status bars.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Status<'a> {
+    state: &'a State,
+    today: Date,
+}
+
+impl fmt::Display for Status<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let daily = self.state.played_daily_on(self.today);
+        if f.alternate() {
+            write!(
+                f,
+                "streak:{} daily:{}",
+                self.state.streak,
+                if daily { "done" } else { "todo" }
+            )
+        } else {
+            writeln!(f, "Current streak: {}", self.state.streak)?;
+            writeln!(f, "Best streak:    {}", self.state.best_streak)?;
+            writeln!(
+                f,
+                "Games played:   {} ({} won)",
+                self.state.played, self.state.won
+            )?;
+            write!(
+                f,
+                "Daily puzzle:   {}",
+                if daily {
+                    "played today"
+                } else {
+                    "not yet played today"
+                }
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Month;
+
+    #[test]
+    fn test_record_game() {
+        let mut state = State::default();
+        state.record_game(true);
+        state.record_game(true);
+        state.record_game(false);
+        state.record_game(true);
+        assert_eq!(
+            state,
+            State {
+                played: 4,
+                won: 3,
+                streak: 1,
+                best_streak: 2,
+                last_daily: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_porcelain_status() {
+        let today = Date::from_calendar_date(2024, Month::March, 14).unwrap();
+        let mut state = State {
+            streak: 3,
+            ..State::default()
+        };
+        assert_eq!(format!("{:#}", state.status(today)), "streak:3 daily:todo");
+        state.last_daily = Some(today);
+        assert_eq!(format!("{:#}", state.status(today)), "streak:3 daily:done");
+    }
+
+    #[test]
+    fn test_puzzle_number() {
+        let first = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+        assert_eq!(puzzle_number(first), 1);
+        let pi_day = Date::from_calendar_date(2024, Month::March, 14).unwrap();
+        assert_eq!(puzzle_number(pi_day), 74);
+    }
+
+    #[test]
+    fn test_deserialize_partial() {
+        let state = serde_json::from_str::<State>(r#"{"streak": 2}"#).unwrap();
+        assert_eq!(
+            state,
+            State {
+                streak: 2,
+                ..State::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+
+/// Serve one HTTP request on `listener` with the given status line, extra
+/// headers, and body, returning the request line and headers
+pub(crate) fn serve(
+    listener: &TcpListener,
+    status: &str,
+    headers: &str,
+    body: &str,
+) -> Vec<String> {
+    let (stream, _) = listener
+        .accept()
+        .expect("accepting a connection should not fail");
+    let mut reader = BufReader::new(stream);
+    let mut received = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .expect("reading a request line should not fail");
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        received.push(line.to_owned());
+    }
+    write!(
+        reader.get_mut(),
+        "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .expect("writing a response should not fail");
+    received
+}
@@ -0,0 +1,600 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{
+    de::{Deserializer, Unexpected, Visitor},
+    Deserialize,
+};
+use std::ffi::OsStr;
+use std::fmt;
+use thiserror::Error;
+
+/// The colors & text attributes with which the parts of the game display are
+/// drawn
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Theme {
+    /// The gallows (or, in kids mode, the dancer)
+    pub(crate) gallows: Style,
+    /// The body part added to the gallows by the latest incorrect guess
+    pub(crate) new_part: Style,
+    /// The letters revealed by the latest correct guess
+    pub(crate) revealed: Style,
+    /// The suggested next guess in assist mode
+    pub(crate) suggestion: Style,
+    /// The message beneath the secret word
+    pub(crate) message: Style,
+    /// The line showing the hint and category
+    pub(crate) hint: Style,
+    /// If set, styles applied on top of `gallows` to show how much trouble
+    /// the player is in: while more than half of the allowed misses remain,
+    /// while two or more remain, and once only one or none remain
+    pub(crate) danger: Option<[Style; 3]>,
+}
+
+impl Theme {
+    /// A theme with no colors or attributes at all
+    pub(crate) const PLAIN: Theme = Theme {
+        gallows: Style::new(),
+        new_part: Style::new(),
+        revealed: Style::new(),
+        suggestion: Style::new(),
+        message: Style::new(),
+        hint: Style::new(),
+        danger: None,
+    };
+
+    /// The names of the builtin themes, in the order they're listed in the
+    /// documentation
+    pub(crate) const BUILTIN: [&'static str; 5] = [
+        "default",
+        "vivid",
+        "high-contrast",
+        "deuteranopia",
+        "monochrome-bold",
+    ];
+
+    /// Returns the builtin theme with the given name
+    pub(crate) fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::default()),
+            "vivid" => Some(Theme {
+                gallows: Style::new().fg(Color::Yellow),
+                new_part: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+                revealed: Style::new().fg(Color::Green).add_modifier(Modifier::BOLD),
+                suggestion: Style::new().add_modifier(Modifier::REVERSED),
+                message: Style::new().fg(Color::Cyan),
+                hint: Style::new().add_modifier(Modifier::ITALIC),
+                danger: Some([
+                    Style::new().fg(Color::Green),
+                    Style::new().fg(Color::Yellow),
+                    Style::new().fg(Color::Red),
+                ]),
+            }),
+            // The remaining themes mark the highlights with attributes or with
+            // colors other than red & green, so that they can be told apart
+            // without distinguishing those colors.
+            "high-contrast" => Some(Theme {
+                gallows: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+                new_part: Style::new()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+                revealed: Style::new()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                suggestion: Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                message: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+                hint: Style::new().fg(Color::White),
+                danger: None,
+            }),
+            // Blue & orange, which remain distinct with red-green color
+            // blindness
+            "deuteranopia" => Some(Theme {
+                gallows: Style::new(),
+                new_part: Style::new()
+                    .fg(Color::Indexed(208))
+                    .add_modifier(Modifier::BOLD),
+                revealed: Style::new()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+                suggestion: Style::new().add_modifier(Modifier::REVERSED),
+                message: Style::new(),
+                hint: Style::new(),
+                danger: None,
+            }),
+            "monochrome-bold" => Some(Theme {
+                gallows: Style::new(),
+                new_part: Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                revealed: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                suggestion: Style::new().add_modifier(Modifier::REVERSED),
+                message: Style::new(),
+                hint: Style::new(),
+                danger: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            gallows: Style::new(),
+            new_part: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            revealed: Style::new().add_modifier(Modifier::BOLD),
+            suggestion: Style::new().add_modifier(Modifier::REVERSED),
+            message: Style::new(),
+            hint: Style::new(),
+            danger: None,
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the theme with any colors that can't be displayed at `depth`
+    /// replaced by the nearest colors that can
+    pub(crate) fn downsample(self, depth: ColorDepth) -> Theme {
+        let f = |style: Style| Style {
+            fg: style.fg.map(|c| depth.fit(c)),
+            bg: style.bg.map(|c| depth.fit(c)),
+            ..style
+        };
+        Theme {
+            gallows: f(self.gallows),
+            new_part: f(self.new_part),
+            revealed: f(self.revealed),
+            suggestion: f(self.suggestion),
+            message: f(self.message),
+            hint: f(self.hint),
+            danger: self.danger.map(|styles| styles.map(f)),
+        }
+    }
+
+    /// Returns the style in which to draw a gallows with `parts` of its
+    /// `total` body parts drawn
+    pub(crate) fn gallows_at(&self, parts: usize, total: usize) -> Style {
+        let Some([safe, warning, danger]) = self.danger else {
+            return self.gallows;
+        };
+        let remaining = total.saturating_sub(parts);
+        self.gallows.patch(if remaining <= 1 {
+            danger
+        } else if remaining * 2 <= total {
+            warning
+        } else {
+            safe
+        })
+    }
+}
+
+/// The range of colors that a terminal can display
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum ColorDepth {
+    /// The 16 basic ANSI colors
+    Ansi16,
+    /// The 256-color xterm palette
+    Ansi256,
+    /// Any 24-bit RGB color
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// The RGB values of the 16 basic colors, as used by xterm
+    const BASIC: [(Color, (u8, u8, u8)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+
+    /// The intensities of each component in the 6×6×6 color cube of the
+    /// 256-color palette
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    /// Determine the terminal's color depth from the environment: a
+    /// `COLORTERM` of "truecolor" or "24bit" means 24-bit color, a `TERM`
+    /// ending in "256color" means the 256-color palette, and anything else
+    /// means just the basic colors.
+    pub(crate) fn detect() -> ColorDepth {
+        ColorDepth::from_env(
+            std::env::var_os("COLORTERM").as_deref(),
+            std::env::var_os("TERM").as_deref(),
+        )
+    }
+
+    fn from_env(colorterm: Option<&OsStr>, term: Option<&OsStr>) -> ColorDepth {
+        if colorterm.is_some_and(|c| c == "truecolor" || c == "24bit") {
+            ColorDepth::TrueColor
+        } else if term.is_some_and(|t| t.to_string_lossy().ends_with("256color")) {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Returns `color` if it can be displayed at this depth, or else the
+    /// nearest color that can
+    fn fit(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => ColorDepth::nearest_indexed((r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => ColorDepth::nearest_basic((r, g, b)),
+            (ColorDepth::Ansi16, Color::Indexed(i)) if i >= 16 => {
+                ColorDepth::nearest_basic(ColorDepth::indexed_rgb(i))
+            }
+            _ => color,
+        }
+    }
+
+    /// Returns the color in the 256-color palette (outside of the basic
+    /// colors, whose exact values vary between terminals) nearest to `rgb`
+    fn nearest_indexed(rgb: (u8, u8, u8)) -> Color {
+        let level = |c: u8| {
+            (0..6u8)
+                .min_by_key(|&i| ColorDepth::CUBE_LEVELS[usize::from(i)].abs_diff(c))
+                .unwrap_or_default()
+        };
+        let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+        let average = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+        let gray = 232
+            + u8::try_from(average.saturating_sub(3) / 10)
+                .unwrap_or(23)
+                .min(23);
+        [cube, gray]
+            .into_iter()
+            .min_by_key(|&i| distance(ColorDepth::indexed_rgb(i), rgb))
+            .map_or(Color::Indexed(cube), Color::Indexed)
+    }
+
+    /// Returns the basic color nearest to `rgb`
+    fn nearest_basic(rgb: (u8, u8, u8)) -> Color {
+        ColorDepth::BASIC
+            .into_iter()
+            .min_by_key(|&(_, value)| distance(value, rgb))
+            .map_or(Color::White, |(color, _)| color)
+    }
+
+    /// Returns the RGB value of the color at index `i` in the 256-color
+    /// palette
+    fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+        match i {
+            0..=15 => ColorDepth::BASIC[usize::from(i)].1,
+            16..=231 => {
+                let i = usize::from(i - 16);
+                (
+                    ColorDepth::CUBE_LEVELS[i / 36],
+                    ColorDepth::CUBE_LEVELS[i / 6 % 6],
+                    ColorDepth::CUBE_LEVELS[i % 6],
+                )
+            }
+            _ => {
+                let level = 8 + 10 * (i - 232);
+                (level, level, level)
+            }
+        }
+    }
+}
+
+/// Returns the RGB value of `color` as displayed by xterm, or `None` for the
+/// terminal's default color
+pub(crate) fn color_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i) => Some(ColorDepth::indexed_rgb(i)),
+        named => ColorDepth::BASIC
+            .iter()
+            .find(|&&(c, _)| c == named)
+            .map(|&(_, rgb)| rgb),
+    }
+}
+
+/// Returns the squared Euclidean distance between two RGB colors
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// A user-defined theme in the configuration file, consisting of a builtin
+/// theme with some of its styles replaced
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct ThemeSpec {
+    /// The name of the builtin theme to start from, if not "default"
+    pub(crate) base: Option<String>,
+    pub(crate) gallows: Option<StyleSpec>,
+    pub(crate) new_part: Option<StyleSpec>,
+    pub(crate) revealed: Option<StyleSpec>,
+    pub(crate) suggestion: Option<StyleSpec>,
+    pub(crate) message: Option<StyleSpec>,
+    pub(crate) hint: Option<StyleSpec>,
+    pub(crate) danger: Option<DangerSpec>,
+}
+
+impl ThemeSpec {
+    /// Returns the theme described by the spec, or `None` if its base is not
+    /// a builtin theme
+    pub(crate) fn resolve(&self) -> Option<Theme> {
+        let mut theme = Theme::builtin(self.base.as_deref().unwrap_or("default"))?;
+        for (style, spec) in [
+            (&mut theme.gallows, self.gallows),
+            (&mut theme.new_part, self.new_part),
+            (&mut theme.revealed, self.revealed),
+            (&mut theme.suggestion, self.suggestion),
+            (&mut theme.message, self.message),
+            (&mut theme.hint, self.hint),
+        ] {
+            if let Some(StyleSpec(s)) = spec {
+                *style = s;
+            }
+        }
+        if let Some(DangerSpec(danger)) = self.danger {
+            theme.danger = danger;
+        }
+        Some(theme)
+    }
+}
+
+/// The `danger` styles of a user-defined theme, written as a list of either
+/// three styles or none at all, the latter turning danger-level styling off
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "Vec<StyleSpec>")]
+pub(crate) struct DangerSpec(Option<[Style; 3]>);
+
+impl TryFrom<Vec<StyleSpec>> for DangerSpec {
+    type Error = DangerLengthError;
+
+    fn try_from(styles: Vec<StyleSpec>) -> Result<DangerSpec, DangerLengthError> {
+        match *styles {
+            [] => Ok(DangerSpec(None)),
+            [StyleSpec(safe), StyleSpec(warning), StyleSpec(danger)] => {
+                Ok(DangerSpec(Some([safe, warning, danger])))
+            }
+            _ => Err(DangerLengthError),
+        }
+    }
+}
+
+/// Error returned when a theme's `danger` list has the wrong number of styles
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("danger must be a list of three styles or an empty list")]
+pub(crate) struct DangerLengthError;
+
+/// A style written as space-separated words: any of the attributes "bold",
+/// "dim", "italic", "underlined", and "reversed"; at most one foreground
+/// color; and a background color preceded by "on", e.g., "bold white on
+/// red".  Colors may be given by name, by 256-color palette index, or as
+/// `#RRGGBB`.  An empty string denotes no styling.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct StyleSpec(pub(crate) Style);
+
+impl std::str::FromStr for StyleSpec {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<StyleSpec, ParseStyleError> {
+        let mut style = Style::new();
+        let mut words = s.split_whitespace();
+        while let Some(word) = words.next() {
+            style = match word.to_lowercase().as_str() {
+                "bold" => style.add_modifier(Modifier::BOLD),
+                "dim" => style.add_modifier(Modifier::DIM),
+                "italic" => style.add_modifier(Modifier::ITALIC),
+                "underlined" => style.add_modifier(Modifier::UNDERLINED),
+                "reversed" => style.add_modifier(Modifier::REVERSED),
+                "on" if style.bg.is_none() => {
+                    let color = words.next().ok_or(ParseStyleError)?;
+                    style.bg(color.parse().map_err(|_| ParseStyleError)?)
+                }
+                _ if style.fg.is_none() => style.fg(word.parse().map_err(|_| ParseStyleError)?),
+                _ => return Err(ParseStyleError),
+            };
+        }
+        Ok(StyleSpec(style))
+    }
+}
+
+impl<'de> Deserialize<'de> for StyleSpec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StyleVisitor;
+
+        impl Visitor<'_> for StyleVisitor {
+            type Value = StyleSpec;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a style such as \"bold red\" or \"white on blue\"")
+            }
+
+            fn visit_str<E>(self, input: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                input
+                    .parse::<StyleSpec>()
+                    .map_err(|_| E::invalid_value(Unexpected::Str(input), &self))
+            }
+        }
+
+        deserializer.deserialize_str(StyleVisitor)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("invalid style: expected attributes and colors such as \"bold red\" or \"white on blue\"")]
+pub(crate) struct ParseStyleError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_style() {
+        for (s, style) in [
+            ("", Style::new()),
+            ("bold", Style::new().add_modifier(Modifier::BOLD)),
+            (
+                "Bold Red",
+                Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            (
+                "italic white on blue",
+                Style::new()
+                    .fg(Color::White)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+            ("#ff8000", Style::new().fg(Color::Rgb(255, 128, 0))),
+            ("on 236", Style::new().bg(Color::Indexed(236))),
+        ] {
+            assert_eq!(s.parse::<StyleSpec>(), Ok(StyleSpec(style)), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_bad_style() {
+        for s in ["red blue", "bold on", "on red on blue", "sparkly"] {
+            assert_eq!(s.parse::<StyleSpec>(), Err(ParseStyleError), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_spec() {
+        let spec = toml::from_str::<ThemeSpec>("base = \"vivid\"\nmessage = \"bold\"\n").unwrap();
+        assert_eq!(
+            spec.resolve(),
+            Some(Theme {
+                message: Style::new().add_modifier(Modifier::BOLD),
+                ..Theme::builtin("vivid").unwrap()
+            })
+        );
+        let spec = toml::from_str::<ThemeSpec>("base = \"nonexistent\"\n").unwrap();
+        assert_eq!(spec.resolve(), None);
+        assert!(toml::from_str::<ThemeSpec>("gallows = \"sparkly\"\n").is_err());
+    }
+
+    #[test]
+    fn test_builtin_themes() {
+        for name in Theme::BUILTIN {
+            assert!(Theme::builtin(name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_detect_color_depth() {
+        let s = |s| Some(OsStr::new(s));
+        for (colorterm, term, depth) in [
+            (s("truecolor"), s("xterm-256color"), ColorDepth::TrueColor),
+            (s("24bit"), None, ColorDepth::TrueColor),
+            (None, s("xterm-256color"), ColorDepth::Ansi256),
+            (s("yes"), s("screen-256color"), ColorDepth::Ansi256),
+            (None, s("xterm"), ColorDepth::Ansi16),
+            (None, None, ColorDepth::Ansi16),
+        ] {
+            assert_eq!(ColorDepth::from_env(colorterm, term), depth);
+        }
+    }
+
+    #[test]
+    fn test_downsample() {
+        let orange = Color::Rgb(255, 135, 0);
+        assert_eq!(ColorDepth::TrueColor.fit(orange), orange);
+        assert_eq!(ColorDepth::Ansi256.fit(orange), Color::Indexed(208));
+        assert_eq!(ColorDepth::Ansi16.fit(orange), Color::Yellow);
+        assert_eq!(ColorDepth::Ansi16.fit(Color::Indexed(208)), Color::Yellow);
+        assert_eq!(
+            ColorDepth::Ansi256.fit(Color::Rgb(128, 128, 128)),
+            Color::Indexed(244)
+        );
+        assert_eq!(
+            ColorDepth::Ansi256.fit(Color::Rgb(0, 0, 0)),
+            Color::Indexed(16)
+        );
+        assert_eq!(
+            ColorDepth::Ansi256.fit(Color::Rgb(250, 250, 250)),
+            Color::Indexed(231)
+        );
+        assert_eq!(ColorDepth::Ansi16.fit(Color::Cyan), Color::Cyan);
+        assert_eq!(color_rgb(Color::Red), Some((205, 0, 0)));
+        assert_eq!(color_rgb(Color::Indexed(208)), Some((255, 135, 0)));
+        assert_eq!(color_rgb(Color::Reset), None);
+        assert_eq!(ColorDepth::Ansi16.fit(Color::Indexed(3)), Color::Indexed(3));
+        let theme = Theme {
+            hint: Style::new().fg(orange).bg(Color::Rgb(0, 0, 90)),
+            ..Theme::default()
+        };
+        assert_eq!(
+            theme.downsample(ColorDepth::Ansi16).hint,
+            Style::new().fg(Color::Yellow).bg(Color::Black)
+        );
+    }
+
+    #[test]
+    fn test_gallows_danger() {
+        let theme = Theme::builtin("vivid").unwrap();
+        let colors = (0..=6)
+            .map(|parts| theme.gallows_at(parts, 6).fg)
+            .collect::<Vec<_>>();
+        let (green, yellow, red) = (Some(Color::Green), Some(Color::Yellow), Some(Color::Red));
+        assert_eq!(colors, [green, green, green, yellow, yellow, red, red]);
+        let plain = Theme::default();
+        assert_eq!(plain.gallows_at(6, 6), plain.gallows);
+    }
+
+    #[test]
+    fn test_danger_spec() {
+        let spec = toml::from_str::<ThemeSpec>(
+            "base = \"vivid\"\ndanger = [\"blue\", \"magenta\", \"bold red\"]\n",
+        )
+        .unwrap();
+        let theme = spec.resolve().unwrap();
+        assert_eq!(
+            theme.danger,
+            Some([
+                Style::new().fg(Color::Blue),
+                Style::new().fg(Color::Magenta),
+                Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ])
+        );
+        let spec = toml::from_str::<ThemeSpec>("base = \"vivid\"\ndanger = []\n").unwrap();
+        assert_eq!(spec.resolve().unwrap().danger, None);
+        assert!(toml::from_str::<ThemeSpec>("danger = [\"red\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_accessible_themes() {
+        let red_green = [Color::Red, Color::LightRed, Color::Green, Color::LightGreen];
+        for name in ["high-contrast", "deuteranopia", "monochrome-bold"] {
+            let theme = Theme::builtin(name).unwrap();
+            for style in [theme.new_part, theme.revealed] {
+                assert!(!style.add_modifier.is_empty(), "{name}");
+                for color in [style.fg, style.bg].into_iter().flatten() {
+                    assert!(!red_green.contains(&color), "{name}");
+                }
+            }
+            assert_ne!(theme.new_part, theme.revealed, "{name}");
+        }
+        let mono = Theme::builtin("monochrome-bold").unwrap();
+        for style in [
+            mono.gallows,
+            mono.new_part,
+            mono.revealed,
+            mono.suggestion,
+            mono.message,
+            mono.hint,
+        ] {
+            assert_eq!((style.fg, style.bg), (None, None));
+        }
+    }
+}
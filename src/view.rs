@@ -1,57 +1,290 @@
-use crate::model::Gallows;
+use crate::graphics::{push_styled, Bitmap, Graphics};
+use crate::model::{AnswerError, Gallows, Guess};
+#[cfg(unix)]
+use crate::signals::{self, Caught};
+use crate::theme::{color_rgb, ColorDepth, Theme, ThemeSpec};
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
-    event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    queue,
+    cursor::MoveTo,
+    event::{
+        poll, read, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+        EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent,
+        KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     style::Print,
     terminal::{
-        disable_raw_mode, enable_raw_mode, size, Clear, ClearType, EnterAlternateScreen,
-        LeaveAlternateScreen,
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
     },
     ExecutableCommand,
 };
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
 use std::fmt::{self, Write as _};
-use std::io::{self, Write};
+use std::fs::File;
+use std::io::{self, IsTerminal, Write};
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Screen<W: Write> {
-    inner: W,
-    columns: u16,
-    rows: u16,
-    lines: Vec<String>,
+    terminal: Terminal<CrosstermBackend<W>>,
+    theme: Theme,
+    /// The themes defined in the configuration file, which `:theme` can
+    /// switch to as well as the builtin ones
+    themes: BTreeMap<String, ThemeSpec>,
+    /// The content currently displayed, kept so that it can be laid out
+    /// again when the terminal is resized, or `None` if a match summary is
+    /// displayed instead
+    content: Option<Content>,
+    page: Page,
+    /// The screen positions at which images were placed by the last draw,
+    /// along with the protocols used to draw them
+    placed: Vec<(u16, u16, Graphics)>,
+    /// Whether mouse clicks are being reported to the program
+    mouse: bool,
+    /// Whether letters can be selected with the cursor keys
+    cursor_keys: bool,
+    /// The letter in the letter grid on which the selection cursor rests
+    cursor: Option<char>,
+    /// The letter in the letter grid under the mouse pointer
+    hover: Option<char>,
+    /// A notification to show in the top-right corner of the screen until
+    /// the given time
+    toast: Option<(String, Instant)>,
+    /// The title most recently given to the terminal window
+    title: Option<Title>,
+    /// The command being typed after `:`, if the user is in command mode
+    command: Option<String>,
+    /// Whether `page` has changed since it was last drawn, as happens when
+    /// drawing is put off until queued input has been handled
+    stale: bool,
 }
 
 impl<W: Write> Screen<W> {
-    pub(crate) fn new(mut inner: W, content: Content) -> Result<Screen<W>, ScreenError> {
-        let (columns, rows) = size().map_err(ScreenError::Init)?;
-        inner
+    /// The number of times that a newly added body part blinks
+    const BLINKS: usize = 2;
+
+    /// How long a newly added body part is shown or hidden for while
+    /// blinking
+    const BLINK_INTERVAL: Duration = Duration::from_millis(90);
+
+    /// The number of frames in the victory animation
+    const SPARKLE_FRAMES: usize = 8;
+
+    /// How long each frame of the victory animation is shown for
+    const SPARKLE_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// The style patched onto the letter under the selection cursor
+    const CURSOR: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+    /// How long a notification is shown for
+    const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+    /// The xterm control sequence for saving the terminal window's title on
+    /// the terminal's title stack
+    const PUSH_TITLE: &'static str = "\x1B[22;0t";
+
+    /// The xterm control sequence for restoring the terminal window's title
+    /// from the terminal's title stack
+    const POP_TITLE: &'static str = "\x1B[23;0t";
+
+    /// How often to check whether the process has been sent a signal while
+    /// waiting for input
+    const SIGNAL_INTERVAL: Duration = Duration::from_millis(200);
+
+    /// Take over the terminal to display `content`.  `themes` are the
+    /// user-defined themes that can be switched to with `:theme`.  If `mouse`
+    /// is true, the user can also click letters in the letter grid to guess
+    /// them, and if `cursor_keys` is true, the user can select letters in the
+    /// grid with the cursor keys & guess them with Enter.
+    pub(crate) fn new(
+        inner: W,
+        content: Content,
+        theme: Theme,
+        themes: BTreeMap<String, ThemeSpec>,
+        mouse: bool,
+        cursor_keys: bool,
+    ) -> Result<Screen<W>, ScreenError> {
+        let mut terminal =
+            Terminal::new(CrosstermBackend::new(inner)).map_err(ScreenError::Init)?;
+        terminal
+            .backend_mut()
             .execute(EnterAlternateScreen)
             .map_err(ScreenError::Init)?;
         if let Err(e) = enable_raw_mode() {
-            let _ = inner.execute(LeaveAlternateScreen);
+            let _ = terminal.backend_mut().execute(LeaveAlternateScreen);
             return Err(ScreenError::Init(e));
         }
-        if let Err(e) = inner.execute(Hide) {
+        if let Err(e) = terminal.hide_cursor() {
             let _ = disable_raw_mode();
-            let _ = inner.execute(LeaveAlternateScreen);
+            let _ = terminal.backend_mut().execute(LeaveAlternateScreen);
             return Err(ScreenError::Init(e));
         }
-        Ok(Screen {
-            inner,
-            columns,
-            rows,
-            lines: content.render(),
-        })
+        if mouse {
+            if let Err(e) = terminal.backend_mut().execute(EnableMouseCapture) {
+                let _ = terminal.show_cursor();
+                let _ = disable_raw_mode();
+                let _ = terminal.backend_mut().execute(LeaveAlternateScreen);
+                return Err(ScreenError::Init(e));
+            }
+        }
+        // Terminals that don't support the title stack, focus reporting, or
+        // bracketed paste just ignore these
+        let _ = terminal.backend_mut().execute(Print(Self::PUSH_TITLE));
+        let _ = terminal.backend_mut().execute(EnableFocusChange);
+        let _ = terminal.backend_mut().execute(EnableBracketedPaste);
+        let mut screen = Screen {
+            terminal,
+            theme,
+            themes,
+            content: None,
+            page: Page::default(),
+            placed: Vec::new(),
+            mouse,
+            cursor_keys,
+            cursor: None,
+            hover: None,
+            toast: None,
+            command: None,
+            title: None,
+            stale: false,
+        };
+        #[cfg(unix)]
+        signals::arm();
+        screen.page = screen.decorate(content.clone().render(&theme, screen.size()));
+        screen.set_title(content.title)?;
+        screen.content = Some(content);
+        Ok(screen)
     }
 
-    pub(crate) fn read_guess(&mut self) -> Result<Option<char>, ScreenError> {
+    pub(crate) fn read_input(&mut self) -> Result<Input, ScreenError> {
         let normal_modifiers = KeyModifiers::NONE | KeyModifiers::SHIFT;
         loop {
-            match read().map_err(ScreenError::Read)? {
+            // While a clock or countdown is running, redraw it whenever it
+            // changes, and give up waiting once the countdown runs out.
+            // Likewise, take down any notification once its time is up.
+            if let Some(content) = &self.content {
+                if content.countdown.is_some_and(Countdown::is_expired) {
+                    return Ok(Input::TimeUp);
+                }
+            }
+            #[cfg(unix)]
+            match signals::caught() {
+                Some(Caught::Stop) => return Ok(Input::Suspend),
+                Some(Caught::Terminate(signal)) => return Err(ScreenError::Terminated(signal)),
+                None => (),
+            }
+            if self.stale && !poll(Duration::ZERO).map_err(ScreenError::Read)? {
+                self.draw()?;
+            }
+            let wait = self
+                .content
+                .as_ref()
+                .and_then(Content::redraw_interval)
+                .into_iter()
+                .chain(
+                    self.toast
+                        .as_ref()
+                        .map(|(_, until)| until.saturating_duration_since(Instant::now())),
+                )
+                .min();
+            let timeout = wait.map_or(Self::SIGNAL_INTERVAL, |w| w.min(Self::SIGNAL_INTERVAL));
+            if !poll(timeout).map_err(ScreenError::Read)? {
+                if wait.is_some_and(|w| w <= timeout) {
+                    if self
+                        .toast
+                        .as_ref()
+                        .is_some_and(|&(_, until)| until <= Instant::now())
+                    {
+                        self.toast = None;
+                    }
+                    self.relay_out()?;
+                }
+                continue;
+            }
+            let event = read().map_err(ScreenError::Read)?;
+            if self.command.is_some() {
+                if let Some(input) = self.command_event(event)? {
+                    return Ok(input);
+                }
+                continue;
+            }
+            match event {
+                // Escape only quits outright once the game is over;
+                // otherwise, it opens the pause menu
                 Event::Key(KeyEvent {
                     code: KeyCode::Esc, ..
-                }) => return Ok(None),
+                }) if self.page.prompt => return Ok(Input::Quit),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Esc,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::Pause),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::Quit),
+                // The terminal is in raw mode, so Ctrl-Z arrives as a key
+                // press rather than as SIGTSTP
+                #[cfg(unix)]
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('z'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::Suspend),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Tab,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::ToggleAssist),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Enter,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => match self.cursor.filter(|_| !self.page.prompt) {
+                    Some(ch) => return Ok(Input::Guess(ch)),
+                    None => return Ok(Input::Enter),
+                },
+                Event::Key(KeyEvent {
+                    code: code @ (KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) if self.cursor_keys => self.move_cursor(code)?,
+                Event::Key(KeyEvent {
+                    code: KeyCode::Backspace,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::Backspace),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('+'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::NextHint),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('?') | KeyCode::F(1),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => return Ok(Input::Help),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(':'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) if !self.page.prompt && self.content.is_some() => {
+                    self.command = Some(String::new());
+                    self.relay_out()?;
+                }
                 Event::Key(KeyEvent {
                     code,
                     modifiers,
@@ -60,333 +293,5624 @@ impl<W: Write> Screen<W> {
                 }) => {
                     if normal_modifiers.contains(modifiers) {
                         if let KeyCode::Char(ch) = code {
-                            return Ok(Some(ch));
+                            return Ok(Input::Guess(ch));
                         }
                     }
                     self.beep()?;
                 }
-                Event::Resize(columns, rows) => {
-                    self.columns = columns;
-                    self.rows = rows;
-                    self.draw()?;
+                Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(MouseButton::Left),
+                    column,
+                    row,
+                    ..
+                }) => {
+                    if let Some(ch) = self.page.letter_at(self.size(), column, row) {
+                        return Ok(Input::Guess(ch));
+                    } else if self.page.prompt {
+                        // Clicking anywhere when asked to press any key
+                        // counts as pressing Enter
+                        return Ok(Input::Enter);
+                    }
+                }
+                Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::Moved,
+                    column,
+                    row,
+                    ..
+                }) => {
+                    let hover = self
+                        .page
+                        .option_at(self.size(), column, row)
+                        .map(GuessOption::letter);
+                    if hover != self.hover {
+                        self.hover = hover;
+                        self.relay_out()?;
+                    }
+                }
+                // The terminal picks up the new size on its own when drawing,
+                // but figures that scale with the terminal need to be redrawn
+                // at the new size
+                Event::Resize(_, _) => match self.content.clone() {
+                    Some(content) => self.lay_out(content)?,
+                    None => self.draw()?,
+                },
+                Event::FocusLost if !self.page.prompt => return Ok(Input::Unfocused),
+                // A pasted word is guessed all at once rather than letter by
+                // letter
+                Event::Paste(text) if !self.page.prompt => {
+                    let text = text.trim();
+                    let mut chars = text.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => return Ok(Input::Guess(ch)),
+                        (Some(_), Some(_)) => return Ok(Input::GuessWord(text.to_owned())),
+                        (None, _) => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Handle `event` while the user is typing a command after `:`.  Returns
+    /// `Some` if a command was entered that the caller needs to act on;
+    /// changing the theme is taken care of here.
+    fn command_event(&mut self, event: Event) -> Result<Option<Input>, ScreenError> {
+        let Some(command) = self.command.as_mut() else {
+            return Ok(None);
+        };
+        match event {
+            Event::Key(
+                KeyEvent {
+                    code: KeyCode::Esc,
+                    kind: KeyEventKind::Press,
+                    ..
+                }
+                | KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                },
+            ) => self.command = None,
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                let command = self.command.take().unwrap_or_default();
+                let mut words = command.split_whitespace();
+                match (words.next(), words.next(), words.next()) {
+                    (None, _, _) => (),
+                    (Some("q" | "quit"), None, _) => return Ok(Some(Input::Quit)),
+                    (Some("new"), None, _) => return Ok(Some(Input::NewWord)),
+                    (Some("hint"), None, _) => return Ok(Some(Input::NextHint)),
+                    // As with --theme, themes from the configuration file
+                    // take precedence over builtin themes of the same name
+                    (Some("theme"), Some(name), None) => match self
+                        .themes
+                        .get(name)
+                        .map_or_else(|| Theme::builtin(name), ThemeSpec::resolve)
+                    {
+                        // Without styling, there's no theme to change
+                        Some(_) if self.theme == Theme::PLAIN => self.notify("Colors are off"),
+                        Some(theme) => {
+                            self.theme = theme.downsample(ColorDepth::detect());
+                            self.notify(format!("Theme: {name}"));
+                        }
+                        None => {
+                            self.notify(format!("Unknown theme {name:?}"));
+                            self.beep()?;
+                        }
+                    },
+                    _ => {
+                        self.notify(format!("Not a command: {}", command.trim()));
+                        self.beep()?;
+                    }
+                }
+            }
+            // Deleting past the start of the command leaves command mode
+            Event::Key(KeyEvent {
+                code: KeyCode::Backspace,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                if command.pop().is_none() {
+                    self.command = None;
+                }
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                kind: KeyEventKind::Press,
+                ..
+            }) => command.push(ch),
+            Event::Key(KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            }) => return self.beep().map(|()| None),
+            Event::Paste(text) => command.extend(text.chars().filter(|ch| !ch.is_control())),
+            Event::Resize(_, _) => (),
+            _ => return Ok(None),
+        }
+        self.relay_out()?;
+        Ok(None)
+    }
+
+    pub(crate) fn pause(&mut self) -> Result<(), ScreenError> {
+        self.prompt().map(|_| ())
+    }
+
+    /// Wait for the user to press a key, suspending the game in the meantime
+    /// if asked to, and return whether they asked to quit
+    pub(crate) fn prompt(&mut self) -> Result<bool, ScreenError> {
+        loop {
+            match self.read_input()? {
+                Input::Suspend => self.suspend()?,
+                input => return Ok(input == Input::Quit),
+            }
+        }
+    }
+
+    /// Show the pause menu over a dimmed copy of the current page until the
+    /// user makes a choice.  "New word" is only offered if `new_word` is
+    /// true, and the option to toggle assist mode only if `assist` is true.
+    /// The menu is navigated with the up & down arrow keys and Enter, or by
+    /// pressing the first letter of an item; Escape backs out of the options
+    /// or resumes the game.
+    pub(crate) fn pause_menu(
+        &mut self,
+        new_word: bool,
+        assist: bool,
+    ) -> Result<MenuChoice, ScreenError> {
+        let underlying = std::mem::take(&mut self.page);
+        let main = [
+            Some(MenuItem::Resume),
+            Some(MenuItem::NewWord).filter(|_| new_word),
+            Some(MenuItem::Options),
+            Some(MenuItem::Quit),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        let options = [
+            Some(MenuItem::Assist).filter(|_| assist),
+            Some(MenuItem::BigWord),
+            Some(MenuItem::Back),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        let mut items = &main;
+        let mut selected = 0;
+        let choice = loop {
+            let title = if items == &main {
+                " Paused "
+            } else {
+                " Options "
+            };
+            self.page = underlying.with_menu(title, items, selected);
+            self.draw()?;
+            let item = match self.next_event()? {
+                Event::Key(KeyEvent {
+                    code,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => match code {
+                    KeyCode::Up => {
+                        selected = (selected + items.len() - 1) % items.len();
+                        continue;
+                    }
+                    KeyCode::Down => {
+                        selected = (selected + 1) % items.len();
+                        continue;
+                    }
+                    KeyCode::Enter => Some(items[selected]),
+                    KeyCode::Esc if items == &main => Some(MenuItem::Resume),
+                    KeyCode::Esc => Some(MenuItem::Back),
+                    KeyCode::Char(ch) => items
+                        .iter()
+                        .copied()
+                        .find(|item| item.key() == ch.to_ascii_lowercase()),
+                    _ => None,
+                },
+                _ => continue,
+            };
+            let Some(item) = item else {
+                self.beep()?;
+                continue;
+            };
+            match item {
+                MenuItem::Resume => break MenuChoice::Resume,
+                MenuItem::NewWord => break MenuChoice::NewWord,
+                MenuItem::Quit => break MenuChoice::Quit,
+                MenuItem::Assist => break MenuChoice::ToggleAssist,
+                MenuItem::BigWord => break MenuChoice::ToggleBigWord,
+                MenuItem::Options => {
+                    items = &options;
+                    selected = 0;
+                }
+                MenuItem::Back => {
+                    items = &main;
+                    selected = 0;
                 }
+            }
+        };
+        self.page = underlying;
+        if choice != MenuChoice::Quit {
+            self.draw()?;
+        }
+        Ok(choice)
+    }
+
+    /// Ask the user to confirm ending the game in progress over a dimmed copy
+    /// of the current page, returning `true` if they press Y or Ctrl-C or
+    /// `false` if they press N or Escape
+    pub(crate) fn confirm_quit(&mut self) -> Result<bool, ScreenError> {
+        let underlying = std::mem::take(&mut self.page);
+        self.page = underlying.with_overlay(" Quit ", vec![Line::from(" End this game? (y/n) ")]);
+        self.draw()?;
+        let confirmed = loop {
+            match self.next_event()? {
+                Event::Key(KeyEvent {
+                    code,
+                    modifiers,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => match code {
+                    KeyCode::Char('y' | 'Y') => break true,
+                    KeyCode::Char('c') if modifiers == KeyModifiers::CONTROL => break true,
+                    KeyCode::Char('n' | 'N') | KeyCode::Esc => break false,
+                    _ => self.beep()?,
+                },
+                Event::Resize(_, _) => self.draw()?,
+                _ => (),
+            }
+        };
+        self.page = underlying;
+        if !confirmed {
+            self.draw()?;
+        }
+        Ok(confirmed)
+    }
+
+    /// Show `help` over a dimmed copy of the current page until the user
+    /// presses a key or clicks
+    pub(crate) fn show_help(&mut self, help: &Help) -> Result<(), ScreenError> {
+        let underlying = std::mem::take(&mut self.page);
+        self.page = underlying.with_overlay(" Help ", help.lines());
+        self.draw()?;
+        loop {
+            match self.next_event()? {
+                Event::Key(KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..
+                })
+                | Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(_),
+                    ..
+                }) => break,
+                Event::Resize(_, _) => self.draw()?,
+                _ => (),
+            }
+        }
+        self.page = underlying;
+        self.draw()
+    }
+
+    /// Show a notice that the game is paused until the terminal window
+    /// regains focus (or the user presses a key or clicks, in case the
+    /// terminal doesn't report focus changes), drawn over a dimmed copy of
+    /// the current page or, if `hide` is true, over an empty screen
+    pub(crate) fn wait_for_focus(&mut self, hide: bool) -> Result<(), ScreenError> {
+        let underlying = std::mem::take(&mut self.page);
+        let backdrop = if hide { &Page::default() } else { &underlying };
+        self.page = backdrop.with_overlay(
+            " Paused ",
+            vec![Line::from(" Return to this window to resume. ")],
+        );
+        self.draw()?;
+        loop {
+            match self.next_event()? {
+                Event::FocusGained
+                | Event::Key(KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..
+                })
+                | Event::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(_),
+                    ..
+                }) => break,
+                Event::Resize(_, _) => self.draw()?,
                 _ => (),
             }
         }
+        self.page = underlying;
+        self.draw()
+    }
+
+    /// Give the terminal back to the shell and stop the process, as the
+    /// shell's job control expects on Ctrl-Z, then take over the terminal
+    /// again and redraw the screen once the process is continued
+    #[cfg(unix)]
+    pub(crate) fn suspend(&mut self) -> Result<(), ScreenError> {
+        self.release();
+        // This returns once the process receives SIGCONT
+        signals::stop().map_err(ScreenError::Write)?;
+        let backend = self.terminal.backend_mut();
+        backend
+            .execute(Print(Self::PUSH_TITLE))
+            .and_then(|b| b.execute(EnterAlternateScreen))
+            .map_err(ScreenError::Init)?;
+        enable_raw_mode().map_err(ScreenError::Init)?;
+        self.terminal.hide_cursor().map_err(ScreenError::Init)?;
+        if self.mouse {
+            self.terminal
+                .backend_mut()
+                .execute(EnableMouseCapture)
+                .map_err(ScreenError::Init)?;
+        }
+        let _ = self.terminal.backend_mut().execute(EnableFocusChange);
+        let _ = self.terminal.backend_mut().execute(EnableBracketedPaste);
+        if let Some(title) = self.title.take() {
+            self.set_title(title)?;
+        }
+        // Anything drawn before suspending is gone, so draw it all anew
+        self.terminal.clear().map_err(ScreenError::Write)?;
+        self.placed.clear();
+        self.draw()
+    }
+
+    /// Without job control, there's nothing to suspend to
+    #[cfg(not(unix))]
+    pub(crate) fn suspend(&mut self) -> Result<(), ScreenError> {
+        Ok(())
+    }
+
+    /// Undo the changes made to the terminal by [`Screen::new()`]
+    fn release(&mut self) {
+        if self.mouse {
+            let _ = self.terminal.backend_mut().execute(DisableMouseCapture);
+        }
+        let _ = self.terminal.backend_mut().execute(DisableBracketedPaste);
+        let _ = self.terminal.backend_mut().execute(DisableFocusChange);
+        let _ = self.terminal.show_cursor();
+        let _ = disable_raw_mode();
+        let _ = self.terminal.backend_mut().execute(LeaveAlternateScreen);
+        let _ = self.terminal.backend_mut().execute(Print(Self::POP_TITLE));
+    }
+
+    /// Show `message` in the top-right corner of the screen for a few
+    /// seconds, starting with the next update of the display
+    pub(crate) fn notify<S: Into<String>>(&mut self, message: S) {
+        self.toast = Some((message.into(), Instant::now() + Self::TOAST_DURATION));
+    }
+
+    /// Replace the game display with the summary of a match
+    pub(crate) fn show_summary(&mut self, summary: &MatchSummary) -> Result<(), ScreenError> {
+        self.content = None;
+        self.toast = None;
+        self.show(summary.render())
+    }
+
+    /// Display `content`.  If a body part was just added to a figure, it's
+    /// made to blink a few times first, and if the user just won, a short
+    /// celebration is played first; either animation stops as soon as a key
+    /// is pressed.
+    pub(crate) fn update(&mut self, content: Content) -> Result<(), ScreenError> {
+        // When more keys are already waiting (as when the user types quickly
+        // or pastes), skip the animations and don't draw anything until
+        // they've all been handled, so that a burst of guesses is drawn just
+        // once.  The end of a game is always drawn, though.
+        if poll(Duration::ZERO).map_err(ScreenError::Read)? {
+            let page = content.clone().render(&self.theme, self.size());
+            if page.prompt {
+                return self.lay_out(content);
+            }
+            self.set_title(content.title)?;
+            self.content = Some(content);
+            let page = self.decorate(page);
+            if page != self.page {
+                self.page = page;
+                self.stale = true;
+            }
+            return Ok(());
+        }
+        let just_won = content.message == Message::Won
+            && self
+                .content
+                .as_ref()
+                .is_none_or(|old| old.message != Message::Won);
+        let figures_changed = self
+            .content
+            .as_ref()
+            .is_none_or(|old| (old.figure, old.opponent) != (content.figure, content.opponent));
+        if figures_changed {
+            let size = self.size();
+            let shown = content.clone().render(&self.theme, size);
+            let hidden = content
+                .clone()
+                .render_frame(&self.theme, size, Effect::HideNewParts);
+            if hidden != shown {
+                'blink: for _ in 0..Self::BLINKS {
+                    for page in [&shown, &hidden] {
+                        self.show(page.clone())?;
+                        // Stop blinking as soon as the user presses a key
+                        if poll(Self::BLINK_INTERVAL).map_err(ScreenError::Read)? {
+                            break 'blink;
+                        }
+                    }
+                }
+            }
+        }
+        if just_won {
+            let size = self.size();
+            for frame in 0..Self::SPARKLE_FRAMES {
+                let page = content
+                    .clone()
+                    .render_frame(&self.theme, size, Effect::Sparkles(frame));
+                self.show(page)?;
+                if poll(Self::SPARKLE_INTERVAL).map_err(ScreenError::Read)? {
+                    break;
+                }
+            }
+        }
+        self.lay_out(content)
+    }
+
+    /// Display `content` without any animation
+    fn lay_out(&mut self, content: Content) -> Result<(), ScreenError> {
+        let page = content.clone().render(&self.theme, self.size());
+        self.set_title(content.title)?;
+        self.content = Some(content);
+        self.show(page)
+    }
+
+    /// Set the title of the terminal window, if it's changed
+    fn set_title(&mut self, title: Title) -> Result<(), ScreenError> {
+        if self.title != Some(title) {
+            self.terminal
+                .backend_mut()
+                .execute(SetTitle(title))
+                .map_err(ScreenError::Write)?;
+            self.title = Some(title);
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the terminal, or an empty area if it can't be
+    /// determined
+    fn size(&self) -> Rect {
+        self.terminal.size().unwrap_or_default()
+    }
+
+    /// Move the selection cursor to the nearest letter in the direction of
+    /// the cursor key `code`, or beep if there's no letter that way
+    fn move_cursor(&mut self, code: KeyCode) -> Result<(), ScreenError> {
+        match self
+            .cursor
+            .filter(|_| !self.page.prompt)
+            .and_then(|ch| self.page.neighbor(ch, code))
+        {
+            Some(ch) => {
+                self.cursor = Some(ch);
+                self.relay_out()
+            }
+            None => self.beep(),
+        }
+    }
+
+    /// Lay out the content currently displayed again, if it's not a match
+    /// summary
+    fn relay_out(&mut self) -> Result<(), ScreenError> {
+        match self.content.clone() {
+            Some(content) => self.lay_out(content),
+            None => Ok(()),
+        }
+    }
+
+    /// Draw the selection cursor & the highlight & tooltip for the letter
+    /// under the mouse pointer on `page`.  The selection cursor is kept on a
+    /// letter that can still be guessed, moving on to the next letter in the
+    /// grid once its letter is guessed.
+    fn decorate(&mut self, mut page: Page) -> Page {
+        page.toast = self.toast.as_ref().map(|(message, _)| message.clone());
+        page.command = self.command.clone();
+        if page.prompt {
+            return page;
+        }
+        if self.cursor_keys {
+            let letters = page.open_letters();
+            self.cursor = match self.cursor {
+                Some(ch) if letters.iter().any(|&(_, _, c)| c == ch) => Some(ch),
+                Some(ch) => {
+                    let old = self.page.position(ch).unwrap_or_default();
+                    letters
+                        .iter()
+                        .find(|&&(row, column, _)| (row, column) >= old)
+                        .or_else(|| letters.last())
+                        .map(|&(_, _, c)| c)
+                }
+                None => letters.first().map(|&(_, _, c)| c),
+            };
+            if let Some(ch) = self.cursor {
+                page.highlight(ch, Self::CURSOR);
+            }
+        }
+        if let Some(ch) = self.hover {
+            page.hover(ch);
+        }
+        page
+    }
+
+    /// Display `page`, unless it's already what's on the screen
+    fn show(&mut self, page: Page) -> Result<(), ScreenError> {
+        let page = self.decorate(page);
+        if page != self.page {
+            self.page = page;
+            self.draw()?;
+        }
+        Ok(())
+    }
+
+    /// Draw the current page.  The terminal keeps a copy of what it last
+    /// drew, and only the cells that differ from that are written out, so
+    /// that redrawing doesn't flicker; the screen is only cleared when the
+    /// terminal is resized or when an image needs to be erased.
+    ///
+    /// Images are drawn over the blank cells left for them after the text is
+    /// drawn.  As the terminal doesn't know what's under an image, the screen
+    /// is cleared whenever an image is removed rather than replaced by one in
+    /// the same place.
+    pub(crate) fn draw(&mut self) -> Result<(), ScreenError> {
+        self.stale = false;
+        let screen = self.terminal.size().map_err(ScreenError::Write)?;
+        let area = self.page.area(screen);
+        let placements = self
+            .page
+            .images
+            .iter()
+            .filter_map(|image| Some((image.position(area, screen)?, image)))
+            .collect::<Vec<_>>();
+        if self
+            .placed
+            .iter()
+            .any(|&(x, y, _)| !placements.iter().any(|&(position, _)| position == (x, y)))
+        {
+            self.terminal.clear().map_err(ScreenError::Write)?;
+        }
+        for (_, _, graphics) in std::mem::take(&mut self.placed) {
+            self.terminal
+                .backend_mut()
+                .execute(Print(graphics.clear()))
+                .map_err(ScreenError::Write)?;
+        }
+        let page = &self.page;
+        self.terminal
+            .draw(|frame| page.render(frame))
+            .map_err(ScreenError::Write)?;
+        for ((x, y), image) in placements {
+            let backend = self.terminal.backend_mut();
+            backend.execute(MoveTo(x, y)).map_err(ScreenError::Write)?;
+            backend
+                .execute(Print(&image.escape))
+                .map_err(ScreenError::Write)?;
+            self.placed.push((x, y, image.graphics));
+        }
+        Ok(())
+    }
+
+    /// Wait for the next input event while a menu or overlay is shown,
+    /// suspending the game if the process is sent SIGTSTP and giving up if
+    /// it's sent SIGTERM or SIGHUP
+    fn next_event(&mut self) -> Result<Event, ScreenError> {
+        loop {
+            #[cfg(unix)]
+            match signals::caught() {
+                Some(Caught::Stop) => self.suspend()?,
+                Some(Caught::Terminate(signal)) => return Err(ScreenError::Terminated(signal)),
+                None => (),
+            }
+            if poll(Self::SIGNAL_INTERVAL).map_err(ScreenError::Read)? {
+                return read().map_err(ScreenError::Read);
+            }
+        }
+    }
+
+    pub(crate) fn beep(&mut self) -> Result<(), ScreenError> {
+        self.terminal
+            .backend_mut()
+            .execute(Print("\x07"))
+            .map_err(ScreenError::Write)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for Screen<W> {
+    fn drop(&mut self) {
+        self.release();
+        #[cfg(unix)]
+        signals::disarm();
+    }
+}
+
+/// When to draw the game with colors & text attributes, as set with `--color`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum ColorChoice {
+    /// Use styling unless the `NO_COLOR` environment variable is set to a
+    /// nonempty value or standard output is not a terminal
+    #[default]
+    Auto,
+    /// Always use styling
+    Always,
+    /// Never use styling
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns `theme`, with its colors adjusted to the terminal's color
+    /// depth, if styling should be used, or else a theme without any styling
+    pub(crate) fn apply(self, theme: Theme) -> Theme {
+        let no_color = std::env::var_os("NO_COLOR");
+        if self.enabled(no_color.as_deref(), io::stdout().is_terminal()) {
+            theme.downsample(ColorDepth::detect())
+        } else {
+            Theme::PLAIN
+        }
+    }
+
+    /// Returns whether to use styling, given the value of `NO_COLOR` and
+    /// whether standard output is a terminal
+    fn enabled(self, no_color: Option<&OsStr>, terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => no_color.is_none_or(OsStr::is_empty) && terminal,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = ParseColorChoiceError;
+
+    fn from_str(s: &str) -> Result<ColorChoice, ParseColorChoiceError> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(ParseColorChoiceError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"color choice must be "auto", "always", or "never""#)]
+pub(crate) struct ParseColorChoiceError;
+
+/// A screenful of lines to display, centered as a block
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Page {
+    /// The width of the page in columns, used for centering it; lines may
+    /// extend past this
+    width: usize,
+    /// Lines that are cut off at the edge of the screen if they don't fit
+    board: Vec<Line<'static>>,
+    /// Lines of prose beneath the board, which are wrapped to fit the screen
+    text: Vec<Line<'static>>,
+    /// Images to draw over the board
+    images: Vec<Image>,
+    /// The row & column on the board of each letter in the letter grid
+    letters: Vec<(usize, usize, GuessOption)>,
+    /// Whether the page asks the user to press any key
+    prompt: bool,
+    /// A note to draw over the board, starting at the given row & column
+    tooltip: Option<(usize, usize, Line<'static>)>,
+    /// Lines to draw in a box with the given title in the middle of the
+    /// screen, over everything else, such as a menu
+    overlay: Option<(&'static str, Vec<Line<'static>>)>,
+    /// A notification to draw in a box in the top-right corner of the screen
+    toast: Option<String>,
+    /// A command being typed, to draw after a `:` on the bottom line of the
+    /// screen along with the cursor
+    command: Option<String>,
+    /// Lines to draw against the right edge of the screen if there's room
+    /// for them beside the page
+    sidebar: Vec<Line<'static>>,
+}
+
+impl Page {
+    /// The style patched onto the letter under the mouse pointer
+    const HOVER: Style = Style::new().add_modifier(Modifier::UNDERLINED);
+
+    /// The style of the note shown beneath the letter under the mouse pointer
+    const TOOLTIP: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+    /// The style patched onto a page shown beneath an overlay
+    const DIMMED: Style = Style::new().add_modifier(Modifier::DIM);
+
+    /// The style of the selected item in a menu
+    const SELECTED: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+    /// The number of rows taken up by a notification & its border
+    const TOAST_HEIGHT: u16 = 3;
+
+    /// The smallest number of blank columns between the page & the sidebar
+    const SIDEBAR_GAP: usize = 4;
+
+    /// Returns a dimmed copy of the page with `lines` drawn over it in a box
+    /// titled `title`.  Images are left out, as they would be drawn over the
+    /// box.
+    fn with_overlay(&self, title: &'static str, lines: Vec<Line<'static>>) -> Page {
+        let dim = |lines: &[Line<'static>]| {
+            lines
+                .iter()
+                .cloned()
+                .map(|mut line| {
+                    line.patch_style(Page::DIMMED);
+                    line
+                })
+                .collect::<Vec<_>>()
+        };
+        Page {
+            width: self.width,
+            board: dim(&self.board),
+            text: dim(&self.text),
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: Some((title, lines)),
+            toast: None,
+            command: None,
+            sidebar: dim(&self.sidebar),
+        }
+    }
+
+    /// Returns a dimmed copy of the page with a menu of `items` drawn over
+    /// it, with the item at index `selected` highlighted
+    fn with_menu(&self, title: &'static str, items: &[MenuItem], selected: usize) -> Page {
+        let width = items
+            .iter()
+            .map(|item| item.label().len())
+            .max()
+            .unwrap_or(0)
+            .max(title.len());
+        let menu = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let label = format!(" {:<width$} ", item.label());
+                if i == selected {
+                    Line::styled(label, Page::SELECTED)
+                } else {
+                    Line::from(label)
+                }
+            })
+            .collect();
+        self.with_overlay(title, menu)
+    }
+
+    /// Returns the area of `screen` in which the page is drawn, starting at
+    /// its top-left corner
+    fn area(&self, screen: Rect) -> Rect {
+        let left_margin = match u16::try_from(self.width) {
+            Ok(width) => screen.width.saturating_sub(width) / 2,
+            Err(_) => 0,
+        };
+        let top_margin = match u16::try_from(self.board.len() + self.text.len()) {
+            Ok(length) => screen.height.saturating_sub(length) / 2,
+            Err(_) => 0,
+        };
+        Rect {
+            x: left_margin,
+            y: top_margin,
+            width: screen.width - left_margin,
+            height: screen.height - top_margin,
+        }
+    }
+
+    /// Returns the letter in the letter grid drawn at the given position on
+    /// `screen`, if any
+    fn option_at(&self, screen: Rect, column: u16, row: u16) -> Option<GuessOption> {
+        let area = self.area(screen);
+        let x = usize::from(column.checked_sub(area.x)?);
+        let y = usize::from(row.checked_sub(area.y)?);
+        self.letters
+            .iter()
+            .find(|&&(r, c, _)| (r, c) == (y, x))
+            .map(|&(_, _, opt)| opt)
+    }
+
+    /// Returns the guessable letter drawn at the given position on `screen`,
+    /// if any
+    fn letter_at(&self, screen: Rect, column: u16, row: u16) -> Option<char> {
+        match self.option_at(screen, column, row)? {
+            GuessOption::Open(ch) => Some(ch),
+            GuessOption::Guessed(_) => None,
+        }
+    }
+
+    /// Returns the letter `ch` in the letter grid
+    fn option(&self, ch: char) -> Option<GuessOption> {
+        self.letters
+            .iter()
+            .find(|&&(_, _, opt)| opt.letter() == ch)
+            .map(|&(_, _, opt)| opt)
+    }
+
+    /// Returns the row & column on the board of the letter `ch` in the
+    /// letter grid
+    fn position(&self, ch: char) -> Option<(usize, usize)> {
+        self.letters
+            .iter()
+            .find(|&&(_, _, opt)| opt.letter() == ch)
+            .map(|&(row, column, _)| (row, column))
+    }
+
+    /// Returns the row, column, & letter of each guessable letter in the
+    /// letter grid
+    fn open_letters(&self) -> Vec<(usize, usize, char)> {
+        self.letters
+            .iter()
+            .filter_map(|&(row, column, opt)| match opt {
+                GuessOption::Open(ch) => Some((row, column, ch)),
+                GuessOption::Guessed(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the guessable letter nearest to `ch` in the direction of the
+    /// cursor key `code`: the previous or next letter for Left & Right, or
+    /// the letter closest in column in the nearest row above or below for Up
+    /// & Down
+    fn neighbor(&self, ch: char, code: KeyCode) -> Option<char> {
+        let letters = self.open_letters();
+        let i = letters.iter().position(|&(_, _, c)| c == ch)?;
+        let (row, column, _) = letters[i];
+        let target_row = match code {
+            KeyCode::Left => return Some(letters.get(i.checked_sub(1)?)?.2),
+            KeyCode::Right => return Some(letters.get(i + 1)?.2),
+            KeyCode::Up => letters
+                .iter()
+                .map(|&(r, _, _)| r)
+                .filter(|&r| r < row)
+                .max()?,
+            KeyCode::Down => letters.iter().map(|&(r, _, _)| r).find(|&r| r > row)?,
+            _ => return None,
+        };
+        letters
+            .iter()
+            .filter(|&&(r, _, _)| r == target_row)
+            .min_by_key(|&&(_, c, _)| c.abs_diff(column))
+            .map(|&(_, _, c)| c)
+    }
+
+    /// Patch `style` onto the style of the letter `ch` in the letter grid
+    fn highlight(&mut self, ch: char, style: Style) {
+        let Some((row, column)) = self.position(ch) else {
+            return;
+        };
+        let Some(line) = self.board.get_mut(row) else {
+            return;
+        };
+        let mut start = 0;
+        for span in &mut line.spans {
+            let width = span.width();
+            if start == column && width > 0 {
+                span.style = span.style.patch(style);
+                return;
+            }
+            start += width;
+        }
+    }
+
+    /// Highlight the letter `ch` in the letter grid as being under the mouse
+    /// pointer, with a note beneath it saying whether it can still be
+    /// guessed
+    fn hover(&mut self, ch: char) {
+        let (Some(opt), Some((row, column))) = (self.option(ch), self.position(ch)) else {
+            return;
+        };
+        let note = match opt {
+            GuessOption::Open(ch) => format!(" {ch}: available "),
+            GuessOption::Guessed(ch) => format!(" {ch}: already guessed "),
+        };
+        self.highlight(ch, Page::HOVER);
+        self.tooltip = Some((row + 1, column, Line::styled(note, Page::TOOLTIP)));
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = self.area(frame.size());
+        let board_height = u16::try_from(self.board.len()).unwrap_or(u16::MAX);
+        let chunks = Layout::new(
+            Direction::Vertical,
+            [Constraint::Length(board_height), Constraint::Min(0)],
+        )
+        .split(area);
+        if let [board, text] = *chunks {
+            frame.render_widget(Paragraph::new(self.board.clone()), board);
+            frame.render_widget(
+                Paragraph::new(self.text.clone()).wrap(Wrap { trim: false }),
+                text,
+            );
+        }
+        if let Some((row, column, note)) = &self.tooltip {
+            // Keep the note on the screen, sliding it left if needed
+            let screen = frame.size();
+            let width = u16::try_from(note.width())
+                .unwrap_or(u16::MAX)
+                .min(screen.width);
+            let x = u16::try_from(usize::from(area.x) + column)
+                .unwrap_or(u16::MAX)
+                .min(screen.width - width);
+            if let Ok(y) = u16::try_from(usize::from(area.y) + row) {
+                if y < screen.height {
+                    frame.render_widget(Paragraph::new(note.clone()), Rect::new(x, y, width, 1));
+                }
+            }
+        }
+        if !self.sidebar.is_empty() {
+            let screen = frame.size();
+            let width = self.sidebar.iter().map(Line::width).max().unwrap_or(0);
+            // Lines of the board such as the tally can be wider than the
+            // page
+            let used = self
+                .board
+                .iter()
+                .map(Line::width)
+                .fold(self.width, usize::max);
+            let room = usize::from(screen.width)
+                .saturating_sub(usize::from(area.x) + used + Page::SIDEBAR_GAP);
+            if width <= room {
+                // If the guesses don't all fit, keep the heading & the
+                // latest guesses
+                let rows = usize::from(screen.height);
+                let mut lines = self.sidebar.clone();
+                if lines.len() > rows {
+                    lines.drain(1..=(lines.len() - rows));
+                }
+                let width = u16::try_from(width).unwrap_or(u16::MAX);
+                frame.render_widget(
+                    Paragraph::new(lines),
+                    Rect::new(screen.width - width, 0, width, screen.height),
+                );
+            }
+        }
+        if let Some(toast) = &self.toast {
+            let screen = frame.size();
+            let width = u16::try_from(Span::raw(toast).width() + 4)
+                .unwrap_or(u16::MAX)
+                .min(screen.width);
+            let area = Rect::new(
+                screen.width - width,
+                0,
+                width,
+                screen.height.min(Page::TOAST_HEIGHT),
+            );
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(format!(" {toast} ")).block(Block::new().borders(Borders::ALL)),
+                area,
+            );
+        }
+        if let Some(command) = &self.command {
+            let screen = frame.size();
+            if screen.height > 0 {
+                let area = Rect::new(0, screen.height - 1, screen.width, 1);
+                let line = format!(":{command}");
+                let width = u16::try_from(Span::raw(&line).width()).unwrap_or(u16::MAX);
+                frame.render_widget(Clear, area);
+                frame.render_widget(Paragraph::new(line), area);
+                frame.set_cursor(width.min(screen.width.saturating_sub(1)), area.y);
+            }
+        }
+        if let Some((title, lines)) = &self.overlay {
+            let screen = frame.size();
+            let width = lines.iter().map(Line::width).max().unwrap_or(0) + 2;
+            let width = u16::try_from(width).unwrap_or(u16::MAX).min(screen.width);
+            let height = u16::try_from(lines.len() + 2)
+                .unwrap_or(u16::MAX)
+                .min(screen.height);
+            let area = Rect::new(
+                (screen.width - width) / 2,
+                (screen.height - height) / 2,
+                width,
+                height,
+            );
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(lines.clone())
+                    .block(Block::new().borders(Borders::ALL).title(*title)),
+                area,
+            );
+        }
+    }
+}
+
+/// An image drawn over a block of blank cells on the board with a terminal
+/// graphics protocol
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Image {
+    graphics: Graphics,
+    /// The escape sequence that draws the image at the cursor position
+    escape: String,
+    /// The row of the board at which the top of the image is placed
+    row: usize,
+    /// The column of the board at which the left of the image is placed
+    column: usize,
+    /// The width of the image in cells
+    columns: usize,
+    /// The height of the image in cells
+    rows: usize,
+}
+
+impl Image {
+    /// Returns the screen position of the image's top-left corner when the
+    /// page is drawn in `area`, or `None` if the image wouldn't fit on the
+    /// screen
+    fn position(&self, area: Rect, screen: Rect) -> Option<(u16, u16)> {
+        let x = u16::try_from(usize::from(area.x) + self.column).ok()?;
+        let y = u16::try_from(usize::from(area.y) + self.row).ok()?;
+        (usize::from(x) + self.columns <= usize::from(screen.width)
+            && usize::from(y) + self.rows <= usize::from(screen.height))
+        .then_some((x, y))
+    }
+}
+
+/// Returns `figure` as lines of plain text, for the line-based interface
+pub(crate) fn figure_text(figure: Figure) -> Vec<String> {
+    Content::draw_figure(figure, false, &Theme::default(), Content::GALLOWS_HEIGHT)
+        .into_iter()
+        .map(|line| {
+            line.spans
+                .into_iter()
+                .map(|span| span.content)
+                .collect::<String>()
+                .trim_end()
+                .to_owned()
+        })
+        .collect()
+}
+
+/// Returns the secret word as revealed so far as plain text, with its
+/// characters separated by spaces and `blank` in place of each character not
+/// yet revealed, for the line-based interface
+pub(crate) fn word_text(word: &[CharDisplay], blank: Placeholder) -> String {
+    word.iter()
+        .map(|&cd| match cd {
+            CharDisplay::Plain(ch) | CharDisplay::Highlighted(ch) => ch,
+            CharDisplay::Blank => blank.0,
+        })
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the secret word as revealed so far as words for a screen reader to
+/// read out, e.g., "C blank T", for `--accessible`
+pub(crate) fn word_speech(word: &[CharDisplay]) -> String {
+    word.iter()
+        .map(|&cd| match cd {
+            CharDisplay::Plain(ch) | CharDisplay::Highlighted(ch) if ch.is_whitespace() => {
+                String::from("space")
+            }
+            CharDisplay::Plain(ch) | CharDisplay::Highlighted(ch) => ch.to_uppercase().collect(),
+            CharDisplay::Blank => String::from("blank"),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Fail with [`ScreenError::NotATerminal`] unless standard output is a
+/// terminal and there's a terminal to read keys from, so that the game can
+/// give a clear error instead of failing partway through taking over the
+/// terminal
+pub(crate) fn require_terminal() -> Result<(), ScreenError> {
+    // Keys are read from the controlling terminal when standard input is
+    // redirected (as with `-f -`)
+    let input = io::stdin().is_terminal() || (cfg!(unix) && File::open("/dev/tty").is_ok());
+    if io::stdout().is_terminal() && input {
+        Ok(())
+    } else {
+        Err(ScreenError::NotATerminal)
+    }
+}
+
+/// Display `prompt` on standard error and read a line of input from the
+/// terminal without echoing it, as when entering a password.  Returns `None`
+/// if the user presses Escape or Ctrl-C instead of Enter.
+pub(crate) fn read_hidden(prompt: &str) -> Result<Option<String>, ScreenError> {
+    let mut stderr = io::stderr();
+    stderr.execute(Print(prompt)).map_err(ScreenError::Write)?;
+    enable_raw_mode().map_err(ScreenError::Init)?;
+    let r = read_hidden_line();
+    let _ = disable_raw_mode();
+    stderr.execute(Print("\r\n")).map_err(ScreenError::Write)?;
+    r
+}
+
+fn read_hidden_line() -> Result<Option<String>, ScreenError> {
+    let normal_modifiers = KeyModifiers::NONE | KeyModifiers::SHIFT;
+    let mut line = String::new();
+    loop {
+        if let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = read().map_err(ScreenError::Read)?
+        {
+            match code {
+                KeyCode::Enter => return Ok(Some(line)),
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if modifiers == KeyModifiers::CONTROL => return Ok(None),
+                KeyCode::Backspace => {
+                    line.pop();
+                }
+                KeyCode::Char(ch) if normal_modifiers.contains(modifiers) => line.push(ch),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// An action requested by the user via the keyboard
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Input {
+    /// The user typed a character as a guess
+    Guess(char),
+    /// The user pasted a whole word (of more than one character) as a guess
+    GuessWord(String),
+    /// The user pressed Tab to toggle assist mode
+    ToggleAssist,
+    /// The user pressed `+` or entered `:hint` to reveal the next hint
+    NextHint,
+    /// The user entered `:new` to abandon the word & draw a new one
+    NewWord,
+    /// The user pressed `?` or F1 to show the help overlay
+    Help,
+    /// The user pressed Enter to submit typed input
+    Enter,
+    /// The user pressed Backspace to delete typed input
+    Backspace,
+    /// The user pressed Escape to quit after the game ended, or pressed
+    /// Ctrl-C or entered `:q` at any time
+    Quit,
+    /// The user pressed Escape during the game to open the pause menu
+    Pause,
+    /// The countdown for making a guess ran out
+    TimeUp,
+    /// The terminal window lost focus during the game
+    Unfocused,
+    /// The user pressed Ctrl-Z or the process was sent SIGTSTP
+    Suspend,
+}
+
+/// The contents of the help overlay
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Help {
+    /// The keys (or other inputs) that do something in the game, each paired
+    /// with a description of what it does
+    pub(crate) keys: Vec<(&'static str, &'static str)>,
+    /// The rules in effect for the current game
+    pub(crate) rules: Vec<String>,
+    /// A description of where the secret word came from, if it came from
+    /// anywhere
+    pub(crate) source: Option<String>,
+}
+
+impl Help {
+    /// Returns the keys common to every game, which are listed after the
+    /// game-specific keys
+    pub(crate) fn common_keys() -> [(&'static str, &'static str); 4] {
+        [
+            ("Esc", "Pause the game"),
+            (":", "Type a command (:q, :new, :hint, :theme NAME)"),
+            ("Ctrl-C", "Quit the game"),
+            ("?, F1", "Show this help"),
+        ]
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let key_width = self
+            .keys
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![Line::styled(
+            " Keys:",
+            Style::new().add_modifier(Modifier::BOLD),
+        )];
+        for (key, action) in &self.keys {
+            lines.push(Line::from(format!("   {key:<key_width$}   {action} ")));
+        }
+        if !self.rules.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::styled(
+                " Rules:",
+                Style::new().add_modifier(Modifier::BOLD),
+            ));
+            for rule in &self.rules {
+                lines.push(Line::from(format!("   {rule} ")));
+            }
+        }
+        if let Some(source) = &self.source {
+            lines.push(Line::default());
+            lines.push(Line::from(format!(" Word source: {source} ")));
+        }
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            " Press any key to return to the game. ",
+            Page::DIMMED,
+        ));
+        lines
+    }
+}
+
+/// What the user chose from the pause menu
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum MenuChoice {
+    /// Go back to the game
+    Resume,
+    /// Abandon the current word & play a new one
+    NewWord,
+    /// Turn assist mode on or off
+    ToggleAssist,
+    /// Turn drawing the word in large letters on or off
+    ToggleBigWord,
+    /// Quit the game
+    Quit,
+}
+
+/// An item in the pause menu or its submenu of options
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MenuItem {
+    Resume,
+    NewWord,
+    Options,
+    Quit,
+    Assist,
+    BigWord,
+    Back,
+}
+
+impl MenuItem {
+    fn label(self) -> &'static str {
+        match self {
+            MenuItem::Resume => "Resume",
+            MenuItem::NewWord => "New word",
+            MenuItem::Options => "Options",
+            MenuItem::Quit => "Quit",
+            MenuItem::Assist => "Assist mode",
+            MenuItem::BigWord => "Large letters",
+            MenuItem::Back => "Back",
+        }
+    }
+
+    /// Returns the key that chooses the item, which is the first letter of
+    /// its label in lowercase
+    fn key(self) -> char {
+        self.label()
+            .chars()
+            .next()
+            .map_or(' ', |ch| ch.to_ascii_lowercase())
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum ScreenError {
+    #[error("failed to initialize terminal display")]
+    Init(#[source] io::Error),
+    #[error("failed to read from terminal")]
+    Read(#[source] io::Error),
+    #[error("failed to write to terminal")]
+    Write(#[source] io::Error),
+    #[error("game ended by {0}")]
+    Terminated(&'static str),
+    #[error("hangman requires an interactive terminal; see --no-tui")]
+    NotATerminal,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Content {
+    pub(crate) hint: Option<String>,
+    /// When the secret word has more than one hint, the number of the hint
+    /// shown and the total number of hints
+    pub(crate) hint_position: Option<(usize, usize)>,
+    /// The category of the secret word, shown alongside the hint
+    pub(crate) category: Option<String>,
+    /// The part of speech of the secret word (e.g., "noun"), shown alongside
+    /// the hint
+    pub(crate) part_of_speech: Option<String>,
+    pub(crate) figure: Figure,
+    /// In a duel, the computer opponent's figure, drawn to the right of the
+    /// letter grid
+    pub(crate) opponent: Option<Figure>,
+    /// Progress through a multi-word game, shown above the gallows
+    pub(crate) tally: Option<Tally>,
+    pub(crate) guess_options: Vec<GuessOption>,
+    /// How to draw the letters in `guess_options` that have been guessed
+    pub(crate) guessed: GuessedLetters,
+    /// How to arrange the letters in `guess_options` into a grid
+    pub(crate) letter_layout: LetterLayout,
+    /// Where to draw the parts of the display relative to each other
+    pub(crate) arrangement: Arrangement,
+    /// A letter in `guess_options` to highlight as the suggested next guess
+    pub(crate) suggestion: Option<char>,
+    /// The number of words in the word list consistent with the game so far,
+    /// if the cheat meter is enabled
+    pub(crate) candidates: Option<usize>,
+    /// The number of incorrect guesses that the user can still make and the
+    /// total number allowed, shown as a row of hearts above the figure
+    pub(crate) lives: Option<(usize, usize)>,
+    /// The incorrectly-guessed letters, in the order they were guessed, to
+    /// list beneath the figure
+    pub(crate) wrong: Option<Vec<char>>,
+    pub(crate) word_display: Vec<CharDisplay>,
+    /// When the secret word is too wide for the terminal & is wrapped across
+    /// lines, the number of characters that are kept together on a line
+    pub(crate) wrap_group: NonZeroUsize,
+    /// The character drawn in place of each unrevealed character of the word
+    pub(crate) blank: Placeholder,
+    /// Whether to draw the secret word in large letters when the terminal
+    /// has room for them
+    pub(crate) big_word: bool,
+    pub(crate) message: Message,
+    /// Which of the phrasings of `message` to show; see [`Message::text()`]
+    pub(crate) flavor: usize,
+    /// Text that the user is typing in response to a prompt, shown beneath
+    /// the message
+    pub(crate) input: Option<String>,
+    /// A definition of the secret word, shown beneath the message once the
+    /// game is over
+    pub(crate) definition: Option<String>,
+    /// Statistics on the finished game, shown beneath the end-of-game banner
+    pub(crate) stats: Option<Stats>,
+    /// The status line shown at the bottom of the screen, if enabled
+    pub(crate) status: Option<Status>,
+    /// The time left for making the next guess, shown as a shrinking bar
+    /// beneath the message
+    pub(crate) countdown: Option<Countdown>,
+    /// The state of the game as shown in the title of the terminal window
+    pub(crate) title: Title,
+    /// The guesses made so far, in order, each paired with how long into the
+    /// game it was made, listed in a sidebar when the terminal is wide enough
+    pub(crate) history: Vec<(Guess, Duration)>,
+}
+
+impl Content {
+    const GALLOWS_HEIGHT: usize = 5;
+    /// The width of the figure in terminal columns, which is more than its
+    /// number of characters when it's drawn with (double-width) emoji
+    const GALLOWS_WIDTH: usize = 8;
+    /// The smallest number of columns in the letter grid
+    const LETTER_COLUMNS: usize = 6;
+    const GUTTER: usize = 4;
+    const LETTERS_WIDTH: usize = (Content::LETTER_COLUMNS * 2) - 1;
+    /// The number of rows taken up by everything other than the figure when
+    /// the figure is at least as tall as the letter grid
+    const CHROME_HEIGHT: usize = 10;
+    /// The largest number of rows that a figure that scales with the terminal
+    /// is allowed to take up
+    const MAX_FIGURE_HEIGHT: usize = 15;
+    /// The largest number of lives that are shown as hearts rather than as a
+    /// number
+    const MAX_HEARTS: usize = 10;
+    /// The largest number of lines that a long hint is wrapped across
+    const MAX_HINT_LINES: usize = 3;
+
+    /// Returns the number of rows that a figure that scales with the terminal
+    /// should take up on a terminal `rows` tall, leaving room for the rest of
+    /// the display
+    fn figure_height(rows: usize) -> usize {
+        rows.saturating_sub(Content::CHROME_HEIGHT)
+            .clamp(Content::GALLOWS_HEIGHT, Content::MAX_FIGURE_HEIGHT)
+    }
+
+    /// The color of the lines of a gallows image whose style doesn't specify
+    /// a color
+    const IMAGE_COLOR: (u8, u8, u8) = (192, 192, 192);
+
+    /// Returns how long until the display next changes on its own, if it
+    /// shows a running clock or countdown
+    fn redraw_interval(&self) -> Option<Duration> {
+        let tick = self
+            .status
+            .and_then(|status| status.clock.running())
+            .map(|elapsed| {
+                Duration::from_secs(1).saturating_sub(Duration::new(0, elapsed.subsec_nanos()))
+            });
+        let frame = self
+            .countdown
+            .map(|countdown| Countdown::FRAME.min(countdown.remaining()));
+        tick.into_iter().chain(frame).min()
+    }
+
+    /// Returns the number of columns in which to lay out the letter grid
+    /// beside `figure`: enough for the letters to fit in as many rows as the
+    /// figure is tall, but no more than fit in the width of `screen`
+    fn letter_columns(&self, figure: &[Line<'_>], opponent: bool, screen: Rect) -> usize {
+        let figure_width = figure.first().map_or(Content::GALLOWS_WIDTH, Line::width);
+        let figures_width = if opponent {
+            (figure_width + Content::GUTTER) * 2
+        } else {
+            figure_width + Content::GUTTER
+        };
+        let max_columns = usize::from(screen.width)
+            .saturating_sub(figures_width)
+            .div_ceil(2);
+        let rows = figure.len().max(1);
+        self.guess_options
+            .len()
+            .div_ceil(rows)
+            .max(Content::LETTER_COLUMNS)
+            .min(max_columns.max(1))
+    }
+
+    /// Lay out the content for a terminal of size `screen`
+    fn render(self, theme: &Theme, screen: Rect) -> Page {
+        self.render_frame(theme, screen, Effect::None)
+    }
+
+    /// Lay out the content for a terminal of size `screen`, as it appears in
+    /// a frame of an animation with the given effect
+    fn render_frame(mut self, theme: &Theme, screen: Rect, effect: Effect) -> Page {
+        let hide = self.arrangement.hide;
+        if hide.hint {
+            self.hint = None;
+            self.category = None;
+            self.part_of_speech = None;
+        }
+        // The status line & the list of wrong guesses each take up a row
+        let extra_lines = usize::from(self.status.is_some()) + usize::from(self.wrong.is_some());
+        let mut rows = usize::from(screen.height).saturating_sub(extra_lines);
+        let mut highlight = self.message.gallows_advanced();
+        let mut opponent_highlight = self.message.opponent_gallows_advanced();
+        if effect == Effect::HideNewParts {
+            if highlight {
+                self.figure = self.figure.previous();
+                highlight = false;
+            }
+            if opponent_highlight {
+                self.opponent = self.opponent.map(Figure::previous);
+                opponent_highlight = false;
+            }
+        }
+        // Large letters are only used if they fit across the terminal and
+        // leave room for at least a regular-sized figure
+        let big_word_width =
+            (self.word_display.len() * (CharDisplay::BIG_WIDTH + 1)).saturating_sub(1);
+        let big_word = self.big_word
+            && big_word_width <= usize::from(screen.width)
+            && rows
+                >= Content::CHROME_HEIGHT + Content::GALLOWS_HEIGHT + CharDisplay::BIG_HEIGHT - 1;
+        if big_word {
+            rows -= CharDisplay::BIG_HEIGHT - 1;
+        }
+        // Likewise, the banner at the end of the game (which takes up the
+        // rows of its large letters, a blank line above them, and the line of
+        // statistics beneath the message) is only drawn if it fits.  It's not
+        // drawn during the victory animation, which uses the line above the
+        // word.
+        let banner_rows = CharDisplay::BIG_HEIGHT + 1 + usize::from(self.stats.is_some());
+        let banner = self
+            .message
+            .banner()
+            .filter(|_| !matches!(effect, Effect::Sparkles(_)))
+            .map(|text| {
+                (
+                    text,
+                    (text.len() * (CharDisplay::BIG_WIDTH + 1)).saturating_sub(1),
+                )
+            })
+            .filter(|&(_, banner_width)| {
+                banner_width <= usize::from(screen.width)
+                    && rows >= Content::CHROME_HEIGHT + Content::GALLOWS_HEIGHT + banner_rows
+            });
+        if banner.is_some() {
+            rows -= banner_rows;
+        }
+        let height = Content::figure_height(rows);
+        let figure = Content::draw_figure(self.figure, highlight, theme, height);
+        let opponent = self
+            .opponent
+            .map(|fig| Content::draw_figure(fig, opponent_highlight, theme, height));
+        // The width of the figures in columns, not counting any lines that
+        // may extend past the letter grid
+        let figure_width = figure.first().map_or(Content::GALLOWS_WIDTH, Line::width);
+        // Even when the figures are hidden, the letter grid keeps the shape
+        // it has beside them
+        let letter_rows = if hide.letters {
+            Vec::new()
+        } else {
+            self.letter_layout.arrange(
+                &self.guess_options,
+                self.letter_columns(&figure, opponent.is_some(), screen),
+            )
+        };
+        let letters_width = if hide.letters {
+            0
+        } else {
+            letter_rows
+                .iter()
+                .map(|(indent, row)| indent + (row.len() * 2).saturating_sub(1))
+                .fold(Content::LETTERS_WIDTH, usize::max)
+        };
+        let (figure, opponent) = if hide.gallows {
+            (None, None)
+        } else {
+            (Some(figure), opponent)
+        };
+        let figures = usize::from(figure.is_some()) + usize::from(opponent.is_some());
+        let columns = [
+            figure.as_ref().map(|_| figure_width),
+            (!hide.letters).then_some(letters_width),
+            opponent.as_ref().map(|_| figure_width),
+        ];
+        let mut width = columns.iter().flatten().sum::<usize>()
+            + Content::GUTTER * columns.iter().flatten().count().saturating_sub(1);
+        let mut board = Vec::with_capacity(figure.as_ref().map_or(0, Vec::len) + 5);
+        let hint = match (self.hint, self.part_of_speech) {
+            (Some(hint), pos) => {
+                let mut label = String::from("Hint");
+                if let Some((number, total)) = self.hint_position {
+                    write!(label, " {number}/{total}")
+                        .expect("writing to a String should not fail");
+                }
+                if let Some(pos) = pos {
+                    write!(label, " ({pos})").expect("writing to a String should not fail");
+                }
+                Some(format!("{label}: {hint}"))
+            }
+            (None, Some(pos)) => Some(format!("Part of speech: {pos}")),
+            (None, None) => None,
+        };
+        let hint_text = match (self.category, hint) {
+            (Some(category), Some(hint)) => format!("Category: {category}    {hint}"),
+            (Some(category), None) => format!("Category: {category}"),
+            (None, Some(hint)) => hint,
+            (None, None) => String::new(),
+        };
+        // The hint is filled in once the width of the board is known
+        board.push(Line::default());
+        let meter = match self.candidates {
+            Some(1) => Some(String::from("1 possible word")),
+            Some(n) => Some(format!("{n} possible words")),
+            None => None,
+        };
+        let lives = self.lives.map(|(remaining, total)| {
+            if total <= Content::MAX_HEARTS {
+                format!("{}{}", "♥".repeat(remaining), "♡".repeat(total - remaining))
+            } else {
+                format!("Lives: {remaining}/{total}")
+            }
+        });
+        board.push(Line::from(
+            [self.tally.map(|tally| tally.to_string()), meter, lives]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("    "),
+        ));
+        let mirror = self.arrangement.mirror;
+        let grid_start = board.len();
+        if opponent.is_some() {
+            let (left_name, right_name) = if mirror {
+                ("Computer", "You")
+            } else {
+                ("You", "Computer")
+            };
+            board.push(Line::from(format!(
+                "{:^figure$}{:gap$}{:^figure$}",
+                left_name,
+                "",
+                right_name,
+                figure = figure_width,
+                gap = if hide.letters {
+                    Content::GUTTER
+                } else {
+                    Content::GUTTER * 2 + letters_width
+                },
+            )));
+        }
+        let (figure_column, opponent_column) = if mirror {
+            (width - figure_width, 0)
+        } else {
+            (0, width - figure_width)
+        };
+        let mut images = Vec::new();
+        for (figure, highlight, column) in [
+            (
+                figure.as_ref().map(|_| self.figure),
+                highlight,
+                figure_column,
+            ),
+            (
+                self.opponent.filter(|_| opponent.is_some()),
+                opponent_highlight,
+                opponent_column,
+            ),
+        ] {
+            if let Some(Figure::Image(graphics, gallows)) = figure {
+                images.push(Image {
+                    graphics,
+                    escape: Content::draw_image(graphics, gallows, highlight, theme),
+                    row: board.len(),
+                    column,
+                    columns: Content::GALLOWS_WIDTH,
+                    rows: Content::GALLOWS_HEIGHT,
+                });
+            }
+        }
+        // The figures drawn to the left & right of the letter grid
+        let (left, right) = if mirror {
+            (opponent.as_ref(), figure.as_ref())
+        } else {
+            (figure.as_ref(), opponent.as_ref())
+        };
+        let letters_start = if left.is_some() {
+            figure_width + Content::GUTTER
+        } else {
+            0
+        };
+        let mut letters = Vec::new();
+        // Nothing follows the figure on the left if it's all that's shown
+        let left_gutter = if hide.letters && figures == 1 {
+            0
+        } else {
+            Content::GUTTER
+        };
+        let figure_rows = left.or(right).map_or(0, Vec::len);
+        for i in 0..figure_rows.max(letter_rows.len()) {
+            let mut spans = match left.map(|lines| lines.get(i)) {
+                Some(Some(row)) => {
+                    let mut spans = row.spans.clone();
+                    spans.push(Span::raw(" ".repeat(left_gutter)));
+                    spans
+                }
+                Some(None) => vec![Span::raw(" ".repeat(figure_width + left_gutter))],
+                None => Vec::new(),
+            };
+            let (indent, optchunk) = letter_rows
+                .get(i)
+                .map_or((0, &[][..]), |(indent, row)| (*indent, &row[..]));
+            if !optchunk.is_empty() {
+                spans.push(Span::raw(" ".repeat(indent)));
+            }
+            for (j, opt) in optchunk.iter().enumerate() {
+                if j > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                let column = letters_start + indent + j * 2;
+                letters.push((board.len(), column, *opt));
+                spans.push(match *opt {
+                    GuessOption::Open(ch) if Some(ch) == self.suggestion => {
+                        Span::styled(ch.to_string(), theme.suggestion)
+                    }
+                    GuessOption::Open(ch) => Span::raw(ch.to_string()),
+                    GuessOption::Guessed(ch) => match self.guessed.style() {
+                        Some(style) => Span::styled(ch.to_string(), style),
+                        None => Span::raw(" "),
+                    },
+                });
+            }
+            if let Some(row) = right.and_then(|lines| lines.get(i)) {
+                let used = if optchunk.is_empty() {
+                    0
+                } else {
+                    indent + optchunk.len() * 2 - 1
+                };
+                let pad = if hide.letters {
+                    0
+                } else {
+                    letters_width.saturating_sub(used) + Content::GUTTER
+                };
+                spans.push(Span::raw(" ".repeat(pad)));
+                spans.extend(row.spans.iter().cloned());
+            }
+            board.push(Line::from(spans));
+        }
+        if let Some(wrong) = self.wrong {
+            // The line is left blank until there's a letter to list, so that
+            // the layout doesn't shift on the first miss
+            board.push(Line::from(if wrong.is_empty() {
+                String::new()
+            } else {
+                let letters = wrong.iter().map(char::to_string).collect::<Vec<_>>();
+                format!("Wrong: {}", letters.join(", "))
+            }));
+        }
+        // The number of columns taken up by each character of the word at
+        // normal size, which is wider than one if the placeholder for blanks
+        // is a wide character
+        let cell = self.blank.width();
+        let word_rows = if big_word {
+            vec![&self.word_display[..]]
+        } else {
+            Content::wrap_word(
+                &self.word_display,
+                usize::from(screen.width),
+                cell,
+                self.wrap_group,
+            )
+        };
+        let word_width = if big_word {
+            big_word_width
+        } else {
+            word_rows
+                .iter()
+                .map(|row| (row.len() * (cell + 1)).saturating_sub(1))
+                .max()
+                .unwrap_or_default()
+        };
+        let big_width = banner
+            .map_or(0, |(_, banner_width)| banner_width)
+            .max(word_width);
+        let mut offset = 0;
+        if big_width > width {
+            // Widen the page to fit the large letters or long word, keeping
+            // the rest of the board centered above them
+            offset = (big_width - width) / 2;
+            for line in &mut board {
+                line.spans.insert(0, Span::raw(" ".repeat(offset)));
+            }
+            for image in &mut images {
+                image.column += offset;
+            }
+            for (_, column, _) in &mut letters {
+                *column += offset;
+            }
+            width = big_width;
+        }
+        // Wrap the hint to fit between the board's indentation & the right
+        // edge of the terminal
+        let margin = usize::from(screen.width).saturating_sub(width) / 2;
+        let hint_lines = match self.arrangement.hint {
+            // A hidden hint doesn't leave a blank line behind
+            _ if hide.hint => Vec::new(),
+            HintPlacement::Top => Content::wrap_text(
+                &hint_text,
+                usize::from(screen.width).saturating_sub(margin + offset),
+                Content::MAX_HINT_LINES,
+            ),
+            HintPlacement::Bottom => Vec::new(),
+        };
+        if let Some((text, banner_width)) = banner {
+            board.push(Line::default());
+            let banner_indent = " ".repeat((width - banner_width) / 2);
+            for row in 0..CharDisplay::BIG_HEIGHT {
+                let mut s = banner_indent.clone();
+                for (i, ch) in text.chars().enumerate() {
+                    if i > 0 {
+                        s.push(' ');
+                    }
+                    s.push_str(&CharDisplay::big_char(ch, row));
+                }
+                board.push(Line::styled(s, theme.message));
+            }
+        }
+        let indent = width.saturating_sub(word_width) / 2;
+        let sparkles = match effect {
+            Effect::Sparkles(frame) => Some(frame),
+            _ => None,
+        };
+        let word_start = board.len();
+        board.push(match sparkles {
+            Some(frame) => Content::sparkle_line(indent, word_width, frame, theme),
+            None => Line::default(),
+        });
+        // Each line of the word is paired with its indentation, so that the
+        // lines of a wrapped word are each centered
+        let mut wordlines = if big_word {
+            (0..CharDisplay::BIG_HEIGHT)
+                .map(|row| {
+                    let mut spans = Vec::new();
+                    for (i, ch) in self.word_display.iter().enumerate() {
+                        if i > 0 {
+                            spans.push(Span::raw(" "));
+                        }
+                        spans.push(ch.big_span(row, theme));
+                    }
+                    (indent, spans)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            word_rows
+                .iter()
+                .map(|row| {
+                    let mut spans = Vec::new();
+                    for (i, ch) in row.iter().enumerate() {
+                        if i > 0 {
+                            spans.push(Span::raw(" "));
+                        }
+                        let span = ch.span(theme, self.blank);
+                        let pad = cell.saturating_sub(span.width());
+                        spans.push(span);
+                        if pad > 0 {
+                            spans.push(Span::raw(" ".repeat(pad)));
+                        }
+                    }
+                    let row_width = (row.len() * (cell + 1)).saturating_sub(1);
+                    (width.saturating_sub(row_width) / 2, spans)
+                })
+                .collect::<Vec<_>>()
+        };
+        if let Some(frame) = sparkles.filter(|_| wordlines.iter().all(|&(indent, _)| indent >= 2)) {
+            let side = ["*", "+"][frame % 2];
+            let middle = wordlines.len() / 2;
+            for (i, (indent, spans)) in wordlines.iter_mut().enumerate() {
+                *indent -= 2;
+                if i == middle {
+                    spans.insert(0, Span::styled(format!("{side} "), theme.revealed));
+                    spans.push(Span::styled(format!(" {side}"), theme.revealed));
+                } else {
+                    spans.insert(0, Span::raw("  "));
+                }
+            }
+        }
+        board.extend(wordlines.into_iter().map(|(indent, mut spans)| {
+            spans.insert(0, Span::raw(" ".repeat(indent)));
+            Line::from(spans)
+        }));
+        board.push(match sparkles {
+            Some(frame) => Content::sparkle_line(indent, word_width, frame + 2, theme),
+            None => Line::default(),
+        });
+        if self.arrangement.word == WordPlacement::Above {
+            // Move the word (& the lines around it) up above the figures &
+            // letter grid
+            let word_lines = board.drain(word_start..).collect::<Vec<_>>();
+            let shift = word_lines.len();
+            board.splice(grid_start..grid_start, word_lines);
+            for image in &mut images {
+                image.row += shift;
+            }
+            for (row, _, _) in &mut letters {
+                *row += shift;
+            }
+        }
+        // Replace the placeholder for the hint with the wrapped hint (if it's
+        // shown at the top), which everything else on the board is below
+        let hint_rows = hint_lines.len();
+        board.splice(
+            0..1,
+            hint_lines.into_iter().map(|ln| {
+                let mut line = Line::styled(ln, theme.hint);
+                if offset > 0 {
+                    line.spans.insert(0, Span::raw(" ".repeat(offset)));
+                }
+                line
+            }),
+        );
+        for image in &mut images {
+            image.row = image.row + hint_rows - 1;
+        }
+        for (row, _, _) in &mut letters {
+            *row = *row + hint_rows - 1;
+        }
+        let mut text = Vec::new();
+        if !hide.message {
+            text.push(Line::styled(self.message.text(self.flavor), theme.message));
+        }
+        if let Some(stats) = self.stats.filter(|_| banner.is_some()) {
+            text.push(Line::from(stats.to_string()));
+        }
+        text.push(Line::from(match (self.input, self.definition) {
+            (Some(input), _) => format!("> {input}"),
+            (None, Some(definition)) => definition,
+            (None, None) => String::new(),
+        }));
+        let mut prompt = self.message == Message::Solved;
+        if sparkles.is_some() {
+            text.push(Line::default());
+        } else if self.message.is_game_over() {
+            text.push(Line::from("Press the Any Key to exit."));
+            prompt = true;
+        } else if matches!(self.message, Message::RoundWon { .. } | Message::RoundLost) {
+            text.push(Line::from("Press any key to continue."));
+            prompt = true;
+        } else if let Some(countdown) = self.countdown {
+            text.push(Line::from(countdown.bar(countdown.remaining(), width)));
+        } else {
+            text.push(Line::default());
+        }
+        let text_width = usize::from(screen.width).saturating_sub(margin);
+        if self.arrangement.hint == HintPlacement::Bottom && !hint_text.is_empty() {
+            for ln in Content::wrap_text(&hint_text, text_width, Content::MAX_HINT_LINES) {
+                text.push(Line::styled(ln, theme.hint));
+            }
+        }
+        if let Some(status) = self.status {
+            text.push(Line::from(status.to_string()));
+        }
+        // Wrap the text here rather than leaving it to the terminal, so that
+        // the page's height accounts for the wrapped lines when centering it
+        let text = text
+            .into_iter()
+            .flat_map(|line| {
+                let style = line
+                    .spans
+                    .first()
+                    .map_or_else(Style::new, |span| span.style);
+                let content = line
+                    .spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>();
+                Content::wrap_text(&content, text_width, usize::MAX)
+                    .into_iter()
+                    .map(move |ln| Line::styled(ln, style))
+            })
+            .collect();
+        Page {
+            width,
+            board,
+            text,
+            images,
+            letters,
+            prompt,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: if hide.history {
+                Vec::new()
+            } else {
+                Content::sidebar(&self.history, theme)
+            },
+        }
+    }
+
+    /// Returns the lines of the sidebar listing `history`: a heading followed
+    /// by each guess, marked with whether it was correct and when it was made
+    fn sidebar(history: &[(Guess, Duration)], theme: &Theme) -> Vec<Line<'static>> {
+        if history.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![Line::from("Guesses")];
+        for (guess, at) in history {
+            let secs = at.as_secs();
+            let (mark, style) = if guess.is_hit() {
+                ("✓", Style::new())
+            } else {
+                ("✗", theme.new_part)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(mark, style),
+                Span::raw(format!(
+                    " {} {:>2}:{:02}",
+                    guess.letter,
+                    secs / 60,
+                    secs % 60
+                )),
+            ]));
+        }
+        lines
+    }
+
+    /// Wrap `text` at spaces into lines no wider than `width` columns,
+    /// breaking words that are too long to fit on a line by themselves.  If
+    /// more than `max_lines` lines are needed, the text is cut off with an
+    /// ellipsis.  Empty text is returned as a single empty line.
+    fn wrap_text(text: &str, width: usize, max_lines: usize) -> Vec<String> {
+        let text_width = |s: &str| Span::raw(s).width();
+        let width = width.max(1);
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        for word in text.split(' ') {
+            if current.is_empty() && word.is_empty() {
+                continue;
+            }
+            let needed = text_width(&current) + 1 + text_width(word);
+            if !current.is_empty() && needed <= width {
+                current.push(' ');
+                current.push_str(word);
+                continue;
+            }
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current).trim_end().to_owned());
+            }
+            for ch in word.chars() {
+                let mut buf = [0; 4];
+                if text_width(&current) + text_width(ch.encode_utf8(&mut buf)) > width {
+                    lines.push(std::mem::take(&mut current));
+                }
+                current.push(ch);
+            }
+        }
+        if !current.is_empty() || lines.is_empty() {
+            lines.push(current);
+        }
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            if let Some(last) = lines.last_mut() {
+                while !last.is_empty() && text_width(last) + 1 > width {
+                    last.pop();
+                }
+                last.push('…');
+            }
+        }
+        lines
+    }
+
+    /// Split the secret word, whose characters are each drawn `cell` columns
+    /// wide with a space between them, into rows that each fit within `width`
+    /// columns.  If the word needs more than one row, each row but the last
+    /// holds a multiple of `group` characters, unless a group is too wide to
+    /// fit.
+    fn wrap_word(
+        word: &[CharDisplay],
+        width: usize,
+        cell: usize,
+        group: NonZeroUsize,
+    ) -> Vec<&[CharDisplay]> {
+        let fit = (width + 1) / (cell + 1);
+        if word.len() <= fit {
+            return vec![word];
+        }
+        let group = group.get();
+        let per_row = if group <= fit {
+            fit / group * group
+        } else {
+            fit.max(1)
+        };
+        word.chunks(per_row).collect()
+    }
+
+    /// Returns a line of sparkles spanning the secret word (which starts
+    /// `indent` columns in & is `word_width` columns wide) plus two columns on
+    /// either side, as it appears in the given frame of the victory animation
+    fn sparkle_line(
+        indent: usize,
+        word_width: usize,
+        frame: usize,
+        theme: &Theme,
+    ) -> Line<'static> {
+        let start = indent.saturating_sub(2);
+        let sparkles = (start..indent + word_width + 2)
+            .map(|column| match (column + frame) % 4 {
+                0 => '*',
+                2 => '.',
+                _ => ' ',
+            })
+            .collect::<String>();
+        Line::from(vec![
+            Span::raw(" ".repeat(start)),
+            Span::styled(sparkles, theme.revealed),
+        ])
+    }
+
+    /// Draw `figure`, scaling it to `height` rows if it's a figure that scales
+    fn draw_figure(
+        figure: Figure,
+        highlight: bool,
+        theme: &Theme,
+        height: usize,
+    ) -> Vec<Line<'static>> {
+        match figure {
+            Figure::Gallows(gallows) => Content::draw_gallows(gallows, highlight, theme),
+            Figure::Scene(Art::Braille, gallows) => {
+                Content::draw_braille(gallows, highlight, theme, height)
+            }
+            Figure::Scene(art, gallows) => Content::draw_scene(art, gallows, highlight, theme),
+            Figure::Image(..) => {
+                vec![Line::from(" ".repeat(Content::GALLOWS_WIDTH)); Content::GALLOWS_HEIGHT]
+            }
+            Figure::Dancer(misses) => Content::draw_dancer(misses)
+                .iter()
+                .map(|&row| Line::styled(row, theme.gallows))
+                .collect(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn draw_dancer(misses: usize) -> &'static [&'static str; Content::GALLOWS_HEIGHT] {
+        static FRAMES: [[&str; Content::GALLOWS_HEIGHT]; 4] = [
+            [
+                "        ",
+                "    o   ",
+                "   /|\\  ",
+                "   / \\  ",
+                "────────",
+            ],
+            [
+                "        ",
+                "    o/  ",
+                "   /|   ",
+                "   / \\  ",
+                "────────",
+            ],
+            [
+                "        ",
+                "   \\o   ",
+                "    |\\  ",
+                "   / \\  ",
+                "────────",
+            ],
+            [
+                "        ",
+                "   \\o/  ",
+                "    |   ",
+                "   / \\  ",
+                "────────",
+            ],
+        ];
+        &FRAMES[misses % FRAMES.len()]
+    }
+
+    /// Draw one of the alternatives to the gallows in the stage matching
+    /// `gallows`.  When `highlight` is true, the characters that changed since
+    /// the previous stage are drawn in the style for a new body part.
+    #[rustfmt::skip]
+    fn draw_scene(art: Art, gallows: Gallows, highlight: bool, theme: &Theme) -> Vec<Line<'static>> {
+        type Frames = [[&'static str; Content::GALLOWS_HEIGHT]; Gallows::PARTS + 1];
+        static SNOWMAN: Frames = [
+            ["  _===_ ", "  (o.o) ", " <( : )>", "  ( : ) ", "────────"],
+            ["        ", "  (o.o) ", " <( : )>", "  ( : ) ", "────────"],
+            ["        ", "  (o.o) ", "  ( : ) ", "  ( : ) ", "────────"],
+            ["        ", "  (;.;) ", "  ( : ) ", "  ( : ) ", "────────"],
+            ["        ", "        ", "  (;.;) ", "  (   ) ", "────────"],
+            ["        ", "        ", "        ", "  (;_;) ", "────────"],
+            ["        ", "        ", "        ", "  ~~~~  ", "────────"],
+        ];
+        static FLOWER: Frames = [
+            ["   (@)  ", "   \\|/  ", "    |   ", "  [___] ", "────────"],
+            ["   (@   ", "   \\|/  ", "    |   ", "  [___] ", "────────"],
+            ["    @   ", "   \\|/  ", "    |   ", "  [___] ", "────────"],
+            ["    @   ", "   /|\\  ", "    |   ", "  [___] ", "────────"],
+            ["        ", "    _@  ", "   /|\\  ", "  [___] ", "────────"],
+            ["        ", "        ", "   _/@  ", "  [___] ", "────────"],
+            ["        ", "        ", "   .,.  ", "  [___] ", "────────"],
+        ];
+        static BOAT: Frames = [
+            ["    |\\  ", "    | \\ ", "    |__\\", "\\______/", "~~~~~~~~"],
+            ["    |\\  ", "    | \\ ", "    |__\\", "\\_~~___/", "~~~~~~~~"],
+            ["    |\\  ", "    | \\ ", "    |__\\", "~~~~~~~~", "~~~~~~~~"],
+            ["        ", "    |\\  ", "~~~~|__\\", "~~~~~~~~", "~~~~~~~~"],
+            ["        ", "        ", "~~~~|\\~~", "~~~~~~~~", "~~~~~~~~"],
+            ["        ", "        ", "~~~~|~~~", "~~~~~~~~", "~~~~~~~~"],
+            ["        ", "        ", "~~~~~~~~", "~~~~~~~~", "~~~~~~~~"],
+        ];
+        let frames = match art {
+            Art::Gallows => return Content::draw_gallows(gallows, highlight, theme),
+            Art::Emoji => return Content::draw_emoji(gallows, highlight, theme),
+            Art::Braille => {
+                return Content::draw_braille(gallows, highlight, theme, Content::GALLOWS_HEIGHT)
+            }
+            Art::Snowman => &SNOWMAN,
+            Art::Flower => &FLOWER,
+            Art::Boat => &BOAT,
+        };
+        let (parts, total) = gallows.progress();
+        let style = theme.gallows_at(parts, total);
+        let previous = parts.checked_sub(1).filter(|_| highlight).map(|p| &frames[p]);
+        frames[parts]
+            .iter()
+            .enumerate()
+            .map(|(i, &row)| match previous {
+                Some(before) => {
+                    let mut spans = Vec::new();
+                    for (ch, was) in row.chars().zip(before[i].chars()) {
+                        let changed = ch != ' ' && ch != was;
+                        let ch_style = if changed { theme.new_part } else { style };
+                        push_styled(&mut spans, ch.encode_utf8(&mut [0; 4]), ch_style);
+                    }
+                    Line::from(spans)
+                }
+                None => Line::styled(row, style),
+            })
+            .collect()
+    }
+
+    /// Draw the gallows with emoji.  Each emoji takes up two columns, so the
+    /// figure is laid out as a grid of two-column cells, with empty cells
+    /// filled by two spaces.
+    #[rustfmt::skip]
+    fn draw_emoji(gallows: Gallows, highlight: bool, theme: &Theme) -> Vec<Line<'static>> {
+        const EMPTY: &str = "  ";
+        const WOOD: &str = "\u{1F7EB}";
+        static FULL: [[&str; Content::GALLOWS_WIDTH / 2]; Content::GALLOWS_HEIGHT] = [
+            [WOOD, WOOD,        WOOD,        EMPTY      ],
+            [WOOD, EMPTY,       "\u{1F635}", EMPTY      ],
+            [WOOD, "\u{1F4AA}", "\u{1F455}", "\u{1F4AA}"],
+            [WOOD, "\u{1F9B5}", EMPTY,       "\u{1F9B5}"],
+            [WOOD, WOOD,        WOOD,        WOOD       ],
+        ];
+        // The cells of the body parts, in the order in which they're added
+        static PARTS: [(usize, usize); Gallows::PARTS] =
+            [(1, 2), (2, 2), (2, 1), (2, 3), (3, 1), (3, 3)];
+        let (parts, total) = gallows.progress();
+        let style = theme.gallows_at(parts, total);
+        FULL.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut spans = Vec::new();
+                for (j, &cell) in row.iter().enumerate() {
+                    let (cell, cell_style) = match PARTS.iter().position(|&p| p == (i, j)) {
+                        Some(k) if k >= parts => (EMPTY, style),
+                        Some(k) if highlight && k + 1 == parts => (cell, theme.new_part),
+                        _ => (cell, style),
+                    };
+                    push_styled(&mut spans, cell, cell_style);
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+
+    /// Draw the gallows with Braille characters, each of which is a block of
+    /// 2×4 dots, scaled to `height` rows
+    fn draw_braille(
+        gallows: Gallows,
+        highlight: bool,
+        theme: &Theme,
+        height: usize,
+    ) -> Vec<Line<'static>> {
+        let (parts, total) = gallows.progress();
+        let columns = (height * Content::GALLOWS_WIDTH + Content::GALLOWS_HEIGHT / 2)
+            / Content::GALLOWS_HEIGHT;
+        Bitmap::gallows(gallows, highlight, columns * 2, height * 4, 1)
+            .braille(theme.gallows_at(parts, total), theme.new_part)
+    }
+
+    /// Returns the escape sequence for drawing the gallows as an image with
+    /// `graphics`, sized to take up as many cells as the text gallows
+    fn draw_image(graphics: Graphics, gallows: Gallows, highlight: bool, theme: &Theme) -> String {
+        let (parts, total) = gallows.progress();
+        let (width, height) = graphics.image_size(Content::GALLOWS_WIDTH, Content::GALLOWS_HEIGHT);
+        let bitmap = Bitmap::gallows(gallows, highlight, width, height, 2);
+        let plain = theme
+            .gallows_at(parts, total)
+            .fg
+            .and_then(color_rgb)
+            .unwrap_or(Content::IMAGE_COLOR);
+        let marked = theme.new_part.fg.and_then(color_rgb).unwrap_or(plain);
+        graphics.encode(
+            &bitmap,
+            Content::GALLOWS_WIDTH,
+            Content::GALLOWS_HEIGHT,
+            plain,
+            marked,
+        )
+    }
+
+    #[rustfmt::skip]
+    fn draw_gallows(gallows: Gallows, highlight: bool, theme: &Theme) -> Vec<Line<'static>> {
+        static FRAMES: [[&str; Content::GALLOWS_HEIGHT]; Gallows::PARTS + 1] = [
+            [
+                "  ┌───┐ ",
+                "  │     ",
+                "  │     ",
+                "  │     ",
+                "──┴──   ",
+            ],
+            [
+                "  ┌───┐ ",
+                "  │   o ",
+                "  │     ",
+                "  │     ",
+                "──┴──   ",
+            ],
+            [
+                "  ┌───┐ ",
+                "  │   o ",
+                "  │   | ",
+                "  │     ",
+                "──┴──   ",
+            ],
+            [
+                "  ┌───┐ ",
+                "  │   o ",
+                "  │  /| ",
+                "  │     ",
+                "──┴──   ",
+            ],
+            [
+                "  ┌───┐ ",
+                "  │   o ",
+                "  │  /|\\",
+                "  │     ",
+                "──┴──   ",
+            ],
+            [
+                "  ┌───┐ ",
+                "  │   o ",
+                "  │  /|\\",
+                "  │  /  ",
+                "──┴──   ",
+            ],
+            [
+                "  ┌───┐ ",
+                "  │   o ",
+                "  │  /|\\",
+                "  │  / \\",
+                "──┴──   ",
+            ],
+        ];
+        // The row & column of the body part added in each frame
+        static ADDED: [Option<(usize, usize)>; Gallows::PARTS + 1] = [
+            None,
+            Some((1, 6)),
+            Some((2, 6)),
+            Some((2, 5)),
+            Some((2, 7)),
+            Some((3, 5)),
+            Some((3, 7)),
+        ];
+        let (parts, total) = gallows.progress();
+        let style = theme.gallows_at(parts, total);
+        let mut lines = FRAMES[parts].map(|row| Line::styled(row, style)).to_vec();
+        if let Some((row, column)) = ADDED[parts].filter(|_| highlight) {
+            let frame_row = FRAMES[parts][row];
+            let (start, end) = frame_row
+                .char_indices()
+                .nth(column)
+                .map(|(i, ch)| (i, i + ch.len_utf8()))
+                .expect("highlighted body part should be within gallows row");
+            lines[row] = Line::from(vec![
+                Span::styled(&frame_row[..start], style),
+                Span::styled(&frame_row[start..end], theme.new_part),
+                Span::styled(&frame_row[end..], style),
+            ]);
+        }
+        lines
+    }
+}
+
+/// A variation on how content is drawn, used for the frames of animations
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Effect {
+    /// Draw the content as is
+    None,
+    /// Leave out any body parts added by the latest guess, so that they can
+    /// be made to blink
+    HideNewParts,
+    /// Surround the secret word with sparkles, in the pattern for the given
+    /// frame of the victory animation, & hold off on the "press any key"
+    /// prompt
+    Sparkles(usize),
+}
+
+/// The results of all the rounds of a match, shown once the match is over
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct MatchSummary {
+    pub(crate) rounds: Vec<RoundResult>,
+}
+
+impl MatchSummary {
+    fn render(&self) -> Page {
+        let mut lines = self.lines();
+        lines.push(String::new());
+        lines.push(String::from("Press the Any Key to exit."));
+        Page {
+            width: lines.iter().map(|ln| ln.chars().count()).max().unwrap_or(0),
+            board: lines.into_iter().map(Line::from).collect(),
+            text: Vec::new(),
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: true,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        }
+    }
+
+    /// Returns the lines of the summary as plain text
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let mut lines = vec![String::from("Match over!"), String::new()];
+        let word_width = self
+            .rounds
+            .iter()
+            .map(|r| r.word.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (i, round) in self.rounds.iter().enumerate() {
+            lines.push(format!(
+                "Round {:>2}:  {:<word_width$}  {:<4}  {:>4}",
+                i + 1,
+                round.word,
+                if round.won { "won" } else { "lost" },
+                round.points,
+            ));
+        }
+        lines.push(String::new());
+        let won = self.rounds.iter().filter(|r| r.won).count();
+        let total = self.rounds.iter().map(|r| r.points).sum::<usize>();
+        lines.push(format!(
+            "Won {won} of {} rounds for a total of {total} points",
+            self.rounds.len()
+        ));
+        lines
+    }
+}
+
+/// The outcome of a single round of a match
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RoundResult {
+    /// The secret word
+    pub(crate) word: String,
+    pub(crate) won: bool,
+    pub(crate) points: usize,
+}
+
+/// Progress through a game consisting of multiple words or players
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Tally {
+    /// The number of words solved so far in endless mode
+    Solved(usize),
+    /// The word currently being played in a lightning round, counting from 1
+    Word { number: usize, total: usize },
+    /// The round currently being played in a match, counting from 1, and the
+    /// user's score so far
+    Round {
+        number: usize,
+        total: usize,
+        score: usize,
+    },
+    /// The player (counting from 0) whose turn it is in co-op mode
+    Turn { player: usize },
+    /// How each player fared in a finished co-op game
+    Credits(Vec<Credit>),
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tally::Solved(n) => write!(f, "Words solved: {n}"),
+            Tally::Word { number, total } => write!(f, "Word {number} of {total}"),
+            Tally::Round {
+                number,
+                total,
+                score,
+            } => write!(f, "Round {number} of {total}    Score: {score}"),
+            Tally::Turn { player } => write!(f, "Player {}'s turn", player + 1),
+            Tally::Credits(credits) => {
+                for (i, credit) in credits.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "    ")?;
+                    }
+                    write!(
+                        f,
+                        "P{}: {} right, {} wrong",
+                        i + 1,
+                        credit.right,
+                        credit.wrong
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A summary of the game's progress, shown in the title of the terminal
+/// window, e.g. "hangman — 4 misses left"
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Title {
+    /// The name of the game mode, if not a normal game
+    pub(crate) mode: Option<&'static str>,
+    /// `Some(true)` if the user won the game, `Some(false)` if they lost,
+    /// `None` if it's still in progress
+    pub(crate) won: Option<bool>,
+    /// The number of incorrect guesses that can still be made, if limited
+    pub(crate) misses_remaining: Option<usize>,
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let progress = match (self.won, self.misses_remaining) {
+            (Some(true), _) => Some(String::from("you won!")),
+            (Some(false), _) => Some(String::from("you lost")),
+            (None, Some(1)) => Some(String::from("1 miss left")),
+            (None, Some(n)) => Some(format!("{n} misses left")),
+            (None, None) => None,
+        };
+        write!(f, "hangman")?;
+        match (self.mode, progress) {
+            (Some(mode), Some(progress)) => write!(f, " — {mode}: {progress}"),
+            (Some(mode), None) => write!(f, " — {mode}"),
+            (None, Some(progress)) => write!(f, " — {progress}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Statistics on a finished game
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Stats {
+    /// The number of letters guessed
+    pub(crate) guesses: usize,
+    /// The number of incorrect guesses
+    pub(crate) misses: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Guesses: {}    Misses: {}", self.guesses, self.misses)
+    }
+}
+
+/// The time spent on the current word and the user's progress on it, shown
+/// at the bottom of the screen with `--status-bar`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Status {
+    pub(crate) clock: Clock,
+    /// The number of letters guessed
+    pub(crate) guesses: usize,
+    /// The number of incorrect guesses that the user can still make, or
+    /// `None` if the game cannot be lost
+    pub(crate) misses_remaining: Option<usize>,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.clock.elapsed().as_secs();
+        write!(
+            f,
+            "Time: {}:{:02}    Guesses: {}",
+            secs / 60,
+            secs % 60,
+            self.guesses
+        )?;
+        if let Some(n) = self.misses_remaining {
+            write!(f, "    Misses left: {n}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A deadline by which the user must make their next guess with `--timer`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Countdown {
+    deadline: Instant,
+    /// The total time allowed for the guess
+    limit: Duration,
+}
+
+impl Countdown {
+    /// How often the countdown bar is redrawn
+    const FRAME: Duration = Duration::from_millis(100);
+
+    /// Start counting down `limit` from now
+    pub(crate) fn start(limit: Duration) -> Countdown {
+        Countdown {
+            deadline: Instant::now() + limit,
+            limit,
+        }
+    }
+
+    fn remaining(self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// Push back the deadline by `delay`, so that the time the game was
+    /// paused for isn't counted
+    pub(crate) fn postpone(&mut self, delay: Duration) {
+        self.deadline += delay;
+    }
+
+    fn is_expired(self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Returns a bar `width` columns wide showing how much of the time limit
+    /// is left when `remaining` time is left, followed by the number of
+    /// seconds left (rounded up)
+    fn bar(self, remaining: Duration, width: usize) -> String {
+        let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let label = format!(" {secs}s");
+        let cells = width.saturating_sub(label.len());
+        let limit = self.limit.as_millis().max(1);
+        let filled = usize::try_from((remaining.as_millis() * cells as u128).div_ceil(limit))
+            .unwrap_or(cells)
+            .min(cells);
+        format!(
+            "{}{}{label}",
+            "█".repeat(filled),
+            "░".repeat(cells - filled)
+        )
+    }
+}
+
+/// A clock measuring how long the user has spent on a word
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Clock {
+    /// The clock started at the given time and is still running
+    Running(Instant),
+    /// The clock was stopped after the given amount of time
+    Stopped(Duration),
+}
+
+impl Clock {
+    pub(crate) fn start() -> Clock {
+        Clock::Running(Instant::now())
+    }
+
+    pub(crate) fn stop(&mut self) {
+        *self = Clock::Stopped(self.elapsed());
+    }
+
+    /// If the clock is running, push back its start by `delay`, so that the
+    /// time the game was paused for isn't counted
+    pub(crate) fn postpone(&mut self, delay: Duration) {
+        if let Clock::Running(start) = self {
+            *start += delay;
+        }
+    }
+
+    pub(crate) fn elapsed(self) -> Duration {
+        match self {
+            Clock::Running(start) => start.elapsed(),
+            Clock::Stopped(elapsed) => elapsed,
+        }
+    }
+
+    /// If the clock is running, returns the time elapsed so far
+    fn running(self) -> Option<Duration> {
+        match self {
+            Clock::Running(start) => Some(start.elapsed()),
+            Clock::Stopped(_) => None,
+        }
+    }
+}
+
+/// The numbers of correct & incorrect guesses made by a player
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Credit {
+    pub(crate) right: usize,
+    pub(crate) wrong: usize,
+}
+
+impl Credit {
+    /// Tally the guesses made by `player` in `history`
+    pub(crate) fn for_player(history: &[Guess], player: usize) -> Credit {
+        let mut credit = Credit::default();
+        for guess in history.iter().filter(|g| g.player == player) {
+            if guess.is_hit() {
+                credit.right += 1;
+            } else {
+                credit.wrong += 1;
+            }
+        }
+        credit
+    }
+}
+
+/// The figure drawn to the left of the letter grid
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Figure {
+    /// The classic gallows, in the given state
+    Gallows(Gallows),
+    /// A friendly dancing figure for kids mode, which cycles through a short
+    /// animation as incorrect guesses (counted by the field) are made
+    Dancer(usize),
+    /// An alternative to the gallows, drawn in the stage matching the given
+    /// gallows state
+    Scene(Art, Gallows),
+    /// The gallows drawn as an image with a terminal graphics protocol
+    Image(Graphics, Gallows),
+}
+
+impl Figure {
+    /// Returns the figure as it was before the latest body part was added,
+    /// or the figure unchanged if it has no body parts or isn't a gallows
+    fn previous(self) -> Figure {
+        match self {
+            Figure::Gallows(g) => Figure::Gallows(g.pred().unwrap_or(g)),
+            Figure::Scene(art, g) => Figure::Scene(art, g.pred().unwrap_or(g)),
+            Figure::Image(graphics, g) => Figure::Image(graphics, g.pred().unwrap_or(g)),
+            Figure::Dancer(misses) => Figure::Dancer(misses),
+        }
+    }
+}
+
+/// A letter in the grid of letters to guess
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum GuessOption {
+    /// A letter that has not been guessed yet
+    Open(char),
+    /// A letter that has already been guessed
+    Guessed(char),
+}
+
+impl GuessOption {
+    fn letter(self) -> char {
+        match self {
+            GuessOption::Open(ch) | GuessOption::Guessed(ch) => ch,
+        }
+    }
+}
+
+/// The arrangement of the parts of the game display, as set in the `layout`
+/// table of the configuration file
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Arrangement {
+    /// Whether to draw the figure to the right of the letter grid rather
+    /// than the left (and, in a duel, to swap the two figures)
+    pub(crate) mirror: bool,
+    /// Where to show the hint & category
+    pub(crate) hint: HintPlacement,
+    /// Where to show the secret word
+    pub(crate) word: WordPlacement,
+    /// The parts of the display to leave out
+    pub(crate) hide: Hidden,
+}
+
+/// A part of the game display that can be hidden with `--hide` or the `hide`
+/// key of the `layout` table
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Panel {
+    /// The hint, category, and part of speech
+    Hint,
+    /// The gallows (or other figure), including the computer's in a duel
+    Gallows,
+    /// The grid of letters to guess
+    Letters,
+    /// The message about the last guess
+    Message,
+    /// The sidebar listing the guesses made so far
+    History,
+}
+
+impl std::str::FromStr for Panel {
+    type Err = ParsePanelError;
+
+    fn from_str(s: &str) -> Result<Panel, ParsePanelError> {
+        match s {
+            "hint" => Ok(Panel::Hint),
+            "gallows" => Ok(Panel::Gallows),
+            "letters" => Ok(Panel::Letters),
+            "message" => Ok(Panel::Message),
+            "history" => Ok(Panel::History),
+            _ => Err(ParsePanelError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"panel must be "hint", "gallows", "letters", "message", or "history""#)]
+pub(crate) struct ParsePanelError;
+
+/// The set of parts of the game display that are hidden, given in the
+/// configuration file as a list of [`Panel`] names
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(from = "Vec<Panel>")]
+pub(crate) struct Hidden {
+    hint: bool,
+    gallows: bool,
+    letters: bool,
+    message: bool,
+    history: bool,
+}
+
+impl Hidden {
+    pub(crate) fn insert(&mut self, panel: Panel) {
+        match panel {
+            Panel::Hint => self.hint = true,
+            Panel::Gallows => self.gallows = true,
+            Panel::Letters => self.letters = true,
+            Panel::Message => self.message = true,
+            Panel::History => self.history = true,
+        }
+    }
+
+    /// Returns the panels hidden in either `self` or `other`
+    pub(crate) fn union(self, other: Hidden) -> Hidden {
+        Hidden {
+            hint: self.hint || other.hint,
+            gallows: self.gallows || other.gallows,
+            letters: self.letters || other.letters,
+            message: self.message || other.message,
+            history: self.history || other.history,
+        }
+    }
+}
+
+impl From<Vec<Panel>> for Hidden {
+    fn from(panels: Vec<Panel>) -> Hidden {
+        let mut hidden = Hidden::default();
+        for panel in panels {
+            hidden.insert(panel);
+        }
+        hidden
+    }
+}
+
+/// Where the hint & category are shown
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum HintPlacement {
+    /// Above the figure
+    #[default]
+    Top,
+    /// Beneath the message
+    Bottom,
+}
+
+/// Where the secret word is shown
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum WordPlacement {
+    /// Above the figure & letter grid
+    Above,
+    /// Beneath the figure & letter grid
+    #[default]
+    Below,
+}
+
+/// The arrangement of the letters in the letter grid, as set with
+/// `--letter-layout`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum LetterLayout {
+    /// Alphabetical order, in rows of six
+    #[default]
+    Alphabetical,
+    /// The rows of a QWERTY keyboard
+    Qwerty,
+    /// The rows of an AZERTY keyboard
+    Azerty,
+}
+
+impl LetterLayout {
+    /// Returns the rows of letter keys on the keyboard, or `None` for the
+    /// alphabetical layout
+    fn keyboard(self) -> Option<[&'static str; 3]> {
+        match self {
+            LetterLayout::Alphabetical => None,
+            LetterLayout::Qwerty => Some(["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"]),
+            LetterLayout::Azerty => Some(["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"]),
+        }
+    }
+
+    /// Arrange `options` into rows of at most `columns` letters, each paired
+    /// with the number of columns by which it's indented.  In a keyboard
+    /// layout, each row of keys is indented one column further than the one
+    /// above it, as on a keyboard, and any letters that aren't on the keyboard
+    /// follow in their original order.
+    fn arrange(self, options: &[GuessOption], columns: usize) -> Vec<(usize, Vec<GuessOption>)> {
+        let Some(keyboard) = self.keyboard() else {
+            return options
+                .chunks(columns)
+                .map(|row| (0, row.to_vec()))
+                .collect();
+        };
+        let mut rows = Vec::new();
+        for (indent, keys) in keyboard.into_iter().enumerate() {
+            let row = keys
+                .chars()
+                .filter_map(|key| options.iter().find(|opt| opt.letter() == key).copied())
+                .collect::<Vec<_>>();
+            if !row.is_empty() {
+                rows.push((indent, row));
+            }
+        }
+        let rest = options
+            .iter()
+            .filter(|opt| !keyboard.iter().any(|keys| keys.contains(opt.letter())))
+            .copied()
+            .collect::<Vec<_>>();
+        rows.extend(rest.chunks(columns).map(|row| (0, row.to_vec())));
+        rows
+    }
+}
+
+impl std::str::FromStr for LetterLayout {
+    type Err = ParseLetterLayoutError;
+
+    fn from_str(s: &str) -> Result<LetterLayout, ParseLetterLayoutError> {
+        match s {
+            "alphabetical" => Ok(LetterLayout::Alphabetical),
+            "qwerty" => Ok(LetterLayout::Qwerty),
+            "azerty" => Ok(LetterLayout::Azerty),
+            _ => Err(ParseLetterLayoutError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"letter layout must be "alphabetical", "qwerty", or "azerty""#)]
+pub(crate) struct ParseLetterLayoutError;
+
+/// How already-guessed letters are drawn in the letter grid, as set with
+/// `--guessed-letters`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum GuessedLetters {
+    /// Draw the letters dimmed
+    #[default]
+    Dim,
+    /// Draw the letters struck through
+    Strike,
+    /// Leave gaps where the letters were
+    Blank,
+}
+
+impl GuessedLetters {
+    /// Returns the style with which to draw guessed letters, or `None` if
+    /// they're not drawn
+    fn style(self) -> Option<Style> {
+        match self {
+            GuessedLetters::Dim => Some(Style::new().add_modifier(Modifier::DIM)),
+            GuessedLetters::Strike => Some(Style::new().add_modifier(Modifier::CROSSED_OUT)),
+            GuessedLetters::Blank => None,
+        }
+    }
+}
+
+impl std::str::FromStr for GuessedLetters {
+    type Err = ParseGuessedLettersError;
+
+    fn from_str(s: &str) -> Result<GuessedLetters, ParseGuessedLettersError> {
+        match s {
+            "dim" => Ok(GuessedLetters::Dim),
+            "strike" => Ok(GuessedLetters::Strike),
+            "blank" => Ok(GuessedLetters::Blank),
+            _ => Err(ParseGuessedLettersError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"guessed letters setting must be "dim", "strike", or "blank""#)]
+pub(crate) struct ParseGuessedLettersError;
+
+/// The art drawn to show how many incorrect guesses have been made, as set
+/// with `--art`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Art {
+    /// The classic gallows
+    #[default]
+    Gallows,
+    /// A snowman that melts into a puddle
+    Snowman,
+    /// A flower that wilts and drops its petals
+    Flower,
+    /// A sailboat that sinks beneath the waves
+    Boat,
+    /// The gallows drawn with emoji
+    Emoji,
+    /// The gallows drawn finely with Braille characters, scaled up to fit
+    /// taller terminals
+    Braille,
+}
+
+impl Art {
+    /// Returns the figure showing this art in the stage matching `gallows`
+    pub(crate) fn figure(self, gallows: Gallows) -> Figure {
+        match self {
+            Art::Gallows => Figure::Gallows(gallows),
+            art => Figure::Scene(art, gallows),
+        }
+    }
+}
+
+impl std::str::FromStr for Art {
+    type Err = ParseArtError;
+
+    fn from_str(s: &str) -> Result<Art, ParseArtError> {
+        match s {
+            "gallows" => Ok(Art::Gallows),
+            "snowman" => Ok(Art::Snowman),
+            "flower" => Ok(Art::Flower),
+            "boat" => Ok(Art::Boat),
+            "emoji" => Ok(Art::Emoji),
+            "braille" => Ok(Art::Braille),
+            _ => Err(ParseArtError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"art must be "gallows", "snowman", "flower", "boat", "emoji", or "braille""#)]
+pub(crate) struct ParseArtError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CharDisplay {
+    Plain(char),
+    Highlighted(char),
+    Blank,
+}
+
+impl CharDisplay {
+    /// The number of rows in a large letter
+    const BIG_HEIGHT: usize = 3;
+    /// The number of columns in a large letter
+    const BIG_WIDTH: usize = 3;
+
+    #[rustfmt::skip]
+    const BIG_LETTERS: [[&'static str; CharDisplay::BIG_HEIGHT]; 26] = [
+        ["┏━┓", "┣━┫", "╹ ╹"],
+        ["┏┓ ", "┣┻┓", "┗━┛"],
+        ["┏━╸", "┃  ", "┗━╸"],
+        ["┳━┓", "┃ ┃", "┻━┛"],
+        ["┏━╸", "┣╸ ", "┗━╸"],
+        ["┏━╸", "┣╸ ", "╹  "],
+        ["┏━╸", "┃╺┓", "┗━┛"],
+        ["╻ ╻", "┣━┫", "╹ ╹"],
+        ["╺┳╸", " ┃ ", "╺┻╸"],
+        ["  ╻", "  ┃", "┗━┛"],
+        ["╻┏╸", "┣┻┓", "╹ ╹"],
+        ["╻  ", "┃  ", "┗━╸"],
+        ["┏┳┓", "┃┃┃", "╹ ╹"],
+        ["┏┓╻", "┃┗┫", "╹ ╹"],
+        ["┏━┓", "┃ ┃", "┗━┛"],
+        ["┏━┓", "┣━┛", "╹  "],
+        ["┏━┓", "┃┏┫", "┗┻┛"],
+        ["┏━┓", "┣┳┛", "╹┗╸"],
+        ["┏━┓", "┗━┓", "┗━┛"],
+        ["╺┳╸", " ┃ ", " ╹ "],
+        ["╻ ╻", "┃ ┃", "┗━┛"],
+        ["╻ ╻", "┃┏┛", "┗┛ "],
+        ["╻ ╻", "┃╻┃", "┗┻┛"],
+        ["╻ ╻", "┏╋┛", "╹ ╹"],
+        ["╻ ╻", "┗┳┛", " ╹ "],
+        ["╺━┓", "┏━┛", "┗━╸"],
+    ];
+
+    fn span(self, theme: &Theme, blank: Placeholder) -> Span<'static> {
+        match self {
+            CharDisplay::Plain(ch) => Span::raw(ch.to_string()),
+            CharDisplay::Highlighted(ch) => Span::styled(ch.to_string(), theme.revealed),
+            CharDisplay::Blank => Span::raw(blank.0.to_string()),
+        }
+    }
+
+    /// Returns the given row of the character drawn as a large letter.
+    /// Characters without a large form are drawn at normal size in the
+    /// middle row.
+    fn big_span(self, row: usize, theme: &Theme) -> Span<'static> {
+        match self {
+            CharDisplay::Plain(ch) => Span::raw(CharDisplay::big_char(ch, row)),
+            CharDisplay::Highlighted(ch) => {
+                Span::styled(CharDisplay::big_char(ch, row), theme.revealed)
+            }
+            CharDisplay::Blank if row + 1 == CharDisplay::BIG_HEIGHT => Span::raw("╺━╸"),
+            CharDisplay::Blank => Span::raw(" ".repeat(CharDisplay::BIG_WIDTH)),
+        }
+    }
+
+    /// Returns the given row of `ch` drawn as a large letter
+    fn big_char(ch: char, row: usize) -> String {
+        if let Some(index) = ('A'..='Z').position(|c| c == ch.to_ascii_uppercase()) {
+            CharDisplay::BIG_LETTERS[index][row].to_owned()
+        } else if row == CharDisplay::BIG_HEIGHT / 2 {
+            format!(" {ch} ")
+        } else {
+            " ".repeat(CharDisplay::BIG_WIDTH)
+        }
+    }
+}
+
+/// The character drawn in place of each unrevealed character of the secret
+/// word at normal size, as set with `--blank`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Placeholder(char);
+
+impl Placeholder {
+    /// Returns the number of columns that the placeholder takes up
+    fn width(self) -> usize {
+        Span::raw(self.0.to_string()).width()
+    }
+}
+
+impl Default for Placeholder {
+    fn default() -> Placeholder {
+        Placeholder('_')
+    }
+}
+
+impl std::str::FromStr for Placeholder {
+    type Err = ParsePlaceholderError;
+
+    fn from_str(s: &str) -> Result<Placeholder, ParsePlaceholderError> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) if matches!(Span::raw(s).width(), 1 | 2) => Ok(Placeholder(ch)),
+            _ => Err(ParsePlaceholderError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("blank placeholder must be a single printable character")]
+pub(crate) struct ParsePlaceholderError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Message {
+    Start,
+    StartTruncated {
+        max: usize,
+    },
+    GoodGuess {
+        guess: char,
+        count: usize,
+    },
+    BadGuess {
+        guess: char,
+    },
+    TryAgain {
+        guess: char,
+    },
+    AlreadyGuessed {
+        guess: char,
+    },
+    InvalidGuess {
+        guess: char,
+    },
+    /// The user guessed the whole word and got it wrong
+    WrongWord,
+    Won,
+    Lost,
+    /// The user solved a word in a sequence, and another will follow
+    Solved,
+    /// The user won a round of a match, earning the given number of points
+    RoundWon {
+        points: usize,
+    },
+    /// The user lost a round of a match
+    RoundLost,
+    /// A round of a duel in which both sides guessed without ending the game
+    DuelRound {
+        yours: Guess,
+        theirs: Guess,
+    },
+    /// The computer revealed the final letter in a duel
+    ComputerWon {
+        guess: char,
+    },
+    /// The computer completed its own gallows in a duel
+    ComputerHanged {
+        guess: char,
+    },
+    /// The user ran out of time to make a guess, which counted as a miss
+    TimeUp,
+    /// In reverse mode, prompt the user for the length of their word
+    ReverseStart,
+    ReverseBadLength {
+        max: usize,
+    },
+    /// In reverse mode, ask the user where the computer's guess occurs in
+    /// their word
+    ReverseAsk {
+        guess: char,
+    },
+    ReverseBadAnswer {
+        guess: char,
+        error: AnswerError,
+    },
+    ReverseNotNumbers {
+        guess: char,
+    },
+    /// The computer filled in the user's whole word
+    ReverseSolved,
+    /// The computer completed its gallows in reverse mode
+    ReverseHanged,
+    /// The computer ran out of letters to guess in reverse mode
+    ReverseStumped,
+}
+
+impl Message {
+    /// Returns the text of the banner drawn in large letters when the
+    /// message announces the end of a game
+    fn banner(&self) -> Option<&'static str> {
+        match self {
+            Message::Won
+            | Message::ComputerHanged { .. }
+            | Message::ReverseHanged
+            | Message::ReverseStumped => Some("YOU WIN"),
+            Message::Lost | Message::ComputerWon { .. } | Message::ReverseSolved => {
+                Some("GAME OVER")
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the text of the message in the phrasing numbered `flavor`,
+    /// counting modulo the number of phrasings that the message has.
+    /// Phrasing 0 is the one produced by the `Display` implementation, which
+    /// is also the only phrasing of most messages.
+    pub(crate) fn text(&self, flavor: usize) -> String {
+        match *self {
+            Message::GoodGuess { guess, count } => match flavor % 3 {
+                1 if count == 1 => format!("Nice!  The word has 1 {guess:?}."),
+                1 => format!("Nice!  The word has {count} {guess:?}s."),
+                2 if count == 1 => format!("Yes!  {guess:?} appears once in the word."),
+                2 => format!("Yes!  {guess:?} appears {count} times in the word."),
+                _ => self.to_string(),
+            },
+            Message::BadGuess { guess } => match flavor % 3 {
+                1 => format!("Nope, no {guess:?} in this one."),
+                2 => format!("Sorry, {guess:?} isn't in the word."),
+                _ => self.to_string(),
+            },
+            Message::TryAgain { guess } => match flavor % 2 {
+                1 => format!("Almost!  There's no {guess:?}.  Try another letter!"),
+                _ => self.to_string(),
+            },
+            Message::AlreadyGuessed { guess } => match flavor % 2 {
+                1 => format!("{guess:?} has been guessed already."),
+                _ => self.to_string(),
+            },
+            Message::Won => match flavor % 3 {
+                1 => String::from("Well done, you got it!"),
+                2 => String::from("Nicely solved!"),
+                _ => self.to_string(),
+            },
+            Message::Lost => match flavor % 3 {
+                1 => String::from("Oh no, you're out of guesses!"),
+                2 => String::from("Game over.  Better luck next time!"),
+                _ => self.to_string(),
+            },
+            _ => self.to_string(),
+        }
+    }
+
+    /// Returns true iff the message announces the end of a game
+    pub(crate) fn is_game_over(&self) -> bool {
+        matches!(
+            self,
+            Message::Won
+                | Message::Lost
+                | Message::ComputerWon { .. }
+                | Message::ComputerHanged { .. }
+                | Message::ReverseSolved
+                | Message::ReverseHanged
+                | Message::ReverseStumped
+        )
+    }
+
+    fn gallows_advanced(&self) -> bool {
+        match self {
+            Message::BadGuess { .. }
+            | Message::WrongWord
+            | Message::TimeUp
+            | Message::Lost
+            | Message::RoundLost
+            | Message::ReverseHanged => true,
+            Message::DuelRound { yours, .. } => !yours.is_hit(),
+            _ => false,
+        }
+    }
+
+    fn opponent_gallows_advanced(&self) -> bool {
+        match self {
+            Message::DuelRound { theirs, .. } => !theirs.is_hit(),
+            Message::ComputerHanged { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Start => write!(f, "Try to guess the secret word!"),
+            Message::StartTruncated { max } => {
+                write!(f, "Guess the secret word (cut to {max} characters)!")
+            }
+            Message::GoodGuess { guess, count } => {
+                write!(f, "Correct!  There ")?;
+                if *count == 1 {
+                    write!(f, "is 1 {guess:?} ")?;
+                } else {
+                    write!(f, "are {count} {guess:?}s ")?;
+                }
+                write!(f, "in the word.")?;
+                Ok(())
+            }
+            Message::BadGuess { guess, .. } => {
+                write!(f, "Wrong!  There's no {guess:?} in the word.")
+            }
+            Message::TryAgain { guess } => {
+                write!(f, "Not quite!  No {guess:?}.  Keep trying!")
+            }
+            Message::AlreadyGuessed { guess } => {
+                write!(f, "You already guessed {guess:?}.")
+            }
+            Message::InvalidGuess { guess } => {
+                write!(f, "{guess:?} is not an option.")
+            }
+            Message::WrongWord => write!(f, "Wrong!  That's not the word."),
+            Message::TimeUp => write!(f, "Time's up!  That counts as a miss."),
+            Message::Won => write!(f, "You win!"),
+            Message::Solved => write!(f, "Solved!  Press any key for the next word."),
+            Message::RoundWon { points } => {
+                write!(f, "You win this round (+{points} points)!")
+            }
+            Message::RoundLost => write!(f, "Oh dear, you are dead!  No points this round."),
+            Message::Lost => write!(f, "Oh dear, you are dead!"),
+            Message::DuelRound { yours, theirs } => {
+                write!(
+                    f,
+                    "You: {:?} {}.  Computer: {:?} {}.",
+                    yours.letter,
+                    if yours.is_hit() { "hit" } else { "missed" },
+                    theirs.letter,
+                    if theirs.is_hit() { "hit" } else { "missed" },
+                )
+            }
+            Message::ComputerWon { guess } => {
+                write!(f, "The computer guessed {guess:?} and wins!")
+            }
+            Message::ComputerHanged { guess } => {
+                write!(
+                    f,
+                    "The computer missed with {guess:?} and hanged.  You win!"
+                )
+            }
+            Message::ReverseStart => {
+                write!(f, "Think of a word.  How many letters does it have?")
+            }
+            Message::ReverseBadLength { max } => {
+                write!(f, "Please enter a number of letters from 1 to {max}.")
+            }
+            Message::ReverseAsk { guess } => write!(
+                f,
+                "Where is {guess:?} in your word?  Type positions, or just press Enter if nowhere."
+            ),
+            Message::ReverseBadAnswer { guess, error } => {
+                write!(f, "Sorry, {error}.  Where is {guess:?} in your word?")
+            }
+            Message::ReverseNotNumbers { guess } => {
+                write!(
+                    f,
+                    "Positions must be numbers.  Where is {guess:?} in your word?"
+                )
+            }
+            Message::ReverseSolved => write!(f, "I guessed your word!"),
+            Message::ReverseHanged => write!(f, "Oh dear, I am dead!  You win!"),
+            Message::ReverseStumped => write!(f, "I give up!  You win!"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Modifier, Style};
+    use unicode_width::UnicodeWidthStr;
+
+    /// The size of the terminal that pages are laid out for in tests
+    const SCREEN: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 80,
+        height: 24,
+    };
+
+    /// Returns the lines of `page` as strings, with styled text marked by the
+    /// ANSI escape sequences for its style
+    fn ansi(page: Page) -> Vec<String> {
+        page.board
+            .into_iter()
+            .chain(page.text)
+            .map(|line| {
+                let mut s = String::new();
+                for span in line.spans {
+                    let mut codes = Vec::new();
+                    if span.style.add_modifier.contains(Modifier::BOLD) {
+                        codes.push("1");
+                    }
+                    if span.style.add_modifier.contains(Modifier::DIM) {
+                        codes.push("2");
+                    }
+                    if span.style.add_modifier.contains(Modifier::UNDERLINED) {
+                        codes.push("4");
+                    }
+                    if span.style.add_modifier.contains(Modifier::REVERSED) {
+                        codes.push("7");
+                    }
+                    if span.style.add_modifier.contains(Modifier::CROSSED_OUT) {
+                        codes.push("9");
+                    }
+                    if span.style.fg == Some(Color::Red) {
+                        codes.push("31");
+                    }
+                    if codes.is_empty() {
+                        s.push_str(&span.content);
+                    } else {
+                        write!(s, "\x1B[{}m{}\x1B[m", codes.join(";"), span.content).unwrap();
+                    }
+                }
+                s
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_color_choice() {
+        let set = Some(OsStr::new("1"));
+        let empty = Some(OsStr::new(""));
+        assert!(ColorChoice::Auto.enabled(None, true));
+        assert!(ColorChoice::Auto.enabled(empty, true));
+        assert!(!ColorChoice::Auto.enabled(set, true));
+        assert!(!ColorChoice::Auto.enabled(None, false));
+        assert!(ColorChoice::Always.enabled(set, false));
+        assert!(!ColorChoice::Never.enabled(None, true));
+        assert_eq!("never".parse::<ColorChoice>(), Ok(ColorChoice::Never));
+        assert_eq!(
+            "sometimes".parse::<ColorChoice>(),
+            Err(ParseColorChoiceError)
+        );
+    }
+
+    #[test]
+    fn test_gallows_widths() {
+        for gallows in std::iter::successors(Some(Gallows::Start), |&g| g.succ()) {
+            for highlight in [false, true] {
+                for line in Content::draw_gallows(gallows, highlight, &Theme::default()) {
+                    assert_eq!(line.width(), Content::GALLOWS_WIDTH);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scene_widths() {
+        for art in [
+            Art::Snowman,
+            Art::Flower,
+            Art::Boat,
+            Art::Emoji,
+            Art::Braille,
+        ] {
+            for gallows in std::iter::successors(Some(Gallows::Start), |&g| g.succ()) {
+                for highlight in [false, true] {
+                    let lines = Content::draw_scene(art, gallows, highlight, &Theme::default());
+                    assert_eq!(lines.len(), Content::GALLOWS_HEIGHT);
+                    for line in lines {
+                        assert_eq!(line.width(), Content::GALLOWS_WIDTH, "{art:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_braille_scaling() {
+        assert_eq!(Content::figure_height(0), Content::GALLOWS_HEIGHT);
+        assert_eq!(Content::figure_height(24), 14);
+        assert_eq!(Content::figure_height(100), Content::MAX_FIGURE_HEIGHT);
+        for height in Content::GALLOWS_HEIGHT..=Content::MAX_FIGURE_HEIGHT {
+            for gallows in [Gallows::Start, Gallows::AddRightLeg] {
+                let lines = Content::draw_braille(gallows, true, &Theme::default(), height);
+                assert_eq!(lines.len(), height);
+                let width = (height * 8 + 2) / 5;
+                for line in lines {
+                    assert_eq!(line.width(), width, "height {height}");
+                }
+            }
+        }
+        let lines = ansi(Page {
+            width: 0,
+            board: Content::draw_braille(Gallows::AddHead, true, &Theme::default(), 10),
+            text: Vec::new(),
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        });
+        assert_eq!(
+            lines,
+            [
+                " ⢰⠒⠒⡲⠒⠒⠒⠒⠒⠒⢲    ",
+                " ⢸⡠⠊       \x1B[1;31m⢸⡀\x1B[m   ",
+                " ⢸       \x1B[1;31m⢰⠉⠁⠘⢄\x1B[m  ",
+                " ⢸        \x1B[1;31m⢇⣀⠤⠊\x1B[m  ",
+                " ⢸              ",
+                " ⢸              ",
+                " ⢸              ",
+                " ⢸              ",
+                " ⢸              ",
+                "⠒⠚⠒⠒⠒⠒⠒⠒⠒⠒      ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scene_highlight() {
+        let lines = ansi(Page {
+            width: Content::GALLOWS_WIDTH,
+            board: Content::draw_scene(Art::Snowman, Gallows::AddLeftArm, true, &Theme::default()),
+            text: Vec::new(),
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        });
+        assert_eq!(
+            lines,
+            [
+                "        ",
+                "  (\x1B[1;31m;\x1B[m.\x1B[1;31m;\x1B[m) ",
+                "  ( : ) ",
+                "  ( : ) ",
+                "────────",
+            ]
+        );
+        let lines = ansi(Page {
+            width: Content::GALLOWS_WIDTH,
+            board: Content::draw_emoji(Gallows::AddTorso, true, &Theme::default()),
+            text: Vec::new(),
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        });
+        assert_eq!(
+            lines,
+            [
+                "\u{1F7EB}\u{1F7EB}\u{1F7EB}  ",
+                "\u{1F7EB}  \u{1F635}  ",
+                "\u{1F7EB}  \x1B[1;31m\u{1F455}\x1B[m  ",
+                "\u{1F7EB}      ",
+                "\u{1F7EB}\u{1F7EB}\u{1F7EB}\u{1F7EB}",
+            ]
+        );
+        assert_eq!(
+            Art::Gallows.figure(Gallows::AddHead),
+            Figure::Gallows(Gallows::AddHead)
+        );
+        assert_eq!(
+            Art::Boat.figure(Gallows::AddHead),
+            Figure::Scene(Art::Boat, Gallows::AddHead)
+        );
+        assert_eq!("flower".parse::<Art>(), Ok(Art::Flower));
+        assert_eq!("noose".parse::<Art>(), Err(ParseArtError));
+    }
+
+    #[test]
+    fn test_page_layout() {
+        let page = Page {
+            width: 10,
+            board: vec![Line::from("0123456789ABCDEF")],
+            text: vec![Line::from("the quick brown fox")],
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        };
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(14, 6)).unwrap();
+        terminal.draw(|frame| page.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows = (0..6)
+            .map(|y| {
+                (0..14)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                "              ",
+                "              ",
+                "  0123456789AB",
+                "  the quick   ",
+                "  brown fox   ",
+                "              ",
+            ]
+        );
+    }
+
+    /// A writer whose output can be inspected while a terminal owns it
+    #[derive(Clone, Debug, Default)]
+    struct SharedBuffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn take(&self) -> String {
+            String::from_utf8(self.0.take()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_redraw_writes_changes() {
+        let output = SharedBuffer::default();
+        let mut terminal = Terminal::with_options(
+            CrosstermBackend::new(output.clone()),
+            ratatui::TerminalOptions {
+                viewport: ratatui::Viewport::Fixed(Rect::new(0, 0, 20, 5)),
+            },
+        )
+        .unwrap();
+        let mut page = Page {
+            width: 11,
+            board: vec![Line::from("_ _ _ _ _ _")],
+            text: vec![Line::from("Try to guess the secret word!")],
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        };
+        terminal.draw(|frame| page.render(frame)).unwrap();
+        assert!(strip_escapes(&output.take()).contains("secret"));
+        page.board = vec![Line::from("_ _ _ _ _ X")];
+        terminal.draw(|frame| page.render(frame)).unwrap();
+        let redrawn = strip_escapes(&output.take());
+        assert_eq!(redrawn, "X");
+    }
+
+    #[test]
+    fn test_clipped_style_does_not_leak() {
+        let output = SharedBuffer::default();
+        let mut terminal = Terminal::with_options(
+            CrosstermBackend::new(output.clone()),
+            ratatui::TerminalOptions {
+                viewport: ratatui::Viewport::Fixed(Rect::new(0, 0, 8, 2)),
+            },
+        )
+        .unwrap();
+        let bold_red = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
+        let page = Page {
+            width: 8,
+            board: vec![
+                Line::from(vec![Span::raw("abcdef"), Span::styled("GHIJ", bold_red)]),
+                Line::from("plain"),
+            ],
+            text: Vec::new(),
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        };
+        let buffer = terminal.draw(|frame| page.render(frame)).unwrap().buffer;
+        assert_eq!(buffer.get(6, 0).symbol(), "G");
+        assert_eq!(buffer.get(7, 0).symbol(), "H");
+        assert_eq!(buffer.get(7, 0).fg, Color::Red);
+        assert_eq!(buffer.get(7, 0).modifier, Modifier::BOLD);
+        for x in 0..8 {
+            assert_eq!(buffer.get(x, 1).fg, Color::Reset);
+            assert!(buffer.get(x, 1).modifier.is_empty());
+        }
+        // Bold & red are switched off before the next line is written
+        let out = output.take();
+        let between = &out[out.find("GH").unwrap()..out.find("plain").unwrap()];
+        assert!(between.contains("\x1B[22m"));
+        assert!(between.contains("\x1B[39m"));
+    }
+
+    /// Remove CSI escape sequences from `s`
+    fn strip_escapes(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1B' {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![
+                GuessOption::Open('A'),
+                GuessOption::Guessed('B'),
+                GuessOption::Open('C'),
+                GuessOption::Open('D'),
+            ],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: Some('C'),
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Plain('B')],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[2], "  ┌───┐     A   \x1B[7mC\x1B[m D");
+    }
+
+    #[test]
+    fn test_guessed_letters() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![
+                GuessOption::Open('A'),
+                GuessOption::Guessed('B'),
+                GuessOption::Open('C'),
+            ],
+            guessed: GuessedLetters::default(),
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(lines[2], "  ┌───┐     A \x1B[2mB\x1B[m C");
+        let strike = Content {
+            guessed: GuessedLetters::Strike,
+            ..content.clone()
+        };
+        let lines = ansi(strike.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[2], "  ┌───┐     A \x1B[9mB\x1B[m C");
+        let blank = Content {
+            guessed: GuessedLetters::Blank,
+            ..content
+        };
+        let lines = ansi(blank.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[2], "  ┌───┐     A   C");
+    }
+
+    #[test]
+    fn test_letter_layout() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: ('A'..='Z')
+                .map(|ch| {
+                    if ch == 'E' {
+                        GuessOption::Guessed(ch)
+                    } else {
+                        GuessOption::Open(ch)
+                    }
+                })
+                .collect(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Qwerty,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(
+            &lines[2..5],
+            [
+                "  ┌───┐     Q W   R T Y U I O P",
+                "  │          A S D F G H J K L",
+                "  │           Z X C V B N M",
+            ]
+        );
+        let azerty = Content {
+            letter_layout: LetterLayout::Azerty,
+            arrangement: Arrangement::default(),
+            ..content
+        };
+        let lines = ansi(azerty.render(&Theme::default(), SCREEN));
+        assert_eq!(
+            &lines[2..5],
+            [
+                "  ┌───┐     A Z   R T Y U I O P",
+                "  │          Q S D F G H J K L M",
+                "  │           W X C V B N",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_letter_columns() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: ('А'..='Я').chain(['Ё']).map(GuessOption::Open).collect(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(
+            &lines[2..8],
+            [
+                "  ┌───┐     А Б В Г Д Е Ж",
+                "  │         З И Й К Л М Н",
+                "  │         О П Р С Т У Ф",
+                "  │         Х Ц Ч Ш Щ Ъ Ы",
+                "──┴──       Ь Э Ю Я Ё",
+                "",
+            ]
+        );
+        let narrow = Rect::new(0, 0, 20, 24);
+        let lines = ansi(content.render(&Theme::default(), narrow));
+        assert_eq!(lines[2], "  ┌───┐     А Б В Г");
+    }
+
+    #[test]
+    fn test_letter_at() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![
+                GuessOption::Open('A'),
+                GuessOption::Guessed('B'),
+                GuessOption::Open('C'),
+            ],
+            guessed: GuessedLetters::Dim,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let page = content.clone().render(&Theme::default(), SCREEN);
+        assert!(!page.prompt);
+        let area = page.area(SCREEN);
+        let at = |column: u16, row: u16| page.letter_at(SCREEN, area.x + column, area.y + row);
+        assert_eq!(at(12, 2), Some('A'));
+        assert_eq!(at(13, 2), None);
+        assert_eq!(at(14, 2), None);
+        assert_eq!(at(16, 2), Some('C'));
+        assert_eq!(at(12, 3), None);
+        assert_eq!(page.letter_at(SCREEN, 0, 0), None);
+        let won = Content {
+            message: Message::Won,
+            ..content
+        };
+        assert!(won.render(&Theme::default(), SCREEN).prompt);
+    }
+
+    #[test]
+    fn test_hover() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Guessed('B')],
+            guessed: GuessedLetters::Dim,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let mut page = content.render(&Theme::default(), SCREEN);
+        let mut available = page.clone();
+        available.hover('A');
+        assert_eq!(
+            available.tooltip,
+            Some((
+                3,
+                12,
+                Line::styled(
+                    " A: available ",
+                    Style::new().add_modifier(Modifier::REVERSED)
+                )
+            ))
+        );
+        page.hover('B');
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| page.render(frame)).unwrap();
+        let area = page.area(SCREEN);
+        let buffer = terminal.backend().buffer();
+        let note = (0..20)
+            .map(|x| buffer.get(area.x + 14 + x, area.y + 3).symbol())
+            .collect::<String>();
+        assert_eq!(note, " B: already guessed ");
+        assert_eq!(ansi(page)[2], "  ┌───┐     A \x1B[2;4mB\x1B[m");
+    }
+
+    #[test]
+    fn test_pause_menu() {
+        let page = Page {
+            width: 5,
+            board: vec![Line::from("hello")],
+            text: vec![Line::styled("world", Style::new().fg(Color::Red))],
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: None,
+            command: None,
+            sidebar: Vec::new(),
+        };
+        let items = [MenuItem::Resume, MenuItem::Options, MenuItem::Quit];
+        let menu = page.with_menu(" Paused ", &items, 1);
+        assert_eq!(
+            ansi(menu.clone()),
+            ["\x1B[2mhello\x1B[m", "\x1B[2;31mworld\x1B[m"]
+        );
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(20, 8)).unwrap();
+        terminal.draw(|frame| menu.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows = (0..8)
+            .map(|y| {
+                (0..20)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows[1..6],
+            [
+                "    ┌ Paused ──┐    ",
+                "    │ Resume   │    ",
+                "    │ Options  │    ",
+                "    │ Quit     │    ",
+                "    └──────────┘    ",
+            ]
+        );
+        assert!(buffer.get(6, 3).modifier.contains(Modifier::REVERSED));
+        assert_eq!(MenuItem::NewWord.key(), 'n');
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            word_text(
+                &[
+                    CharDisplay::Plain('c'),
+                    CharDisplay::Blank,
+                    CharDisplay::Highlighted('t'),
+                ],
+                "*".parse().unwrap()
+            ),
+            "c * t"
+        );
+        assert_eq!(
+            word_speech(&[
+                CharDisplay::Blank,
+                CharDisplay::Plain('a'),
+                CharDisplay::Plain(' '),
+                CharDisplay::Blank,
+            ]),
+            "blank A space blank"
+        );
+        assert_eq!(
+            figure_text(Figure::Gallows(Gallows::AddHead)),
+            ["  ┌───┐", "  │   o", "  │", "  │", "──┴──"]
+        );
+    }
+
+    #[test]
+    fn test_title() {
+        let mut title = Title {
+            mode: None,
+            won: None,
+            misses_remaining: Some(4),
+        };
+        assert_eq!(title.to_string(), "hangman — 4 misses left");
+        title.misses_remaining = Some(1);
+        assert_eq!(title.to_string(), "hangman — 1 miss left");
+        title.won = Some(false);
+        assert_eq!(title.to_string(), "hangman — you lost");
+        title.mode = Some("duel");
+        title.won = Some(true);
+        assert_eq!(title.to_string(), "hangman — duel: you won!");
+        assert_eq!(
+            Title {
+                mode: Some("reverse"),
+                won: None,
+                misses_remaining: None,
+            }
+            .to_string(),
+            "hangman — reverse"
+        );
+        assert_eq!(Title::default().to_string(), "hangman");
+    }
+
+    #[test]
+    fn test_sidebar() {
+        let guess = |letter, count| Guess {
+            letter,
+            count,
+            player: 0,
+        };
+        let history = [
+            (guess('E', 0), Duration::from_secs(3)),
+            (guess('A', 2), Duration::from_secs(5)),
+            (guess('T', 1), Duration::from_secs(72)),
+        ];
+        let page = Page {
+            width: 5,
+            board: vec![Line::from("hello")],
+            sidebar: Content::sidebar(&history, &Theme::PLAIN),
+            ..Page::default()
+        };
+        let draw = |width: u16| {
+            let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, 3)).unwrap();
+            terminal.draw(|frame| page.render(frame)).unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            (0..3)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+        // Only the latest guesses that fit are listed
+        assert_eq!(
+            draw(30),
+            [
+                "                     Guesses  ",
+                "            hello    ✓ A  0:05",
+                "                     ✓ T  1:12",
+            ]
+        );
+        // The sidebar is left out if it doesn't fit beside the page
+        assert_eq!(
+            draw(29),
+            [
+                "                             ",
+                "            hello            ",
+                "                             ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_command_line() {
+        let page = Page {
+            board: vec![Line::from("hello")],
+            command: Some(String::from("theme vivid")),
+            ..Page::default()
+        };
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(16, 3)).unwrap();
+        terminal.draw(|frame| page.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows = (0..3)
+            .map(|y| {
+                (0..16)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            ["                ", "        hello   ", ":theme vivid    "]
+        );
+        assert_eq!(terminal.get_cursor().unwrap(), (12, 2));
+    }
+
+    #[test]
+    fn test_toast() {
+        let page = Page {
+            width: 5,
+            board: vec![Line::from("hello")],
+            text: vec![Line::from("world")],
+            images: Vec::new(),
+            letters: Vec::new(),
+            prompt: false,
+            tooltip: None,
+            overlay: None,
+            toast: Some(String::from("Assist mode on")),
+            command: None,
+            sidebar: Vec::new(),
+        };
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(40, 4)).unwrap();
+        terminal.draw(|frame| page.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows = (0..4)
+            .map(|y| {
+                (0..40)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                "                      ┌────────────────┐",
+                "                 hello│ Assist mode on │",
+                "                 world└────────────────┘",
+                "                                        ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_help() {
+        let help = Help {
+            keys: vec![
+                ("A-Z", "Guess a letter"),
+                ("Tab", "Turn assist mode on or off"),
+            ],
+            rules: vec![String::from("You lose after 6 incorrect guesses")],
+            source: Some(String::from("the builtin \"standard\" word list")),
+        };
+        assert_eq!(
+            ansi(Page {
+                board: help.lines(),
+                ..Page::default()
+            }),
+            [
+                "\x1B[1m Keys:\x1B[m",
+                "   A-Z   Guess a letter ",
+                "   Tab   Turn assist mode on or off ",
+                "",
+                "\x1B[1m Rules:\x1B[m",
+                "   You lose after 6 incorrect guesses ",
+                "",
+                " Word source: the builtin \"standard\" word list ",
+                "",
+                "\x1B[2m Press any key to return to the game. \x1B[m",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(Content::wrap_text("", 10, 3), [""]);
+        assert_eq!(
+            Content::wrap_text("Hint: a large gray animal", 12, 3),
+            ["Hint: a", "large gray", "animal"]
+        );
+        assert_eq!(
+            Content::wrap_text("Category: zoo    Hint: big", 16, 3),
+            ["Category: zoo", "Hint: big"]
+        );
+        assert_eq!(
+            Content::wrap_text("Hint: supercalifragilistic", 10, 3),
+            ["Hint:", "supercalif", "ragilistic"]
+        );
+        assert_eq!(
+            Content::wrap_text("one two three four five six", 9, 2),
+            ["one two", "three…"]
+        );
+    }
+
+    #[test]
+    fn test_long_hint() {
+        let content = Content {
+            hint: Some(String::from(
+                "An animal with a long trunk that lives in Africa and Asia",
+            )),
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let page = content.render(&Theme::default(), SCREEN);
+        let area = page.area(SCREEN);
+        assert_eq!(page.letter_at(SCREEN, area.x + 12, area.y + 3), Some('A'));
+        let lines = ansi(page);
+        assert_eq!(
+            &lines[..4],
+            [
+                "Hint: An animal with a long trunk that lives in",
+                "Africa and Asia",
+                "",
+                "  ┌───┐     A",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hidden_panels() {
+        let content = |hide| Content {
+            hint: Some(String::from("Fruit")),
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement {
+                hide,
+                ..Arrangement::default()
+            },
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: vec![(
+                Guess {
+                    letter: 'C',
+                    count: 0,
+                    player: 0,
+                },
+                Duration::from_secs(1),
+            )],
+            title: Title::default(),
+        };
+        let page = content(Hidden::from(vec![
+            Panel::Hint,
+            Panel::Gallows,
+            Panel::Message,
+            Panel::History,
+        ]))
+        .render(&Theme::default(), SCREEN);
+        assert_eq!(ansi(page.clone()), ["", "A B", "", "    _ _", "", "", ""]);
+        let area = page.area(SCREEN);
+        assert_eq!(page.letter_at(SCREEN, area.x + 2, area.y + 1), Some('B'));
+        assert!(page.sidebar.is_empty());
+        let page = content(Hidden::from(vec![Panel::Letters])).render(&Theme::default(), SCREEN);
+        assert!(page.letters.is_empty());
+        assert!(!page.sidebar.is_empty());
+        assert_eq!(
+            ansi(page),
+            [
+                "Hint: Fruit",
+                "",
+                "  ┌───┐ ",
+                "  │     ",
+                "  │     ",
+                "  │     ",
+                "──┴──   ",
+                "",
+                "  _ _",
+                "",
+                "Try to guess the secret word!",
+                "",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arrangement() {
+        let content = Content {
+            hint: Some(String::from("Fruit")),
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement {
+                mirror: true,
+                hint: HintPlacement::Bottom,
+                word: WordPlacement::Above,
+                hide: Hidden::default(),
+            },
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let page = content.render(&Theme::default(), SCREEN);
+        let area = page.area(SCREEN);
+        assert_eq!(page.letter_at(SCREEN, area.x, area.y + 4), Some('A'));
+        let lines = ansi(page);
+        assert_eq!(
+            lines,
+            [
+                "",
+                "",
+                "          _ _",
+                "",
+                "A                ┌───┐ ",
+                "                 │     ",
+                "                 │     ",
+                "                 │     ",
+                "               ──┴──   ",
+                "Try to guess the secret word!",
+                "",
+                "",
+                "Hint: Fruit",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_placeholder() {
+        assert_eq!("•".parse::<Placeholder>(), Ok(Placeholder('•')));
+        assert_eq!("＿".parse::<Placeholder>().map(Placeholder::width), Ok(2));
+        assert!("".parse::<Placeholder>().is_err());
+        assert!("__".parse::<Placeholder>().is_err());
+        assert!("\t".parse::<Placeholder>().is_err());
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![
+                CharDisplay::Plain('A'),
+                CharDisplay::Blank,
+                CharDisplay::Blank,
+            ],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder('＿'),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        // Each character takes up two columns, like the wide placeholder
+        assert_eq!(lines[8], "       A  ＿ ＿");
+    }
+
+    #[test]
+    fn test_flavor() {
+        let good = Message::GoodGuess {
+            guess: 'A',
+            count: 2,
+        };
+        assert_eq!(good.text(0), good.to_string());
+        assert_eq!(good.text(1), "Nice!  The word has 2 'A's.");
+        assert_eq!(good.text(2), "Yes!  'A' appears 2 times in the word.");
+        assert_eq!(good.text(3), good.to_string());
+        let good = Message::GoodGuess {
+            guess: 'T',
+            count: 1,
+        };
+        assert_eq!(good.text(2), "Yes!  'T' appears once in the word.");
+        assert_eq!(Message::Won.text(0), "You win!");
+        assert_eq!(Message::Lost.text(4), "Oh no, you're out of guesses!");
+        // Messages with only one wording ignore the flavor
+        assert_eq!(Message::Start.text(1), Message::Start.to_string());
+    }
+
+    #[test]
+    fn test_narrow_message() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Guessed('A')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Plain('A'), CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::GoodGuess {
+                guess: 'A',
+                count: 2,
+            },
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let narrow = Rect::new(0, 0, 40, 24);
+        let page = content.render(&Theme::default(), narrow);
+        // The page is centered with its wrapped height
+        let height = page.board.len() + page.text.len();
+        assert_eq!(
+            page.area(narrow).y,
+            u16::try_from((24 - height) / 2).unwrap()
+        );
+        let lines = ansi(page);
+        let i = lines
+            .iter()
+            .position(|ln| ln.starts_with("Correct!"))
+            .unwrap();
+        assert_eq!(lines[i], "Correct!  There are 2 'A's in");
+        assert_eq!(lines[i + 1], "the word.");
+    }
+
+    #[test]
+    fn test_wrap_word() {
+        let word = vec![CharDisplay::Blank; 50];
+        let lengths = |group| {
+            Content::wrap_word(&word, 80, 1, NonZeroUsize::new(group).unwrap())
+                .into_iter()
+                .map(<[CharDisplay]>::len)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lengths(1), [40, 10]);
+        assert_eq!(lengths(6), [36, 14]);
+        assert_eq!(lengths(50), [40, 10]);
+        assert_eq!(
+            Content::wrap_word(&word[..40], 80, 1, NonZeroUsize::MIN).len(),
+            1
+        );
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Plain('A'); 14],
+            wrap_group: NonZeroUsize::new(4).unwrap(),
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), Rect::new(0, 0, 20, 24)));
+        let i = lines.iter().position(|ln| ln.contains('A')).unwrap();
+        // Each line is centered on the board, which is a little wider than
+        // the terminal
+        assert_eq!(lines[i], "    A A A A A A A A");
+        assert_eq!(lines[i + 1], "      A A A A A A");
+    }
+
+    #[test]
+    fn test_cursor_movement() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: ('A'..='Z').map(GuessOption::Open).collect(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Qwerty,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let mut page = content.render(&Theme::default(), SCREEN);
+        for (ch, code, neighbor) in [
+            ('Q', KeyCode::Left, None),
+            ('Q', KeyCode::Right, Some('W')),
+            ('P', KeyCode::Right, Some('A')),
+            ('Q', KeyCode::Up, None),
+            ('E', KeyCode::Down, Some('S')),
+            ('A', KeyCode::Down, Some('Z')),
+            ('P', KeyCode::Down, Some('L')),
+            ('M', KeyCode::Up, Some('J')),
+            ('M', KeyCode::Down, None),
+        ] {
+            assert_eq!(page.neighbor(ch, code), neighbor, "{ch} {code:?}");
+        }
+        page.highlight('S', Style::new().add_modifier(Modifier::REVERSED));
+        assert_eq!(ansi(page)[3], "  │          A \x1B[7mS\x1B[m D F G H J K L");
     }
 
-    pub(crate) fn pause(&mut self) -> Result<(), ScreenError> {
-        self.read_guess().map(|_| ())
+    #[test]
+    fn test_theme() {
+        let theme = Theme {
+            gallows: Style::new().fg(Color::Yellow),
+            new_part: Style::new().fg(Color::Magenta),
+            message: Style::new().fg(Color::Cyan),
+            ..Theme::default()
+        };
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::AddHead),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::BadGuess { guess: 'E' },
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let page = content.render(&theme, SCREEN);
+        let head = &page.board[3].spans;
+        assert_eq!(head[0].content, "  │   ");
+        assert_eq!(head[0].style, theme.gallows);
+        assert_eq!(head[1].content, "o");
+        assert_eq!(head[1].style, theme.new_part);
+        assert_eq!(page.text[0].spans[0].style, theme.message);
     }
 
-    pub(crate) fn update(&mut self, content: Content) -> Result<(), ScreenError> {
-        self.lines = content.render();
-        self.draw()?;
-        Ok(())
+    #[test]
+    fn test_cheat_meter() {
+        let content = Content {
+            hint: Some(String::from("Animal")),
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: Some(17),
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[..2], ["Hint: Animal", "17 possible words"]);
     }
 
-    pub(crate) fn draw(&mut self) -> Result<(), ScreenError> {
-        let left_margin = match u16::try_from(Content::WIDTH) {
-            Ok(width) => self.columns.saturating_sub(width) / 2,
-            Err(_) => 0,
+    #[test]
+    fn test_lives() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::AddTorso),
+            opponent: None,
+            tally: Some(Tally::Solved(3)),
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: Some((4, 6)),
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
         };
-        let top_margin = match u16::try_from(self.lines.len()) {
-            Ok(length) => self.rows.saturating_sub(length) / 2,
-            Err(_) => 0,
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(lines[1], "Words solved: 3    ♥♥♥♥♡♡");
+        let many = Content {
+            tally: None,
+            lives: Some((11, 12)),
+            wrong: None,
+            ..content
         };
-        self.write(left_margin, top_margin)
-            .map_err(ScreenError::Write)
+        let lines = ansi(many.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[1], "Lives: 11/12");
     }
 
-    fn write(&mut self, left_margin: u16, top_margin: u16) -> io::Result<()> {
-        queue!(self.inner, Clear(ClearType::All))?;
-        for (y, ln) in std::iter::zip(top_margin.., &self.lines) {
-            queue!(self.inner, MoveTo(left_margin, y), Print(ln))?;
-        }
-        self.inner.flush()?;
-        Ok(())
+    #[test]
+    fn test_wrong_guesses() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: Some(Vec::new()),
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(lines[7..9], ["", ""]);
+        assert_eq!(lines[9], "          _ _");
+        let missed = Content {
+            figure: Figure::Gallows(Gallows::AddTorso),
+            wrong: Some(vec!['E', 'T', 'R']),
+            ..content
+        };
+        let lines = ansi(missed.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[7..9], ["Wrong: E, T, R", ""]);
+        assert_eq!(lines[9], "          _ _");
     }
 
-    fn beep(&mut self) -> Result<(), ScreenError> {
-        self.inner
-            .execute(Print("\x07"))
-            .map_err(ScreenError::Write)?;
-        Ok(())
+    #[test]
+    fn test_category() {
+        let content = Content {
+            hint: Some(String::from("Large and grey")),
+            hint_position: None,
+            category: Some(String::from("animals")),
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[0], "Category: animals    Hint: Large and grey");
     }
-}
 
-impl<W: Write> Drop for Screen<W> {
-    fn drop(&mut self) {
-        let _ = self.inner.execute(Show);
-        let _ = disable_raw_mode();
-        let _ = self.inner.execute(LeaveAlternateScreen);
+    #[test]
+    fn test_hint_position() {
+        let content = Content {
+            hint: Some(String::from("Has a trunk")),
+            hint_position: Some((2, 3)),
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[0], "Hint 2/3: Has a trunk");
     }
-}
-
-#[derive(Debug, Error)]
-pub(crate) enum ScreenError {
-    #[error("failed to initialize terminal display")]
-    Init(#[source] io::Error),
-    #[error("failed to read from terminal")]
-    Read(#[source] io::Error),
-    #[error("failed to write to terminal")]
-    Write(#[source] io::Error),
-}
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) struct Content {
-    pub(crate) hint: Option<String>,
-    pub(crate) gallows: Gallows,
-    pub(crate) guess_options: Vec<Option<char>>,
-    pub(crate) word_display: Vec<CharDisplay>,
-    pub(crate) message: Message,
-}
+    #[test]
+    fn test_part_of_speech() {
+        let mut content = Content {
+            hint: Some(String::from("Large and grey")),
+            hint_position: None,
+            category: Some(String::from("animals")),
+            part_of_speech: Some(String::from("noun")),
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(lines[0], "Category: animals    Hint (noun): Large and grey");
+        content.hint = None;
+        content.category = None;
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[0], "Part of speech: noun");
+    }
 
-impl Content {
-    const GALLOWS_HEIGHT: usize = 5;
-    const GALLOWS_WIDTH: usize = 8;
-    const LETTER_COLUMNS: usize = 6;
-    const GUTTER: usize = 4;
-    const WIDTH: usize =
-        Content::GALLOWS_WIDTH + Content::GUTTER + (Content::LETTER_COLUMNS * 2) - 1;
+    #[test]
+    fn test_definition() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Plain('A'), CharDisplay::Plain('B')],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Won,
+            flavor: 0,
+            input: None,
+            definition: Some(String::from("The start of the alphabet")),
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        let i = lines.iter().position(|ln| ln == "You win!").unwrap();
+        assert_eq!(lines[i + 1], "The start of the alphabet");
+        assert_eq!(lines[i + 2], "Press the Any Key to exit.");
+    }
 
-    fn render(self) -> Vec<String> {
-        let mut lines = Vec::with_capacity(Content::GALLOWS_HEIGHT + 8);
-        if let Some(hint) = self.hint {
-            lines.push(format!("Hint: {hint}"));
-        } else {
-            lines.push(String::new());
-        }
-        lines.push(String::new());
-        for row in Content::draw_gallows(self.gallows, self.message.gallows_advanced()) {
-            lines.push(format!("{}{:gutter$}", row, "", gutter = Content::GUTTER));
-        }
-        for (i, optchunk) in self
-            .guess_options
-            .chunks(Content::LETTER_COLUMNS)
-            .enumerate()
-        {
-            let ln = if let Some(ln) = lines.get_mut(i + 2) {
-                ln
-            } else {
-                lines.push(" ".repeat(Content::GALLOWS_WIDTH + Content::GUTTER));
-                lines
-                    .last_mut()
-                    .expect("lines should not be empty after pushing")
-            };
-            let mut first = true;
-            for opt in optchunk {
-                if !std::mem::replace(&mut first, false) {
-                    ln.push(' ');
-                }
-                ln.push(opt.unwrap_or(' '));
-            }
-        }
-        lines.push(String::new());
-        let indent = Content::WIDTH.saturating_sub(self.word_display.len() * 2 - 1) / 2;
-        let mut wordline = " ".repeat(indent);
-        let mut first = true;
-        for ch in self.word_display {
-            if !std::mem::replace(&mut first, false) {
-                wordline.push(' ');
-            }
-            write!(wordline, "{ch}").unwrap();
-        }
-        lines.push(wordline);
-        lines.push(String::new());
-        lines.push(self.message.to_string());
-        lines.push(String::new());
-        if self.message.is_game_over() {
-            lines.push(String::from("Press the Any Key to exit."));
-        } else {
-            lines.push(String::new());
-        }
-        lines
+    #[test]
+    fn test_tally() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: Some(Tally::Word {
+                number: 3,
+                total: 5,
+            }),
+            guess_options: vec![GuessOption::Open('A'), GuessOption::Open('B')],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: Some(1),
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[1], "Word 3 of 5    1 possible word");
     }
 
-    #[rustfmt::skip]
-    fn draw_gallows(
-        gallows: Gallows,
-        highlight: bool,
-    ) -> &'static [&'static str; Content::GALLOWS_HEIGHT] {
-        match (gallows, highlight) {
-            (Gallows::Start, _) => &[
-                "  ┌───┐ ",
-                "  │     ",
-                "  │     ",
-                "  │     ",
-                "──┴──   "
-            ],
-            (Gallows::AddHead, false) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │     ",
-                "  │     ",
-                "──┴──   "
-            ],
-            (Gallows::AddHead, true) => &[
-                "  ┌───┐ ",
-                "  │   \x1B[1;31mo\x1B[m ",
-                "  │     ",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddTorso, false) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │   | ",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddTorso, true) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │   \x1B[1;31m|\x1B[m ",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddLeftArm, false) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /| ",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddLeftArm, true) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  \x1B[1;31m/\x1B[m| ",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddRightArm, false) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /|\\",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddRightArm, true) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /|\x1B[1;31m\\\x1B[m",
-                "  │     ",
-                "──┴──   ",
-            ],
-            (Gallows::AddLeftLeg, false) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /|\\",
-                "  │  /  ",
-                "──┴──   ",
-            ],
-            (Gallows::AddLeftLeg, true) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /|\\",
-                "  │  \x1B[1;31m/\x1B[m  ",
-                "──┴──   ",
-            ],
-            (Gallows::AddRightLeg, false) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /|\\",
-                "  │  / \\",
-                "──┴──   ",
+    #[test]
+    fn test_match_summary() {
+        let summary = MatchSummary {
+            rounds: vec![
+                RoundResult {
+                    word: String::from("abacus"),
+                    won: true,
+                    points: 70,
+                },
+                RoundResult {
+                    word: String::from("cat"),
+                    won: false,
+                    points: 0,
+                },
             ],
-            (Gallows::AddRightLeg, true) => &[
-                "  ┌───┐ ",
-                "  │   o ",
-                "  │  /|\\",
-                "  │  / \x1B[1;31m\\\x1B[m",
-                "──┴──   ",
+        };
+        assert_eq!(
+            ansi(summary.render()),
+            [
+                "Match over!",
+                "",
+                "Round  1:  abacus  won     70",
+                "Round  2:  cat     lost     0",
+                "",
+                "Won 1 of 2 rounds for a total of 70 points",
+                "",
+                "Press the Any Key to exit.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_credits() {
+        let history = [
+            Guess {
+                letter: 'A',
+                count: 2,
+                player: 0,
+            },
+            Guess {
+                letter: 'E',
+                count: 0,
+                player: 1,
+            },
+            Guess {
+                letter: 'X',
+                count: 0,
+                player: 0,
+            },
+        ];
+        let tally = Tally::Credits(vec![
+            Credit::for_player(&history, 0),
+            Credit::for_player(&history, 1),
+        ]);
+        assert_eq!(
+            tally.to_string(),
+            "P1: 1 right, 1 wrong    P2: 0 right, 1 wrong"
+        );
+    }
+
+    #[test]
+    fn test_hide_new_parts() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::AddTorso),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Lost,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let hidden = ansi(content.clone().render_frame(
+            &Theme::default(),
+            SCREEN,
+            Effect::HideNewParts,
+        ));
+        assert_eq!(
+            &hidden[2..7],
+            [
+                "        ┌───┐     ",
+                "        │   o     ",
+                "        │         ",
+                "        │         ",
+                "      ──┴──       "
+            ]
+        );
+        let shown = ansi(
+            content
+                .clone()
+                .render_frame(&Theme::default(), SCREEN, Effect::None),
+        );
+        assert_eq!(shown[4], "        │   \x1B[1;31m|\x1B[m     ");
+        assert_eq!(hidden[7..], shown[7..]);
+        let kids = Content {
+            figure: Figure::Dancer(2),
+            ..content
+        };
+        assert_eq!(
+            kids.clone()
+                .render_frame(&Theme::default(), SCREEN, Effect::HideNewParts),
+            kids.render(&Theme::default(), SCREEN)
+        );
+        assert_eq!(Gallows::AddHead.pred(), Some(Gallows::Start));
+        assert_eq!(Gallows::Start.pred(), None);
+    }
+
+    #[test]
+    fn test_sparkles() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![
+                CharDisplay::Plain('C'),
+                CharDisplay::Plain('A'),
+                CharDisplay::Plain('T'),
             ],
-        }
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Won,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render_frame(
+            &Theme::default(),
+            SCREEN,
+            Effect::Sparkles(1),
+        ));
+        assert_eq!(
+            lines[7..],
+            [
+                "       \x1B[1m* . * . *\x1B[m",
+                "       \x1B[1m+ \x1B[mC A T\x1B[1m +\x1B[m",
+                "       \x1B[1m. * . * .\x1B[m",
+                "You win!",
+                "",
+                "",
+            ]
+        );
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(lines[lines.len() - 1], "Press the Any Key to exit.");
     }
-}
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub(crate) enum CharDisplay {
-    Plain(char),
-    Highlighted(char),
-    Blank,
-}
+    #[test]
+    fn test_big_word() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![
+                CharDisplay::Plain('C'),
+                CharDisplay::Highlighted('a'),
+                CharDisplay::Blank,
+                CharDisplay::Plain('-'),
+            ],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: true,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(
+            lines[8..11],
+            [
+                "    ┏━╸ \x1B[1m┏━┓\x1B[m        ",
+                "    ┃   \x1B[1m┣━┫\x1B[m      - ",
+                "    ┗━╸ \x1B[1m╹ ╹\x1B[m ╺━╸    ",
+            ]
+        );
+        // Too short for large letters
+        let short = Rect {
+            height: 16,
+            ..SCREEN
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), short));
+        assert_eq!(lines[8], "        C \x1B[1ma\x1B[m _ -");
+        // Too narrow for large letters
+        let narrow = Rect {
+            width: 14,
+            ..SCREEN
+        };
+        let lines = ansi(content.render(&Theme::default(), narrow));
+        assert_eq!(lines[8], "        C \x1B[1ma\x1B[m _ -");
+    }
 
-impl fmt::Display for CharDisplay {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            CharDisplay::Plain(ch) => write!(f, "{ch}"),
-            CharDisplay::Highlighted(ch) => write!(f, "\x1B[1m{ch}\x1B[m"),
-            CharDisplay::Blank => write!(f, "_"),
-        }
+    #[test]
+    fn test_big_word_widens_page() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Plain('A'); 8],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: true,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let page = content.render(&Theme::default(), SCREEN);
+        assert_eq!(page.width, 31);
+        let lines = ansi(page);
+        assert_eq!(lines[2], "      ┌───┐     ");
+        assert_eq!(lines[8], "┏━┓ ".repeat(7) + "┏━┓");
     }
-}
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub(crate) enum Message {
-    Start,
-    GoodGuess { guess: char, count: usize },
-    BadGuess { guess: char },
-    AlreadyGuessed { guess: char },
-    InvalidGuess { guess: char },
-    Won,
-    Lost,
-}
+    #[test]
+    fn test_banner_needs_room() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: None,
+            tally: None,
+            guess_options: Vec::new(),
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Plain('C'), CharDisplay::Plain('A')],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::ComputerHanged { guess: 'E' },
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: Some(Stats {
+                guesses: 3,
+                misses: 1,
+            }),
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(lines[8], "╻ ╻ ┏━┓ ╻ ╻     ╻ ╻ ╺┳╸ ┏┓╻");
+        let short = Rect {
+            height: 18,
+            ..SCREEN
+        };
+        let lines = ansi(content.render(&Theme::default(), short));
+        assert_eq!(
+            lines[7..],
+            [
+                "",
+                "          C A",
+                "",
+                "The computer missed with 'E' and hanged.  You win!",
+                "",
+                "Press the Any Key to exit.",
+            ]
+        );
+    }
 
-impl Message {
-    fn is_game_over(&self) -> bool {
-        matches!(self, Message::Won | Message::Lost)
+    #[test]
+    fn test_status() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::AddHead),
+            opponent: None,
+            tally: None,
+            guess_options: vec![
+                GuessOption::Open('A'),
+                GuessOption::Guessed('B'),
+                GuessOption::Open('C'),
+            ],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Blank],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::BadGuess { guess: 'B' },
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: Some(Status {
+                clock: Clock::Stopped(Duration::from_secs(754)),
+                guesses: 1,
+                misses_remaining: Some(5),
+            }),
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.clone().render(&Theme::default(), SCREEN));
+        assert_eq!(
+            lines.last().unwrap(),
+            "Time: 12:34    Guesses: 1    Misses left: 5"
+        );
+        let kids = Content {
+            status: Some(Status {
+                clock: Clock::Stopped(Duration::from_secs(5)),
+                guesses: 1,
+                misses_remaining: None,
+            }),
+            ..content
+        };
+        let lines = ansi(kids.render(&Theme::default(), SCREEN));
+        assert_eq!(lines.last().unwrap(), "Time: 0:05    Guesses: 1");
     }
 
-    fn gallows_advanced(&self) -> bool {
-        matches!(self, Message::BadGuess { .. } | Message::Lost)
+    #[test]
+    fn test_countdown_bar() {
+        let countdown = Countdown::start(Duration::from_secs(10));
+        assert_eq!(
+            countdown.bar(Duration::from_secs(10), 20),
+            "████████████████ 10s"
+        );
+        assert_eq!(
+            countdown.bar(Duration::from_millis(4500), 20),
+            "████████░░░░░░░░░ 5s"
+        );
+        assert_eq!(countdown.bar(Duration::from_millis(1), 12), "█░░░░░░░░ 1s");
+        assert_eq!(countdown.bar(Duration::ZERO, 12), "░░░░░░░░░ 0s");
     }
-}
 
-impl fmt::Display for Message {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Message::Start => write!(f, "Try to guess the secret word!"),
-            Message::GoodGuess { guess, count } => {
-                write!(f, "Correct!  There ")?;
-                if *count == 1 {
-                    write!(f, "is 1 {guess:?} ")?;
-                } else {
-                    write!(f, "are {count} {guess:?}s ")?;
-                }
-                write!(f, "in the word.")?;
-                Ok(())
-            }
-            Message::BadGuess { guess, .. } => {
-                write!(f, "Wrong!  There's no {guess:?} in the word.")
-            }
-            Message::AlreadyGuessed { guess } => {
-                write!(f, "You already guessed {guess:?}.")
-            }
-            Message::InvalidGuess { guess } => {
-                write!(f, "{guess:?} is not an option.")
-            }
-            Message::Won => write!(f, "You win!"),
-            Message::Lost => write!(f, "Oh dear, you are dead!"),
-        }
+    #[test]
+    fn test_image_figures() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Image(Graphics::Kitty, Gallows::Start),
+            opponent: Some(Figure::Image(Graphics::Kitty, Gallows::AddHead)),
+            tally: None,
+            guess_options: vec![
+                GuessOption::Open('A'),
+                GuessOption::Guessed('B'),
+                GuessOption::Open('C'),
+            ],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Plain('B')],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::Start,
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let page = content.render(&Theme::default(), SCREEN);
+        let positions = page
+            .images
+            .iter()
+            .map(|image| (image.row, image.column))
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [(3, 0), (3, 27)]);
+        assert_eq!(ansi(page.clone())[3], "            A   C                  ");
+        let screen = Rect::new(0, 0, 80, 24);
+        let area = page.area(screen);
+        assert_eq!(page.images[0].position(area, screen), Some((22, 8)));
+        let small = Rect::new(0, 0, 30, 24);
+        assert_eq!(page.images[1].position(page.area(small), small), None);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use unicode_width::UnicodeWidthStr;
+    #[test]
+    fn test_duel() {
+        let content = Content {
+            hint: None,
+            hint_position: None,
+            category: None,
+            part_of_speech: None,
+            figure: Figure::Gallows(Gallows::Start),
+            opponent: Some(Figure::Gallows(Gallows::AddHead)),
+            tally: None,
+            guess_options: vec![
+                GuessOption::Open('A'),
+                GuessOption::Guessed('B'),
+                GuessOption::Open('C'),
+            ],
+            guessed: GuessedLetters::Blank,
+            letter_layout: LetterLayout::Alphabetical,
+            arrangement: Arrangement::default(),
+            suggestion: None,
+            candidates: None,
+            lives: None,
+            wrong: None,
+            word_display: vec![CharDisplay::Blank, CharDisplay::Plain('B')],
+            wrap_group: NonZeroUsize::MIN,
+            blank: Placeholder::default(),
+            big_word: false,
+            message: Message::DuelRound {
+                yours: Guess {
+                    letter: 'B',
+                    count: 1,
+                    player: 0,
+                },
+                theirs: Guess {
+                    letter: 'E',
+                    count: 0,
+                    player: 1,
+                },
+            },
+            flavor: 0,
+            input: None,
+            definition: None,
+            stats: None,
+            status: None,
+            countdown: None,
+            history: Vec::new(),
+            title: Title::default(),
+        };
+        let lines = ansi(content.render(&Theme::default(), SCREEN));
+        assert_eq!(
+            lines,
+            [
+                "",
+                "",
+                "  You                      Computer",
+                "  ┌───┐     A   C            ┌───┐ ",
+                "  │                          │   \x1B[1;31mo\x1B[m ",
+                "  │                          │     ",
+                "  │                          │     ",
+                "──┴──                      ──┴──   ",
+                "",
+                "                _ B",
+                "",
+                "You: 'B' hit.  Computer: 'E' missed.",
+                "",
+                "",
+            ]
+        );
+    }
 
     #[test]
-    fn test_gallows_widths() {
-        for gallows in std::iter::successors(Some(Gallows::Start), |&g| g.succ()) {
-            for line in Content::draw_gallows(gallows, false) {
+    fn test_dancer_widths() {
+        for misses in 0..4 {
+            for line in Content::draw_dancer(misses) {
                 assert_eq!(UnicodeWidthStr::width(*line), Content::GALLOWS_WIDTH);
             }
-            for line in Content::draw_gallows(gallows, false) {
-                let line = strip_ansi_escapes::strip_str(line);
-                assert_eq!(UnicodeWidthStr::width(&*line), Content::GALLOWS_WIDTH);
-            }
         }
     }
 
@@ -398,35 +5922,47 @@ mod tests {
         fn start() {
             let content = Content {
                 hint: Some(String::from("A difficult word")),
-                gallows: Gallows::Start,
+                hint_position: None,
+                category: None,
+                part_of_speech: None,
+                figure: Figure::Gallows(Gallows::Start),
+                opponent: None,
+                tally: None,
                 guess_options: vec![
-                    Some('A'),
-                    Some('B'),
-                    Some('C'),
-                    Some('D'),
-                    Some('E'),
-                    Some('F'),
-                    Some('G'),
-                    Some('H'),
-                    Some('I'),
-                    Some('J'),
-                    Some('K'),
-                    Some('L'),
-                    Some('M'),
-                    Some('N'),
-                    Some('O'),
-                    Some('P'),
-                    Some('Q'),
-                    Some('R'),
-                    Some('S'),
-                    Some('T'),
-                    Some('U'),
-                    Some('V'),
-                    Some('W'),
-                    Some('X'),
-                    Some('Y'),
-                    Some('Z'),
+                    GuessOption::Open('A'),
+                    GuessOption::Open('B'),
+                    GuessOption::Open('C'),
+                    GuessOption::Open('D'),
+                    GuessOption::Open('E'),
+                    GuessOption::Open('F'),
+                    GuessOption::Open('G'),
+                    GuessOption::Open('H'),
+                    GuessOption::Open('I'),
+                    GuessOption::Open('J'),
+                    GuessOption::Open('K'),
+                    GuessOption::Open('L'),
+                    GuessOption::Open('M'),
+                    GuessOption::Open('N'),
+                    GuessOption::Open('O'),
+                    GuessOption::Open('P'),
+                    GuessOption::Open('Q'),
+                    GuessOption::Open('R'),
+                    GuessOption::Open('S'),
+                    GuessOption::Open('T'),
+                    GuessOption::Open('U'),
+                    GuessOption::Open('V'),
+                    GuessOption::Open('W'),
+                    GuessOption::Open('X'),
+                    GuessOption::Open('Y'),
+                    GuessOption::Open('Z'),
                 ],
+                guessed: GuessedLetters::Blank,
+                letter_layout: LetterLayout::Alphabetical,
+                arrangement: Arrangement::default(),
+                suggestion: None,
+                candidates: None,
+                lives: None,
+                wrong: None,
                 word_display: vec![
                     CharDisplay::Blank,
                     CharDisplay::Blank,
@@ -435,9 +5971,20 @@ mod tests {
                     CharDisplay::Blank,
                     CharDisplay::Blank,
                 ],
+                wrap_group: NonZeroUsize::MIN,
+                blank: Placeholder::default(),
+                big_word: false,
                 message: Message::Start,
+                flavor: 0,
+                input: None,
+                definition: None,
+                stats: None,
+                status: None,
+                countdown: None,
+                history: Vec::new(),
+                title: Title::default(),
             };
-            let lines = content.render();
+            let lines = ansi(content.render(&Theme::default(), SCREEN));
             assert_eq!(
                 lines,
                 [
@@ -462,35 +6009,47 @@ mod tests {
         fn no_hint() {
             let content = Content {
                 hint: None,
-                gallows: Gallows::Start,
+                hint_position: None,
+                category: None,
+                part_of_speech: None,
+                figure: Figure::Gallows(Gallows::Start),
+                opponent: None,
+                tally: None,
                 guess_options: vec![
-                    Some('A'),
-                    Some('B'),
-                    Some('C'),
-                    Some('D'),
-                    Some('E'),
-                    Some('F'),
-                    Some('G'),
-                    Some('H'),
-                    Some('I'),
-                    Some('J'),
-                    Some('K'),
-                    Some('L'),
-                    Some('M'),
-                    Some('N'),
-                    Some('O'),
-                    Some('P'),
-                    Some('Q'),
-                    Some('R'),
-                    Some('S'),
-                    Some('T'),
-                    Some('U'),
-                    Some('V'),
-                    Some('W'),
-                    Some('X'),
-                    Some('Y'),
-                    Some('Z'),
+                    GuessOption::Open('A'),
+                    GuessOption::Open('B'),
+                    GuessOption::Open('C'),
+                    GuessOption::Open('D'),
+                    GuessOption::Open('E'),
+                    GuessOption::Open('F'),
+                    GuessOption::Open('G'),
+                    GuessOption::Open('H'),
+                    GuessOption::Open('I'),
+                    GuessOption::Open('J'),
+                    GuessOption::Open('K'),
+                    GuessOption::Open('L'),
+                    GuessOption::Open('M'),
+                    GuessOption::Open('N'),
+                    GuessOption::Open('O'),
+                    GuessOption::Open('P'),
+                    GuessOption::Open('Q'),
+                    GuessOption::Open('R'),
+                    GuessOption::Open('S'),
+                    GuessOption::Open('T'),
+                    GuessOption::Open('U'),
+                    GuessOption::Open('V'),
+                    GuessOption::Open('W'),
+                    GuessOption::Open('X'),
+                    GuessOption::Open('Y'),
+                    GuessOption::Open('Z'),
                 ],
+                guessed: GuessedLetters::Blank,
+                letter_layout: LetterLayout::Alphabetical,
+                arrangement: Arrangement::default(),
+                suggestion: None,
+                candidates: None,
+                lives: None,
+                wrong: None,
                 word_display: vec![
                     CharDisplay::Blank,
                     CharDisplay::Blank,
@@ -499,9 +6058,20 @@ mod tests {
                     CharDisplay::Blank,
                     CharDisplay::Blank,
                 ],
+                wrap_group: NonZeroUsize::MIN,
+                blank: Placeholder::default(),
+                big_word: false,
                 message: Message::Start,
+                flavor: 0,
+                input: None,
+                definition: None,
+                stats: None,
+                status: None,
+                countdown: None,
+                history: Vec::new(),
+                title: Title::default(),
             };
-            let lines = content.render();
+            let lines = ansi(content.render(&Theme::default(), SCREEN));
             assert_eq!(
                 lines,
                 [
@@ -526,35 +6096,47 @@ mod tests {
         fn after_good_guess() {
             let content = Content {
                 hint: Some(String::from("A difficult word")),
-                gallows: Gallows::Start,
+                hint_position: None,
+                category: None,
+                part_of_speech: None,
+                figure: Figure::Gallows(Gallows::Start),
+                opponent: None,
+                tally: None,
                 guess_options: vec![
-                    None,
-                    Some('B'),
-                    Some('C'),
-                    Some('D'),
-                    Some('E'),
-                    Some('F'),
-                    Some('G'),
-                    Some('H'),
-                    Some('I'),
-                    Some('J'),
-                    Some('K'),
-                    Some('L'),
-                    Some('M'),
-                    Some('N'),
-                    Some('O'),
-                    Some('P'),
-                    Some('Q'),
-                    Some('R'),
-                    Some('S'),
-                    Some('T'),
-                    Some('U'),
-                    Some('V'),
-                    Some('W'),
-                    Some('X'),
-                    Some('Y'),
-                    Some('Z'),
+                    GuessOption::Guessed('A'),
+                    GuessOption::Open('B'),
+                    GuessOption::Open('C'),
+                    GuessOption::Open('D'),
+                    GuessOption::Open('E'),
+                    GuessOption::Open('F'),
+                    GuessOption::Open('G'),
+                    GuessOption::Open('H'),
+                    GuessOption::Open('I'),
+                    GuessOption::Open('J'),
+                    GuessOption::Open('K'),
+                    GuessOption::Open('L'),
+                    GuessOption::Open('M'),
+                    GuessOption::Open('N'),
+                    GuessOption::Open('O'),
+                    GuessOption::Open('P'),
+                    GuessOption::Open('Q'),
+                    GuessOption::Open('R'),
+                    GuessOption::Open('S'),
+                    GuessOption::Open('T'),
+                    GuessOption::Open('U'),
+                    GuessOption::Open('V'),
+                    GuessOption::Open('W'),
+                    GuessOption::Open('X'),
+                    GuessOption::Open('Y'),
+                    GuessOption::Open('Z'),
                 ],
+                guessed: GuessedLetters::Blank,
+                letter_layout: LetterLayout::Alphabetical,
+                arrangement: Arrangement::default(),
+                suggestion: None,
+                candidates: None,
+                lives: None,
+                wrong: None,
                 word_display: vec![
                     CharDisplay::Highlighted('A'),
                     CharDisplay::Blank,
@@ -563,12 +6145,23 @@ mod tests {
                     CharDisplay::Blank,
                     CharDisplay::Blank,
                 ],
+                wrap_group: NonZeroUsize::MIN,
+                blank: Placeholder::default(),
+                big_word: false,
                 message: Message::GoodGuess {
                     guess: 'A',
                     count: 2,
                 },
+                flavor: 0,
+                input: None,
+                definition: None,
+                stats: None,
+                status: None,
+                countdown: None,
+                history: Vec::new(),
+                title: Title::default(),
             };
-            let lines = content.render();
+            let lines = ansi(content.render(&Theme::default(), SCREEN));
             assert_eq!(
                 lines,
                 [
@@ -593,35 +6186,47 @@ mod tests {
         fn after_bad_guess() {
             let content = Content {
                 hint: Some(String::from("A difficult word")),
-                gallows: Gallows::AddHead,
+                hint_position: None,
+                category: None,
+                part_of_speech: None,
+                figure: Figure::Gallows(Gallows::AddHead),
+                opponent: None,
+                tally: None,
                 guess_options: vec![
-                    None,
-                    Some('B'),
-                    Some('C'),
-                    Some('D'),
-                    None,
-                    Some('F'),
-                    Some('G'),
-                    Some('H'),
-                    Some('I'),
-                    Some('J'),
-                    Some('K'),
-                    Some('L'),
-                    Some('M'),
-                    Some('N'),
-                    Some('O'),
-                    Some('P'),
-                    Some('Q'),
-                    Some('R'),
-                    Some('S'),
-                    Some('T'),
-                    Some('U'),
-                    Some('V'),
-                    Some('W'),
-                    Some('X'),
-                    Some('Y'),
-                    Some('Z'),
+                    GuessOption::Guessed('A'),
+                    GuessOption::Open('B'),
+                    GuessOption::Open('C'),
+                    GuessOption::Open('D'),
+                    GuessOption::Guessed('E'),
+                    GuessOption::Open('F'),
+                    GuessOption::Open('G'),
+                    GuessOption::Open('H'),
+                    GuessOption::Open('I'),
+                    GuessOption::Open('J'),
+                    GuessOption::Open('K'),
+                    GuessOption::Open('L'),
+                    GuessOption::Open('M'),
+                    GuessOption::Open('N'),
+                    GuessOption::Open('O'),
+                    GuessOption::Open('P'),
+                    GuessOption::Open('Q'),
+                    GuessOption::Open('R'),
+                    GuessOption::Open('S'),
+                    GuessOption::Open('T'),
+                    GuessOption::Open('U'),
+                    GuessOption::Open('V'),
+                    GuessOption::Open('W'),
+                    GuessOption::Open('X'),
+                    GuessOption::Open('Y'),
+                    GuessOption::Open('Z'),
                 ],
+                guessed: GuessedLetters::Blank,
+                letter_layout: LetterLayout::Alphabetical,
+                arrangement: Arrangement::default(),
+                suggestion: None,
+                candidates: None,
+                lives: None,
+                wrong: None,
                 word_display: vec![
                     CharDisplay::Plain('A'),
                     CharDisplay::Blank,
@@ -630,9 +6235,20 @@ mod tests {
                     CharDisplay::Blank,
                     CharDisplay::Blank,
                 ],
+                wrap_group: NonZeroUsize::MIN,
+                blank: Placeholder::default(),
+                big_word: false,
                 message: Message::BadGuess { guess: 'E' },
+                flavor: 0,
+                input: None,
+                definition: None,
+                stats: None,
+                status: None,
+                countdown: None,
+                history: Vec::new(),
+                title: Title::default(),
             };
-            let lines = content.render();
+            let lines = ansi(content.render(&Theme::default(), SCREEN));
             assert_eq!(
                 lines,
                 [
@@ -657,35 +6273,47 @@ mod tests {
         fn win() {
             let content = Content {
                 hint: Some(String::from("A difficult word")),
-                gallows: Gallows::AddRightArm,
+                hint_position: None,
+                category: None,
+                part_of_speech: None,
+                figure: Figure::Gallows(Gallows::AddRightArm),
+                opponent: None,
+                tally: None,
                 guess_options: vec![
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    Some('F'),
-                    Some('G'),
-                    Some('H'),
-                    None,
-                    Some('J'),
-                    Some('K'),
-                    Some('L'),
-                    Some('M'),
-                    Some('N'),
-                    Some('O'),
-                    Some('P'),
-                    Some('Q'),
-                    Some('R'),
-                    None,
-                    None,
-                    None,
-                    Some('V'),
-                    Some('W'),
-                    Some('X'),
-                    Some('Y'),
-                    Some('Z'),
+                    GuessOption::Guessed('A'),
+                    GuessOption::Guessed('B'),
+                    GuessOption::Guessed('C'),
+                    GuessOption::Guessed('D'),
+                    GuessOption::Guessed('E'),
+                    GuessOption::Open('F'),
+                    GuessOption::Open('G'),
+                    GuessOption::Open('H'),
+                    GuessOption::Guessed('I'),
+                    GuessOption::Open('J'),
+                    GuessOption::Open('K'),
+                    GuessOption::Open('L'),
+                    GuessOption::Open('M'),
+                    GuessOption::Open('N'),
+                    GuessOption::Open('O'),
+                    GuessOption::Open('P'),
+                    GuessOption::Open('Q'),
+                    GuessOption::Open('R'),
+                    GuessOption::Guessed('S'),
+                    GuessOption::Guessed('T'),
+                    GuessOption::Guessed('U'),
+                    GuessOption::Open('V'),
+                    GuessOption::Open('W'),
+                    GuessOption::Open('X'),
+                    GuessOption::Open('Y'),
+                    GuessOption::Open('Z'),
                 ],
+                guessed: GuessedLetters::Blank,
+                letter_layout: LetterLayout::Alphabetical,
+                arrangement: Arrangement::default(),
+                suggestion: None,
+                candidates: None,
+                lives: None,
+                wrong: None,
                 word_display: vec![
                     CharDisplay::Plain('A'),
                     CharDisplay::Plain('B'),
@@ -694,23 +6322,42 @@ mod tests {
                     CharDisplay::Plain('U'),
                     CharDisplay::Plain('S'),
                 ],
+                wrap_group: NonZeroUsize::MIN,
+                blank: Placeholder::default(),
+                big_word: false,
                 message: Message::Won,
+                flavor: 0,
+                input: None,
+                definition: None,
+                stats: Some(Stats {
+                    guesses: 9,
+                    misses: 4,
+                }),
+                status: None,
+                countdown: None,
+                history: Vec::new(),
+                title: Title::default(),
             };
-            let lines = content.render();
+            let lines = ansi(content.render(&Theme::default(), SCREEN));
             assert_eq!(
                 lines,
                 [
-                    "Hint: A difficult word",
+                    "  Hint: A difficult word",
+                    "  ",
+                    "    ┌───┐               F",
+                    "    │   o     G H   J K L",
+                    "    │  /|\\    M N O P Q R",
+                    "    │               V W X",
+                    "  ──┴──       Y Z",
                     "",
-                    "  ┌───┐               F",
-                    "  │   o     G H   J K L",
-                    "  │  /|\\    M N O P Q R",
-                    "  │               V W X",
-                    "──┴──       Y Z",
+                    "╻ ╻ ┏━┓ ╻ ╻     ╻ ╻ ╺┳╸ ┏┓╻",
+                    "┗┳┛ ┃ ┃ ┃ ┃     ┃╻┃  ┃  ┃┗┫",
+                    " ╹  ┗━┛ ┗━┛     ┗┻┛ ╺┻╸ ╹ ╹",
                     "",
-                    "      A B A C U S",
+                    "        A B A C U S",
                     "",
                     "You win!",
+                    "Guesses: 9    Misses: 4",
                     "",
                     "Press the Any Key to exit.",
                 ]
@@ -721,35 +6368,47 @@ mod tests {
         fn lose() {
             let content = Content {
                 hint: Some(String::from("A difficult word")),
-                gallows: Gallows::AddRightLeg,
+                hint_position: None,
+                category: None,
+                part_of_speech: None,
+                figure: Figure::Gallows(Gallows::AddRightLeg),
+                opponent: None,
+                tally: None,
                 guess_options: vec![
-                    None,
-                    Some('B'),
-                    Some('C'),
-                    Some('D'),
-                    None,
-                    Some('F'),
-                    Some('G'),
-                    Some('H'),
-                    None,
-                    Some('J'),
-                    Some('K'),
-                    Some('L'),
-                    Some('M'),
-                    Some('N'),
-                    None,
-                    Some('P'),
-                    Some('Q'),
-                    None,
-                    Some('S'),
-                    None,
-                    None,
-                    Some('V'),
-                    Some('W'),
-                    Some('X'),
-                    None,
-                    Some('Z'),
+                    GuessOption::Guessed('A'),
+                    GuessOption::Open('B'),
+                    GuessOption::Open('C'),
+                    GuessOption::Open('D'),
+                    GuessOption::Guessed('E'),
+                    GuessOption::Open('F'),
+                    GuessOption::Open('G'),
+                    GuessOption::Open('H'),
+                    GuessOption::Guessed('I'),
+                    GuessOption::Open('J'),
+                    GuessOption::Open('K'),
+                    GuessOption::Open('L'),
+                    GuessOption::Open('M'),
+                    GuessOption::Open('N'),
+                    GuessOption::Guessed('O'),
+                    GuessOption::Open('P'),
+                    GuessOption::Open('Q'),
+                    GuessOption::Guessed('R'),
+                    GuessOption::Open('S'),
+                    GuessOption::Guessed('T'),
+                    GuessOption::Guessed('U'),
+                    GuessOption::Open('V'),
+                    GuessOption::Open('W'),
+                    GuessOption::Open('X'),
+                    GuessOption::Guessed('Y'),
+                    GuessOption::Open('Z'),
                 ],
+                guessed: GuessedLetters::Blank,
+                letter_layout: LetterLayout::Alphabetical,
+                arrangement: Arrangement::default(),
+                suggestion: None,
+                candidates: None,
+                lives: None,
+                wrong: None,
                 word_display: vec![
                     CharDisplay::Plain('A'),
                     CharDisplay::Highlighted('B'),
@@ -758,23 +6417,42 @@ mod tests {
                     CharDisplay::Plain('U'),
                     CharDisplay::Highlighted('S'),
                 ],
+                wrap_group: NonZeroUsize::MIN,
+                blank: Placeholder::default(),
+                big_word: false,
                 message: Message::Lost,
+                flavor: 0,
+                input: None,
+                definition: None,
+                stats: Some(Stats {
+                    guesses: 8,
+                    misses: 6,
+                }),
+                status: None,
+                countdown: None,
+                history: Vec::new(),
+                title: Title::default(),
             };
-            let lines = content.render();
+            let lines = ansi(content.render(&Theme::default(), SCREEN));
             assert_eq!(
                 lines,
                 [
-                    "Hint: A difficult word",
+                    "      Hint: A difficult word",
+                    "      ",
+                    "        ┌───┐       B C D   F",
+                    "        │   o     G H   J K L",
+                    "        │  /|\\    M N   P Q  ",
+                    "        │  / \x1B[1;31m\\\x1B[m    S     V W X",
+                    "      ──┴──         Z",
                     "",
-                    "  ┌───┐       B C D   F",
-                    "  │   o     G H   J K L",
-                    "  │  /|\\    M N   P Q  ",
-                    "  │  / \x1B[1;31m\\\x1B[m    S     V W X",
-                    "──┴──         Z",
+                    "┏━╸ ┏━┓ ┏┳┓ ┏━╸     ┏━┓ ╻ ╻ ┏━╸ ┏━┓",
+                    "┃╺┓ ┣━┫ ┃┃┃ ┣╸      ┃ ┃ ┃┏┛ ┣╸  ┣┳┛",
+                    "┗━┛ ╹ ╹ ╹ ╹ ┗━╸     ┗━┛ ┗┛  ┗━╸ ╹┗╸",
                     "",
-                    "      A \x1B[1mB\x1B[m A \x1B[1mC\x1B[m U \x1B[1mS\x1B[m",
+                    "            A \x1B[1mB\x1B[m A \x1B[1mC\x1B[m U \x1B[1mS\x1B[m",
                     "",
                     "Oh dear, you are dead!",
+                    "Guesses: 8    Misses: 6",
                     "",
                     "Press the Any Key to exit.",
                 ]
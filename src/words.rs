@@ -1,18 +1,80 @@
+use crate::index::WordIndex;
+use crate::locale::Locale;
+use crate::model::{normalize_char, ASCII_ALPHABET};
+use crate::profanity::is_profane;
+use crate::view::read_hidden;
 use anyhow::Context;
 use patharg::InputArg;
-use rand::seq::IteratorRandom;
+use rand::rngs::StdRng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::Rng;
+use regex::{Regex, RegexBuilder};
 use serde::{
     de::{Deserializer, Unexpected, Visitor},
-    Deserialize,
+    Deserialize, Serialize,
 };
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 use thiserror::Error;
+use time::Date;
 
-static WORDS: &[u8] = include_bytes!("words.csv");
+/// Include the compressed records of the builtin word list `src/<name>.csv`,
+/// as produced by the build script
+macro_rules! builtin_table {
+    ($name:literal) => {
+        BuiltinTable::new(include_bytes!(concat!(env!("OUT_DIR"), "/", $name, ".bin")))
+    };
+}
+
+static WORDS: BuiltinTable = builtin_table!("words");
+
+static KIDS_WORDS: BuiltinTable = builtin_table!("kids");
+
+static ANIMALS_WORDS: BuiltinTable = builtin_table!("animals");
+
+static COUNTRIES_WORDS: BuiltinTable = builtin_table!("countries");
+
+static MOVIES_WORDS: BuiltinTable = builtin_table!("movies");
+
+static SCIENCE_WORDS: BuiltinTable = builtin_table!("science");
 
-#[derive(Clone, Eq, Debug, Hash, PartialEq)]
+/// The number of randomly-chosen entries of a builtin list to try before
+/// falling back to searching the whole list for one that meets the selection
+/// criteria
+const BUILTIN_ATTEMPTS: usize = 100;
+
+#[derive(Clone, Eq, Debug, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
 pub(crate) struct Word(String);
 
+impl Word {
+    /// Returns the number of characters in the word
+    pub(crate) fn char_len(&self) -> usize {
+        self.0.chars().count()
+    }
+
+    /// Returns true iff the word contains at least one character from
+    /// `alphabet`, i.e., iff it can be used in a game with that alphabet
+    pub(crate) fn has_letters_in(&self, alphabet: &str) -> bool {
+        self.0.chars().any(|c| {
+            alphabet
+                .chars()
+                .any(|a| normalize_char(a) == normalize_char(c))
+        })
+    }
+
+    /// Shorten the word to at most `max` characters, removing any whitespace
+    /// left at the end.  Returns `Err` if the result would be empty.
+    fn truncate(&mut self, max: usize) -> Result<(), ParseWordError> {
+        let s: String = self.0.chars().take(max).collect();
+        *self = s.parse()?;
+        Ok(())
+    }
+}
+
 impl AsRef<str> for Word {
     fn as_ref(&self) -> &str {
         self.0.as_ref()
@@ -64,65 +126,1873 @@ impl<'de> Deserialize<'de> for Word {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+/// The maximum length of a secret word, along with what to do about words that
+/// exceed it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct LengthLimit {
+    pub(crate) max: usize,
+    pub(crate) policy: LongWordPolicy,
+}
+
+impl LengthLimit {
+    /// The default maximum length, chosen so that the word display fits on an
+    /// 80-column terminal
+    pub(crate) const DEFAULT_MAX: usize = 40;
+
+    /// Enforce the limit on `word`.  Returns `Ok(true)` if the word was
+    /// truncated, `Ok(false)` if it was already short enough.
+    pub(crate) fn apply(&self, word: &mut Word) -> Result<bool, WordTooLongError> {
+        let length = word.char_len();
+        if length <= self.max {
+            return Ok(false);
+        }
+        let err = WordTooLongError {
+            length,
+            max: self.max,
+        };
+        match self.policy {
+            LongWordPolicy::Error => Err(err),
+            LongWordPolicy::Truncate => {
+                word.truncate(self.max).map_err(|_| err)?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl Default for LengthLimit {
+    fn default() -> LengthLimit {
+        LengthLimit {
+            max: LengthLimit::DEFAULT_MAX,
+            policy: LongWordPolicy::default(),
+        }
+    }
+}
+
+/// What to do with a secret word that is longer than the [`LengthLimit`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum LongWordPolicy {
+    /// Refuse to play
+    #[default]
+    Error,
+    /// Play with the word truncated to the maximum length
+    Truncate,
+}
+
+impl std::str::FromStr for LongWordPolicy {
+    type Err = ParseLongWordPolicyError;
+
+    fn from_str(s: &str) -> Result<LongWordPolicy, ParseLongWordPolicyError> {
+        match s {
+            "error" => Ok(LongWordPolicy::Error),
+            "truncate" => Ok(LongWordPolicy::Truncate),
+            _ => Err(ParseLongWordPolicyError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"long word policy must be "error" or "truncate""#)]
+pub(crate) struct ParseLongWordPolicyError;
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("secret word is {length} characters long; the maximum is {max} (see --max-length and --long-words)")]
+pub(crate) struct WordTooLongError {
+    length: usize,
+    max: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub(crate) struct WordWithHint {
     pub(crate) word: Word,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) hint: Option<String>,
+    /// The category that the word belongs to, e.g., "animals"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) category: Option<String>,
+    /// How hard the word is to guess
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) difficulty: Option<WordDifficulty>,
+    /// The word's part of speech, e.g., "noun"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) part_of_speech: Option<String>,
+    /// Further hints after `hint`, which the player can reveal one at a time
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) more_hints: Vec<String>,
+}
+
+impl From<Word> for WordWithHint {
+    fn from(word: Word) -> WordWithHint {
+        WordWithHint {
+            word,
+            hint: None,
+            category: None,
+            difficulty: None,
+            part_of_speech: None,
+            more_hints: Vec::new(),
+        }
+    }
+}
+
+/// One of the builtin word lists, stored in the binary as the compressed
+/// records written by the build script, and decoded into a table of entries
+/// the first time it's used
+struct BuiltinTable {
+    compressed: &'static [u8],
+    entries: OnceLock<Vec<BuiltinEntry>>,
+}
+
+impl BuiltinTable {
+    /// The character that ends each record
+    const RECORD_SEP: char = '\x1E';
+
+    /// The character that separates the fields of a record
+    const UNIT_SEP: char = '\x1F';
+
+    const fn new(compressed: &'static [u8]) -> BuiltinTable {
+        BuiltinTable {
+            compressed,
+            entries: OnceLock::new(),
+        }
+    }
+
+    /// Returns the entries of the list, decompressing them on first use
+    fn entries(&'static self) -> &'static [BuiltinEntry] {
+        self.entries.get_or_init(|| {
+            let data = miniz_oxide::inflate::decompress_to_vec(self.compressed)
+                .expect("decompressing builtin word list should not fail");
+            // The entries borrow from the decompressed text, which is kept
+            // for the rest of the program's run, like the table itself
+            let text = String::from_utf8(data)
+                .expect("builtin word list should be UTF-8")
+                .leak();
+            text.split_terminator(Self::RECORD_SEP)
+                .map(|record| {
+                    let mut fields = record.split(Self::UNIT_SEP);
+                    let mut field = || fields.next().filter(|s| !s.is_empty());
+                    BuiltinEntry {
+                        word: field().unwrap_or_default(),
+                        hint: field(),
+                        category: field(),
+                        difficulty: match field() {
+                            Some("easy") => Some(WordDifficulty::Easy),
+                            Some("medium") => Some(WordDifficulty::Medium),
+                            Some("hard") => Some(WordDifficulty::Hard),
+                            _ => None,
+                        },
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+/// An entry in one of the builtin word lists, decoded from the records
+/// written by the build script.  The build script has already checked that
+/// `word` is nonempty and trimmed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct BuiltinEntry {
+    word: &'static str,
+    hint: Option<&'static str>,
+    category: Option<&'static str>,
+    difficulty: Option<WordDifficulty>,
+}
+
+impl From<&BuiltinEntry> for WordWithHint {
+    fn from(entry: &BuiltinEntry) -> WordWithHint {
+        WordWithHint {
+            word: Word(entry.word.to_owned()),
+            hint: entry.hint.map(String::from),
+            category: entry.category.map(String::from),
+            difficulty: entry.difficulty,
+            part_of_speech: None,
+            more_hints: Vec::new(),
+        }
+    }
+}
+
+/// A difficulty rating given to a word in a word list
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WordDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl WordDifficulty {
+    /// Estimate how hard `word` is to guess, for words that a word list
+    /// doesn't give a rating for.  Words are harder the rarer their letters
+    /// are in English, the shorter they are, and the fewer repeated letters
+    /// they have (as each correct guess then reveals less of the word).
+    pub(crate) fn estimate(word: &Word) -> WordDifficulty {
+        let letters = word
+            .as_ref()
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .map(normalize_char)
+            .collect::<Vec<_>>();
+        let mut distinct = letters.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if distinct.is_empty() {
+            return WordDifficulty::Easy;
+        }
+        // Each component is scaled to the range 0–1000:
+        let rarity = distinct
+            .iter()
+            .map(|&c| 1000 - letter_frequency(c) * 1000 / MAX_LETTER_FREQUENCY)
+            .sum::<usize>()
+            / distinct.len();
+        let uniqueness = distinct.len() * 1000 / letters.len();
+        let shortness = 10usize.saturating_sub(letters.len()).min(6) * 1000 / 6;
+        match (rarity * 5 + uniqueness * 3 + shortness * 2) / 10 {
+            ..=599 => WordDifficulty::Easy,
+            600..=719 => WordDifficulty::Medium,
+            _ => WordDifficulty::Hard,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            WordDifficulty::Easy => "easy",
+            WordDifficulty::Medium => "medium",
+            WordDifficulty::Hard => "hard",
+        }
+    }
+}
+
+impl std::str::FromStr for WordDifficulty {
+    type Err = ParseWordDifficultyError;
+
+    fn from_str(s: &str) -> Result<WordDifficulty, ParseWordDifficultyError> {
+        match s {
+            "easy" => Ok(WordDifficulty::Easy),
+            "medium" => Ok(WordDifficulty::Medium),
+            "hard" => Ok(WordDifficulty::Hard),
+            _ => Err(ParseWordDifficultyError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"word difficulty must be "easy", "medium", or "hard""#)]
+pub(crate) struct ParseWordDifficultyError;
+
+/// The frequencies of the letters A through Z in English text, in tenths of a
+/// percent
+const LETTER_FREQUENCIES: [usize; 26] = [
+    82, 15, 28, 43, 127, 22, 20, 61, 70, 2, 8, 40, 24, 67, 75, 19, 1, 60, 63, 91, 28, 10, 24, 2,
+    20, 1,
+];
+
+/// The frequency of the most common letter, E
+const MAX_LETTER_FREQUENCY: usize = 127;
+
+/// Returns the frequency of the letter `c` (an uppercase letter) in English
+/// text.  Letters outside the English alphabet are treated as never
+/// occurring.
+fn letter_frequency(c: char) -> usize {
+    if c.is_ascii_uppercase() {
+        LETTER_FREQUENCIES[usize::from(c as u8 - b'A')]
+    } else {
+        0
+    }
+}
+
+/// A builtin word list
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Pool {
+    /// The general-purpose word list
+    #[default]
+    Standard,
+    /// A list of simple words for kids
+    Kids,
+    /// Animals from all over the animal kingdom
+    Animals,
+    /// Countries of the world
+    Countries,
+    /// Classic & popular movies
+    Movies,
+    /// Terms from the natural sciences
+    Science,
+}
+
+impl Pool {
+    /// All of the builtin word lists
+    pub(crate) const ALL: [Pool; 6] = [
+        Pool::Standard,
+        Pool::Kids,
+        Pool::Animals,
+        Pool::Countries,
+        Pool::Movies,
+        Pool::Science,
+    ];
+
+    /// Returns the entries of the list
+    fn entries(self) -> &'static [BuiltinEntry] {
+        let table = match self {
+            Pool::Standard => &WORDS,
+            Pool::Kids => &KIDS_WORDS,
+            Pool::Animals => &ANIMALS_WORDS,
+            Pool::Countries => &COUNTRIES_WORDS,
+            Pool::Movies => &MOVIES_WORDS,
+            Pool::Science => &SCIENCE_WORDS,
+        };
+        table.entries()
+    }
+
+    /// Returns the name by which the list is selected
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Pool::Standard => "standard",
+            Pool::Kids => "kids",
+            Pool::Animals => "animals",
+            Pool::Countries => "countries",
+            Pool::Movies => "movies",
+            Pool::Science => "science",
+        }
+    }
+
+    /// Returns a short description of the list's contents
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Pool::Standard => "A general mix of animals, gems, fruits & vegetables, and more",
+            Pool::Kids => "Simple words for kids",
+            Pool::Animals => "Animals, with their class as the hint",
+            Pool::Countries => "Countries, with their continent as the hint",
+            Pool::Movies => "Movie titles, with their year of release as the hint",
+            Pool::Science => "Science terms, with their field as the hint",
+        }
+    }
+
+    /// Returns the complete contents of the builtin list
+    pub(crate) fn list(self) -> WordList {
+        self.entries().iter().map(WordWithHint::from).collect()
+    }
+}
+
+impl std::str::FromStr for Pool {
+    type Err = ParsePoolError;
+
+    fn from_str(s: &str) -> Result<Pool, ParsePoolError> {
+        Pool::ALL
+            .into_iter()
+            .find(|pool| pool.name() == s)
+            .ok_or(ParsePoolError)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"word list must be "standard", "kids", "animals", "countries", "movies", or "science""#)]
+pub(crate) struct ParsePoolError;
+
+/// A builtin word list with its words spelled according to a locale
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub(crate) struct BuiltinList {
+    pub(crate) pool: Pool,
+    pub(crate) locale: Locale,
+}
+
+impl BuiltinList {
+    /// Returns the complete contents of the builtin list
+    pub(crate) fn list(self) -> WordList {
+        self.pool.entries().iter().map(|e| self.entry(e)).collect()
+    }
+
+    /// Try to select an entry accepted by `filter` by indexing into the
+    /// list's table at random, without building the whole list.  Returns
+    /// `None` if no acceptable entry was found within a limited number of
+    /// tries.
+    fn sample<R: Rng + ?Sized>(self, filter: &WordFilter, rng: &mut R) -> Option<WordWithHint> {
+        let entries = self.pool.entries();
+        if entries.is_empty() {
+            return None;
+        }
+        (0..BUILTIN_ATTEMPTS)
+            .map(|_| self.entry(&entries[rng.gen_range(0..entries.len())]))
+            .find(|entry| filter.accepts(entry))
+    }
+
+    /// Convert an entry of the list's table, respelling it for the locale
+    fn entry(self, entry: &BuiltinEntry) -> WordWithHint {
+        let mut entry = WordWithHint::from(entry);
+        if let Some(spelling) = self.locale.respell(entry.word.as_ref()) {
+            entry.word = Word(spelling.to_owned());
+        }
+        entry
+    }
 }
 
+/// Criteria that a word selected at random from a word list must meet
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct WordFilter {
+    /// Minimum length of the word in characters
+    pub(crate) min_length: Option<usize>,
+    /// Maximum length of the word in characters
+    pub(crate) max_length: Option<usize>,
+    /// Category that the word must belong to, compared case-insensitively
+    pub(crate) category: Option<String>,
+    /// Difficulty rating that the word must have, as estimated by
+    /// [`WordDifficulty::estimate()`] if the word list doesn't give one
+    pub(crate) difficulty: Option<WordDifficulty>,
+    /// Pattern that the word must match
+    pub(crate) pattern: Option<WordPattern>,
+    /// Whether to accept words on the profanity deny-list
+    pub(crate) allow_profanity: bool,
+}
+
+impl WordFilter {
+    /// Returns true iff `word` meets the filter's criteria
+    pub(crate) fn accepts(&self, word: &WordWithHint) -> bool {
+        let length = word.word.char_len();
+        self.min_length.is_none_or(|min| min <= length)
+            && self.max_length.is_none_or(|max| length <= max)
+            && self.category.as_ref().is_none_or(|category| {
+                word.category
+                    .as_ref()
+                    .is_some_and(|c| c.to_lowercase() == category.to_lowercase())
+            })
+            && self.difficulty.is_none_or(|difficulty| {
+                word.difficulty
+                    .unwrap_or_else(|| WordDifficulty::estimate(&word.word))
+                    == difficulty
+            })
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&word.word))
+            && (self.allow_profanity || !is_profane(word.word.as_ref()))
+    }
+}
+
+/// A regular expression that a word must match somewhere within it, ignoring
+/// case
+#[derive(Clone, Debug)]
+pub(crate) struct WordPattern(Regex);
+
+impl WordPattern {
+    fn is_match(&self, word: &Word) -> bool {
+        self.0.is_match(word.as_ref())
+    }
+}
+
+impl PartialEq for WordPattern {
+    fn eq(&self, other: &WordPattern) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for WordPattern {}
+
+impl std::str::FromStr for WordPattern {
+    type Err = regex::Error;
+
+    fn from_str(s: &str) -> Result<WordPattern, regex::Error> {
+        RegexBuilder::new(s)
+            .case_insensitive(true)
+            .build()
+            .map(WordPattern)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) enum WordSource {
-    #[default]
-    Builtin,
-    Fixed(Word),
-    File(InputArg),
+    Builtin(BuiltinList),
+    /// A word (and hint) given on the command line
+    Fixed(WordWithHint),
+    /// A word typed in at a prompt before the game starts
+    Prompt,
+    /// A word read from the first line of a file or standard input
+    SecretFile(InputArg),
+    File(InputArg, ListOptions),
+    /// The output of a shell command
+    Command(String, ListOptions),
+    #[cfg(feature = "words-url")]
+    Url(String, ListOptions),
+    /// The word of the day from the given builtin word list.  The word
+    /// depends only on the date, so the [`WordFilter`] passed to
+    /// [`WordSource::fetch()`] is ignored.
+    OfTheDay(BuiltinList),
+    /// A random word from an online dictionary, falling back to the given
+    /// builtin word list if the dictionary can't be used
+    #[cfg(feature = "dictionary")]
+    Dictionary(BuiltinList),
 }
 
 impl WordSource {
-    pub(crate) fn fetch(self) -> anyhow::Result<WordWithHint> {
+    /// Obtain a secret word from the source.  If the source is a word list, a
+    /// word is selected at random from those accepted by `filter`, and the
+    /// complete list is returned alongside it.  `rng` is used for the
+    /// selection and then handed back for selecting any further words.
+    pub(crate) fn fetch(self, filter: &WordFilter, mut rng: StdRng) -> anyhow::Result<Fetched> {
         match self {
-            WordSource::Builtin => Ok(word_from_csv(WORDS)
-                .expect("builtin wordlist should be nonempty")
-                .expect("reading builtin wordlist should not fail")),
-            WordSource::Fixed(word) => Ok(WordWithHint { word, hint: None }),
-            WordSource::File(infile) => {
-                let reader = infile.open().context("failed to open words file")?;
-                match word_from_csv(reader) {
-                    Some(r) => r.context("failed to read words file"),
-                    None => anyhow::bail!("No words found in words file"),
+            WordSource::Builtin(builtin) => {
+                let list = LazyList::from(builtin);
+                let Some(secret) = builtin
+                    .sample(filter, &mut rng)
+                    .or_else(|| list.get().choose(filter, &mut rng).cloned())
+                else {
+                    anyhow::bail!("No words in builtin word list meet the selection criteria");
+                };
+                Ok(Fetched {
+                    secret,
+                    list: Some(list),
+                    filter: filter.clone(),
+                    rng,
+                    source: format!("the builtin {:?} word list", builtin.pool.name()),
+                })
+            }
+            WordSource::OfTheDay(builtin) => {
+                let list = builtin.list();
+                let Some(secret) = list.word_of_the_day(crate::state::today()).cloned() else {
+                    anyhow::bail!("No word of the day in empty builtin word list");
+                };
+                Ok(Fetched {
+                    secret,
+                    list: Some(list.into()),
+                    filter: filter.clone(),
+                    rng,
+                    source: String::from("the word of the day"),
+                })
+            }
+            WordSource::Prompt => loop {
+                let Some(input) = read_hidden("Enter the secret word (it won't be shown): ")?
+                else {
+                    anyhow::bail!("No secret word entered");
+                };
+                match input.parse::<Word>() {
+                    Ok(word) => break WordSource::Fixed(word.into()).fetch(filter, rng),
+                    Err(e) => eprint!("{e}\r\n"),
+                }
+            },
+            WordSource::SecretFile(infile) => {
+                let src = infile
+                    .read_to_string()
+                    .context("failed to read secret word")?;
+                let word = src
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .parse::<Word>()
+                    .context("invalid secret word")?;
+                WordSource::Fixed(word.into()).fetch(filter, rng)
+            }
+            WordSource::Fixed(secret) => Ok(Fetched {
+                secret,
+                list: None,
+                filter: filter.clone(),
+                rng,
+                source: String::from("a word chosen in advance"),
+            }),
+            WordSource::File(infile, options) => {
+                let source = format!("the word list {infile}");
+                if let Some(path) = infile.path_ref().filter(|_| options.indexed) {
+                    let index = WordIndex::open(path, options.format)?;
+                    if let Some(secret) = index.choose(filter, options, &mut rng)? {
+                        return Ok(Fetched {
+                            secret,
+                            list: None,
+                            filter: filter.clone(),
+                            rng,
+                            source,
+                        });
+                    }
+                    // The filter rejected every entry sampled, so fall back
+                    // to searching the whole file.
                 }
+                let list = WordList::from_file(infile, options)?;
+                let Some(secret) = list.choose(filter, &mut rng).cloned() else {
+                    anyhow::bail!("No suitable words found in words file");
+                };
+                Ok(Fetched {
+                    secret,
+                    list: Some(list.into()),
+                    filter: filter.clone(),
+                    rng,
+                    source,
+                })
+            }
+            WordSource::Command(command, options) => {
+                let list = WordList::from_command(&command, options)?;
+                let Some(secret) = list.choose(filter, &mut rng).cloned() else {
+                    anyhow::bail!("No suitable words found in output of {command:?}");
+                };
+                Ok(Fetched {
+                    secret,
+                    list: Some(list.into()),
+                    filter: filter.clone(),
+                    rng,
+                    source: format!("the output of {command:?}"),
+                })
+            }
+            #[cfg(feature = "dictionary")]
+            WordSource::Dictionary(fallback) => match crate::dictionary::random_word(filter) {
+                Ok(secret) => Ok(Fetched {
+                    secret,
+                    list: None,
+                    filter: filter.clone(),
+                    rng,
+                    source: String::from("an online dictionary"),
+                }),
+                Err(_) => WordSource::Builtin(fallback).fetch(filter, rng),
+            },
+            #[cfg(feature = "words-url")]
+            WordSource::Url(url, options) => {
+                let list = WordList::from_url(&url, options)?;
+                let Some(secret) = list.choose(filter, &mut rng).cloned() else {
+                    anyhow::bail!("No suitable words found in word list at {url}");
+                };
+                Ok(Fetched {
+                    secret,
+                    list: Some(list.into()),
+                    filter: filter.clone(),
+                    rng,
+                    source: format!("the word list at {url}"),
+                })
             }
         }
     }
 }
 
-fn iter_words<R: std::io::Read>(reader: R) -> csv::DeserializeRecordsIntoIter<R, WordWithHint> {
+/// A secret word obtained from a [`WordSource`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Fetched {
+    pub(crate) secret: WordWithHint,
+    /// The complete word list that the secret word was selected from, if any
+    pub(crate) list: Option<LazyList>,
+    /// The criteria that the secret word was selected by, for use in
+    /// selecting further words from `list`
+    pub(crate) filter: WordFilter,
+    /// The random number generator to use for selecting further words
+    pub(crate) rng: StdRng,
+    /// A description of where the secret word came from, for the help
+    /// overlay
+    pub(crate) source: String,
+}
+
+impl Fetched {
+    /// Give the secret word and the words in the word list any hints for
+    /// them in `hints` that they lack
+    pub(crate) fn fill_hints(&mut self, hints: &Hints) {
+        hints.fill(&mut self.secret);
+        if let Some(list) = &mut self.list {
+            list.fill_hints(hints);
+        }
+    }
+}
+
+/// A word list that, if it's a builtin list, isn't built until it's first
+/// needed, so that games that only need a single random word don't pay for
+/// converting every entry of the list
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LazyList {
+    list: OnceCell<WordList>,
+    /// The builtin list to build `list` from, if it wasn't given up front
+    builtin: Option<BuiltinList>,
+    /// Hints to fill in once the list is built
+    hints: Option<Hints>,
+}
+
+impl LazyList {
+    /// Returns the word list, building it if it hasn't been built yet
+    pub(crate) fn get(&self) -> &WordList {
+        self.list.get_or_init(|| {
+            let mut list = self.builtin.map(BuiltinList::list).unwrap_or_default();
+            if let Some(hints) = &self.hints {
+                list.fill_hints(hints);
+            }
+            list
+        })
+    }
+
+    /// Give the words in the list any hints for them in `hints` that they
+    /// lack
+    fn fill_hints(&mut self, hints: &Hints) {
+        match self.list.get_mut() {
+            Some(list) => list.fill_hints(hints),
+            None => self.hints = Some(hints.clone()),
+        }
+    }
+}
+
+impl From<WordList> for LazyList {
+    fn from(list: WordList) -> LazyList {
+        LazyList {
+            list: OnceCell::from(list),
+            builtin: None,
+            hints: None,
+        }
+    }
+}
+
+impl From<BuiltinList> for LazyList {
+    fn from(builtin: BuiltinList) -> LazyList {
+        LazyList {
+            list: OnceCell::new(),
+            builtin: Some(builtin),
+            hints: None,
+        }
+    }
+}
+
+/// Hints for words, maintained separately from the word list that the words
+/// are selected from
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Hints(HashMap<String, WordWithHint>);
+
+impl Hints {
+    /// Read hints from a word list in any format.  Words are matched
+    /// case-insensitively; if a word appears more than once, the first entry
+    /// for it is used.
+    pub(crate) fn from_file(infile: InputArg) -> anyhow::Result<Hints> {
+        let list =
+            WordList::from_file(infile, ListOptions::default()).context("failed to read hints")?;
+        let mut hints = HashMap::new();
+        for entry in list.0 {
+            hints
+                .entry(entry.word.as_ref().to_lowercase())
+                .or_insert(entry);
+        }
+        Ok(Hints(hints))
+    }
+
+    /// If `entry` has no hints, give it the hints for its word, if any
+    fn fill(&self, entry: &mut WordWithHint) {
+        if entry.hint.is_some() || !entry.more_hints.is_empty() {
+            return;
+        }
+        if let Some(found) = self.0.get(&entry.word.as_ref().to_lowercase()) {
+            entry.hint.clone_from(&found.hint);
+            entry.more_hints.clone_from(&found.more_hints);
+        }
+    }
+}
+
+/// A set of words considered valid, used to keep players from choosing
+/// made-up secret words
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Lexicon(HashSet<String>);
+
+impl Lexicon {
+    /// Read a dictionary file consisting of one word per line, such as
+    /// `/usr/share/dict/words`.  Leading & trailing whitespace is ignored, as
+    /// are blank lines.
+    pub(crate) fn from_file(infile: InputArg) -> anyhow::Result<Lexicon> {
+        let src = infile
+            .read_to_string()
+            .with_context(|| format!("failed to read dictionary {infile:#}"))?;
+        Ok(src.lines().collect())
+    }
+
+    /// Returns true iff `word` is in the lexicon, ignoring case
+    pub(crate) fn contains(&self, word: &Word) -> bool {
+        self.0.contains(&word.as_ref().to_lowercase())
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Lexicon {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Lexicon {
+        Lexicon(
+            iter.into_iter()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_lowercase)
+                .collect(),
+        )
+    }
+}
+
+/// The contents of a word list
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct WordList(Vec<WordWithHint>);
+
+impl WordList {
+    /// Give the words in the list any hints for them in `hints` that they
+    /// lack
+    fn fill_hints(&mut self, hints: &Hints) {
+        for entry in &mut self.0 {
+            hints.fill(entry);
+        }
+    }
+
+    fn from_csv<R: std::io::Read>(reader: R) -> Result<WordList, csv::Error> {
+        iter_words(reader).collect::<Result<_, _>>().map(WordList)
+    }
+
+    /// Parse a plain-text word list consisting of one word per line,
+    /// optionally followed by tab-separated fields giving the word's hint,
+    /// category, and difficulty.  Blank lines and lines starting with `#` are
+    /// ignored.
+    fn from_plain(src: &str) -> Result<WordList, PlainLineError> {
+        plain_records(src)
+            .into_iter()
+            .map(|Record { line, entry }| entry.map_err(|source| PlainLineError { line, source }))
+            .collect::<Result<_, _>>()
+            .map(WordList)
+    }
+
+    /// Parse a word list from a TOML document containing an array of
+    /// `[[words]]` tables
+    fn from_toml(src: &str) -> Result<WordList, toml::de::Error> {
+        toml::from_str::<TomlWordList>(src).map(|doc| WordList(doc.words))
+    }
+
+    /// Parse a word list in the given format from a string one entry at a
+    /// time, so that a malformed entry doesn't prevent reading the rest.  A
+    /// TOML document that cannot be parsed at all is reported as a single
+    /// erroneous entry.
+    pub(crate) fn records(src: &str, format: WordFormat) -> Vec<Record> {
+        match format.resolve(src) {
+            WordFormat::Plain => plain_records(src),
+            WordFormat::Toml => toml_records(src),
+            _ => csv_records(src),
+        }
+    }
+
+    /// Parse a word list from a string according to `options`
+    pub(crate) fn parse(src: &str, options: ListOptions) -> anyhow::Result<WordList> {
+        let mut list = WordList::parse_entries(src, options)?;
+        if options.ranked {
+            let total = list.0.len();
+            list.0 = options.tier.select(list.0);
+            if list.0.is_empty() {
+                anyhow::bail!(
+                    "no words in the {} frequency tier (word list has only {total} words)",
+                    options.tier.as_str()
+                );
+            }
+        }
+        if options.skip_unguessable {
+            let before = list.0.len();
+            list.0.retain(|w| w.word.has_letters_in(ASCII_ALPHABET));
+            let skipped = before - list.0.len();
+            if list.0.is_empty() {
+                anyhow::bail!("no words with letters to guess found ({skipped} words skipped)");
+            }
+            if skipped > 0 {
+                eprintln!(
+                    "Warning: skipped {skipped} word{} with no letters to guess in word list",
+                    if skipped == 1 { "" } else { "s" }
+                );
+            }
+        }
+        Ok(list)
+    }
+
+    /// Parse the entries of a word list from a string according to
+    /// `options.format` and `options.lenient`
+    fn parse_entries(src: &str, options: ListOptions) -> anyhow::Result<WordList> {
+        if options.lenient {
+            let mut words = Vec::new();
+            let mut skipped = 0;
+            for record in WordList::records(src, options.format) {
+                match record.entry {
+                    Ok(word) => words.push(word),
+                    Err(_) => skipped += 1,
+                }
+            }
+            if words.is_empty() {
+                anyhow::bail!("no valid entries found ({skipped} malformed entries skipped)");
+            }
+            if skipped > 0 {
+                eprintln!(
+                    "Warning: skipped {skipped} malformed entr{} in word list",
+                    if skipped == 1 { "y" } else { "ies" }
+                );
+            }
+            return Ok(WordList(words));
+        }
+        match options.format.resolve(src) {
+            WordFormat::Plain => Ok(WordList::from_plain(src)?),
+            WordFormat::Toml => Ok(WordList::from_toml(src)?),
+            _ => Ok(WordList::from_csv(src.as_bytes())?),
+        }
+    }
+
+    /// Read a word list from a file or standard input
+    pub(crate) fn from_file(infile: InputArg, options: ListOptions) -> anyhow::Result<WordList> {
+        let src = infile
+            .read_to_string()
+            .context("failed to read words file")?;
+        WordList::parse(&src, options).context("failed to parse words file")
+    }
+
+    /// Run `command` in the system shell and read a word list from its
+    /// standard output
+    pub(crate) fn from_command(command: &str, options: ListOptions) -> anyhow::Result<WordList> {
+        let output = shell(command)
+            .stdin(Stdio::null())
+            .stderr(Stdio::inherit())
+            .output()
+            .with_context(|| format!("failed to run {command:?}"))?;
+        if !output.status.success() {
+            anyhow::bail!("{command:?} failed: {}", output.status);
+        }
+        let src = String::from_utf8(output.stdout)
+            .with_context(|| format!("output of {command:?} was not valid UTF-8"))?;
+        WordList::parse(&src, options)
+            .with_context(|| format!("failed to parse word list from {command:?}"))
+    }
+
+    /// Download a word list from an HTTP(S) URL, or use a cached copy if it's
+    /// still current
+    #[cfg(feature = "words-url")]
+    pub(crate) fn from_url(url: &str, options: ListOptions) -> anyhow::Result<WordList> {
+        let src = crate::remote::fetch(url)?;
+        WordList::parse(&src, options)
+            .with_context(|| format!("failed to parse word list from {url}"))
+    }
+
+    /// Select a word at random from those in the list accepted by `filter`
+    pub(crate) fn choose<R: Rng + ?Sized>(
+        &self,
+        filter: &WordFilter,
+        rng: &mut R,
+    ) -> Option<&WordWithHint> {
+        self.0.iter().filter(|w| filter.accepts(w)).choose(rng)
+    }
+
+    /// Returns the words in the list accepted by `filter`, in a random order
+    pub(crate) fn shuffled<R: Rng + ?Sized>(
+        &self,
+        filter: &WordFilter,
+        rng: &mut R,
+    ) -> Vec<WordWithHint> {
+        let mut words = self
+            .0
+            .iter()
+            .filter(|w| filter.accepts(w))
+            .cloned()
+            .collect::<Vec<_>>();
+        words.shuffle(rng);
+        words
+    }
+
+    /// Select the word of the day for `date`.  The word depends only on the
+    /// date and the contents of the list, so everyone using the same list gets
+    /// the same word on the same day.
+    pub(crate) fn word_of_the_day(&self, date: Date) -> Option<&WordWithHint> {
+        let len = u64::try_from(self.0.len()).ok()?;
+        let day = u64::from(date.to_julian_day().unsigned_abs());
+        let i = splitmix64(day).checked_rem(len)?;
+        self.0.get(usize::try_from(i).ok()?)
+    }
+
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, WordWithHint> {
+        self.0.iter()
+    }
+
+    /// Write out the list as a word list document in the given format, which
+    /// is CSV if `format` is `Auto`
+    pub(crate) fn render(&self, format: WordFormat) -> anyhow::Result<String> {
+        match format {
+            WordFormat::Toml => Ok(toml::to_string(&TomlWordList {
+                words: self.0.clone(),
+            })?),
+            WordFormat::Plain => {
+                let mut out = String::new();
+                for w in &self.0 {
+                    let fields = entry_fields(w)
+                        .into_iter()
+                        .map(|s| s.replace(['\t', '\n'], " "))
+                        .collect::<Vec<_>>();
+                    out.push_str(&fields.join("\t"));
+                    out.push('\n');
+                }
+                Ok(out)
+            }
+            WordFormat::Auto | WordFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .flexible(true)
+                    .from_writer(Vec::new());
+                for w in &self.0 {
+                    writer.write_record(entry_fields(w))?;
+                }
+                let buf = writer.into_inner().context("failed to write CSV")?;
+                Ok(String::from_utf8(buf)?)
+            }
+        }
+    }
+}
+
+impl FromIterator<WordWithHint> for WordList {
+    fn from_iter<I: IntoIterator<Item = WordWithHint>>(iter: I) -> WordList {
+        WordList(iter.into_iter().collect())
+    }
+}
+
+/// Returns the fields of a word list entry for writing out as CSV or plain
+/// text, omitting trailing empty fields
+fn entry_fields(entry: &WordWithHint) -> Vec<&str> {
+    let mut fields = vec![
+        entry.word.as_ref(),
+        entry.hint.as_deref().unwrap_or_default(),
+        entry.category.as_deref().unwrap_or_default(),
+        entry.difficulty.map_or("", WordDifficulty::as_str),
+        entry.part_of_speech.as_deref().unwrap_or_default(),
+    ];
+    fields.extend(entry.more_hints.iter().map(String::as_str));
+    while fields.last().is_some_and(|s| s.is_empty()) {
+        fields.pop();
+    }
+    fields
+}
+
+/// Returns a [`Command`] for running `command` in the system shell
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Returns a [`Command`] for running `command` in the system shell
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+/// Scramble the bits of `x` using the `SplitMix64` mixing function, so that
+/// consecutive inputs give unrelated outputs
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Options for reading a word list from a file, command, or URL
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ListOptions {
+    pub(crate) format: WordFormat,
+    /// Whether to skip malformed entries rather than failing
+    pub(crate) lenient: bool,
+    /// Whether to skip words that have no letters from the alphabet and thus
+    /// can't be played
+    pub(crate) skip_unguessable: bool,
+    /// Whether to pick words from a file via a sidecar index (see
+    /// [`WordIndex`]) instead of reading the whole file
+    pub(crate) indexed: bool,
+    /// Whether the words are ordered from most to least common, in which
+    /// case only the words in `tier` are used
+    pub(crate) ranked: bool,
+    /// The frequency tier to take words from if the list is ranked
+    pub(crate) tier: FrequencyTier,
+}
+
+/// A range of ranks in a word list ordered from most to least common
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FrequencyTier {
+    /// The whole list
+    #[default]
+    All,
+    /// The most common words, which almost everyone knows
+    Common,
+    /// The words between the common ones and the rare tail
+    Uncommon,
+    /// The least common words
+    Rare,
+}
+
+impl FrequencyTier {
+    /// The number of words at the top of a ranked list that are common
+    const COMMON_SIZE: usize = 2000;
+
+    /// The rank at which the rare tail of a ranked list starts
+    const RARE_START: usize = 10000;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            FrequencyTier::All => "all",
+            FrequencyTier::Common => "common",
+            FrequencyTier::Uncommon => "uncommon",
+            FrequencyTier::Rare => "rare",
+        }
+    }
+
+    /// Returns the words in the tier from `words`, which are ordered from
+    /// most to least common
+    fn select(self, mut words: Vec<WordWithHint>) -> Vec<WordWithHint> {
+        let (start, end) = match self {
+            FrequencyTier::All => (0, words.len()),
+            FrequencyTier::Common => (0, FrequencyTier::COMMON_SIZE),
+            FrequencyTier::Uncommon => (FrequencyTier::COMMON_SIZE, FrequencyTier::RARE_START),
+            FrequencyTier::Rare => (FrequencyTier::RARE_START, words.len()),
+        };
+        words.truncate(end);
+        words.drain(..start.min(words.len()));
+        words
+    }
+}
+
+/// The format of a words file
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum WordFormat {
+    /// Determine the format from the file's contents
+    #[default]
+    Auto,
+    /// One word per line, optionally followed by comma-separated fields
+    /// giving the word's hint, category, and difficulty
+    Csv,
+    /// One word per line, optionally followed by tab-separated fields giving
+    /// the word's hint, category, and difficulty, with blank lines and `#`
+    /// comments ignored
+    Plain,
+    /// A TOML document with a `[[words]]` table for each word
+    Toml,
+}
+
+impl WordFormat {
+    /// Returns the format to parse `src` with.  If the format is `Auto`, the
+    /// file is taken to be TOML if any line is a `[[words]]` table header,
+    /// plain text if any line is a `#` comment or contains a tab, and CSV
+    /// otherwise.
+    fn resolve(self, src: &str) -> WordFormat {
+        if self != WordFormat::Auto {
+            self
+        } else if src.lines().any(|ln| ln.trim() == "[[words]]") {
+            WordFormat::Toml
+        } else if src
+            .lines()
+            .any(|ln| ln.trim_start().starts_with('#') || ln.contains('\t'))
+        {
+            WordFormat::Plain
+        } else {
+            WordFormat::Csv
+        }
+    }
+}
+
+impl std::str::FromStr for WordFormat {
+    type Err = ParseWordFormatError;
+
+    fn from_str(s: &str) -> Result<WordFormat, ParseWordFormatError> {
+        match s {
+            "auto" => Ok(WordFormat::Auto),
+            "csv" => Ok(WordFormat::Csv),
+            "plain" => Ok(WordFormat::Plain),
+            "toml" => Ok(WordFormat::Toml),
+            _ => Err(ParseWordFormatError),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error(r#"word list format must be "auto", "csv", "plain", or "toml""#)]
+pub(crate) struct ParseWordFormatError;
+
+/// The structure of a TOML word list
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+struct TomlWordList {
+    #[serde(default)]
+    words: Vec<WordWithHint>,
+}
+
+/// The structure of a TOML word list, with each entry left unparsed
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct TomlRecords {
+    #[serde(default)]
+    words: Vec<toml::Spanned<toml::Value>>,
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("invalid entry on line {line}")]
+pub(crate) struct PlainLineError {
+    line: usize,
+    source: RecordError,
+}
+
+/// An entry read from a word list by [`WordList::records()`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Record {
+    /// The line of the word list on which the entry starts
+    pub(crate) line: usize,
+    pub(crate) entry: Result<WordWithHint, RecordError>,
+}
+
+/// An error in an individual entry of a word list
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub(crate) enum RecordError {
+    #[error(transparent)]
+    Word(#[from] ParseWordError),
+    #[error(transparent)]
+    Difficulty(#[from] ParseWordDifficultyError),
+    /// The entry could not be parsed in the word list's format
+    #[error("{0}")]
+    Malformed(String),
+}
+
+fn plain_records(src: &str) -> Vec<Record> {
+    src.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim();
+            !(trimmed.is_empty() || trimmed.starts_with('#'))
+        })
+        .map(|(i, line)| Record {
+            line: i + 1,
+            entry: entry_from_fields(&line.split('\t').collect::<Vec<_>>()),
+        })
+        .collect()
+}
+
+fn csv_records(src: &str) -> Vec<Record> {
+    csv_reader(src.as_bytes())
+        .into_records()
+        .map(|r| match r {
+            Ok(record) => Record {
+                line: record.position().map_or(0, csv_line),
+                entry: entry_from_fields(&record.iter().collect::<Vec<_>>()),
+            },
+            Err(e) => Record {
+                line: e.position().map_or(0, csv_line),
+                entry: Err(RecordError::Malformed(e.to_string())),
+            },
+        })
+        .collect()
+}
+
+fn csv_line(pos: &csv::Position) -> usize {
+    usize::try_from(pos.line()).unwrap_or(usize::MAX)
+}
+
+fn toml_records(src: &str) -> Vec<Record> {
+    let line_of = |offset: usize| src[..offset].matches('\n').count() + 1;
+    match toml::from_str::<TomlRecords>(src) {
+        Ok(doc) => doc
+            .words
+            .into_iter()
+            .map(|value| Record {
+                line: line_of(value.span().start),
+                entry: WordWithHint::deserialize(value.into_inner())
+                    .map_err(|e| RecordError::Malformed(e.message().to_owned())),
+            })
+            .collect(),
+        Err(e) => vec![Record {
+            line: e.span().map_or(1, |span| line_of(span.start)),
+            entry: Err(RecordError::Malformed(e.message().to_owned())),
+        }],
+    }
+}
+
+/// Construct a word list entry from its fields: the word, followed by its
+/// hint, category, difficulty, part of speech, and any further hints, any of
+/// which may be absent or empty
+fn entry_from_fields(fields: &[&str]) -> Result<WordWithHint, RecordError> {
+    let word = fields
+        .first()
+        .copied()
+        .unwrap_or_default()
+        .parse::<Word>()?;
+    let optional = |i: usize| {
+        fields
+            .get(i)
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    let difficulty = optional(3)
+        .map(|s| s.parse::<WordDifficulty>())
+        .transpose()?;
+    Ok(WordWithHint {
+        word,
+        hint: optional(1),
+        category: optional(2),
+        difficulty,
+        part_of_speech: optional(4),
+        more_hints: (5..fields.len()).filter_map(optional).collect(),
+    })
+}
+
+fn csv_reader<R: std::io::Read>(reader: R) -> csv::Reader<R> {
     csv::ReaderBuilder::new()
         .flexible(true)
         .has_headers(false)
         .trim(csv::Trim::All)
         .from_reader(reader)
-        .into_deserialize::<WordWithHint>()
 }
 
-fn word_from_csv<R: std::io::Read>(reader: R) -> Option<Result<WordWithHint, csv::Error>> {
-    iter_words(reader).choose(&mut rand::thread_rng())
+fn iter_words<R: std::io::Read>(reader: R) -> csv::DeserializeRecordsIntoIter<R, WordWithHint> {
+    csv_reader(reader).into_deserialize::<WordWithHint>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_filter_length() {
+        let filter = WordFilter {
+            min_length: Some(4),
+            max_length: Some(5),
+            ..WordFilter::default()
+        };
+        let list = Pool::Standard.list();
+        for _ in 0..20 {
+            let word = list.choose(&filter, &mut rand::thread_rng()).unwrap();
+            assert!((4..=5).contains(&word.word.char_len()));
+        }
+    }
+
+    #[test]
+    fn test_choose_seeded() {
+        use rand::SeedableRng;
+        let list = Pool::Standard.list();
+        let filter = WordFilter::default();
+        let draw = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..5)
+                .map(|_| list.choose(&filter, &mut rng).unwrap().word.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(42), draw(42));
+        assert_ne!(draw(42), draw(43));
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let src = "cat,Pet\n\"\",Nothing\ndog,,,tough\ncow\n";
+        assert!(WordList::parse(src, ListOptions::default()).is_err());
+        let options = ListOptions {
+            lenient: true,
+            ..ListOptions::default()
+        };
+        let list = WordList::parse(src, options).unwrap();
+        assert_eq!(
+            list.iter().map(|w| w.word.as_ref()).collect::<Vec<_>>(),
+            ["cat", "cow"]
+        );
+        assert!(WordList::parse("\"\"\n", options).is_err());
+    }
+
+    #[test]
+    fn test_parse_skip_unguessable() {
+        let src = "cat\n123\nσοφία\n\"R2-D2\"\n";
+        assert_eq!(
+            WordList::parse(src, ListOptions::default())
+                .unwrap()
+                .iter()
+                .count(),
+            4
+        );
+        let options = ListOptions {
+            skip_unguessable: true,
+            ..ListOptions::default()
+        };
+        let list = WordList::parse(src, options).unwrap();
+        assert_eq!(
+            list.iter().map(|w| w.word.as_ref()).collect::<Vec<_>>(),
+            ["cat", "R2-D2"]
+        );
+        assert!(WordList::parse("42\n", options).is_err());
+    }
+
+    #[test]
+    fn test_filter_profanity() {
+        let list = WordList::from_plain("bullshit\tNonsense\n").unwrap();
+        assert!(list
+            .choose(&WordFilter::default(), &mut rand::thread_rng())
+            .is_none());
+        let filter = WordFilter {
+            allow_profanity: true,
+            ..WordFilter::default()
+        };
+        assert!(list.choose(&filter, &mut rand::thread_rng()).is_some());
+    }
+
+    #[test]
+    fn test_builtin_lists_clean() {
+        for pool in Pool::ALL {
+            for w in pool.list().iter() {
+                assert!(!is_profane(w.word.as_ref()), "{:?} is profane", w.word);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        let src = concat!(
+            "elephant,\"Large, grey\",animals,easy\n",
+            "giraffe\n",
+            "zebra,,animals\n",
+            "mammoth,Extinct,,,noun,Woolly,Tusks\n",
+        );
+        let list = WordList::from_csv(src.as_bytes()).unwrap();
+        assert_eq!(list.render(WordFormat::Csv).unwrap(), src);
+        assert_eq!(
+            list.render(WordFormat::Plain).unwrap(),
+            concat!(
+                "elephant\tLarge, grey\tanimals\teasy\n",
+                "giraffe\n",
+                "zebra\t\tanimals\n",
+                "mammoth\tExtinct\t\t\tnoun\tWoolly\tTusks\n",
+            )
+        );
+        for format in [WordFormat::Csv, WordFormat::Plain, WordFormat::Toml] {
+            let rendered = list.render(format).unwrap();
+            assert_eq!(
+                WordList::parse(&rendered, ListOptions::default()).unwrap(),
+                list
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_hints() {
+        let list = WordList::parse("Cat\ndog\nrat,Squeaks\n", ListOptions::default()).unwrap();
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("hints.csv");
+        std::fs::write(
+            &path,
+            "CAT,Meows,,,,Purrs\ndog,Barks\ndog,Howls\nrat,Pest\n",
+        )
+        .unwrap();
+        let hints = Hints::from_file(InputArg::from_arg(path)).unwrap();
+        let mut fetched = Fetched {
+            secret: list.0[0].clone(),
+            list: Some(list.into()),
+            filter: WordFilter::default(),
+            rng: rand::SeedableRng::seed_from_u64(0),
+            source: String::new(),
+        };
+        fetched.fill_hints(&hints);
+        assert_eq!(fetched.secret.hint.as_deref(), Some("Meows"));
+        assert_eq!(fetched.secret.more_hints, ["Purrs"]);
+        assert_eq!(
+            fetched
+                .list
+                .unwrap()
+                .get()
+                .iter()
+                .map(|w| w.hint.as_deref())
+                .collect::<Vec<_>>(),
+            [Some("Meows"), Some("Barks"), Some("Squeaks")]
+        );
+    }
+
+    #[test]
+    fn test_frequency_tiers() {
+        let src = (1..=10005).map(|i| format!("w{i}\n")).collect::<String>();
+        let tier = |tier| {
+            WordList::parse(
+                &src,
+                ListOptions {
+                    ranked: true,
+                    tier,
+                    ..ListOptions::default()
+                },
+            )
+            .unwrap()
+            .0
+        };
+        let common = tier(FrequencyTier::Common);
+        assert_eq!(common.len(), 2000);
+        assert_eq!(common[0].word.as_ref(), "w1");
+        assert_eq!(common[1999].word.as_ref(), "w2000");
+        let uncommon = tier(FrequencyTier::Uncommon);
+        assert_eq!(uncommon.len(), 8000);
+        assert_eq!(uncommon[0].word.as_ref(), "w2001");
+        let rare = tier(FrequencyTier::Rare);
+        assert_eq!(
+            rare.iter().map(|w| w.word.as_ref()).collect::<Vec<_>>(),
+            ["w10001", "w10002", "w10003", "w10004", "w10005"]
+        );
+        assert_eq!(tier(FrequencyTier::All).len(), 10005);
+        let short = ListOptions {
+            ranked: true,
+            tier: FrequencyTier::Rare,
+            ..ListOptions::default()
+        };
+        assert!(WordList::parse("cat\ndog\n", short).is_err());
+        let unranked = ListOptions {
+            ranked: false,
+            ..short
+        };
+        assert_eq!(WordList::parse("cat\ndog\n", unranked).unwrap().0.len(), 2);
+    }
+
+    #[test]
+    fn test_lexicon() {
+        let lexicon = "apple\n\n  Banana \nice cream\n"
+            .lines()
+            .collect::<Lexicon>();
+        for (word, known) in [
+            ("apple", true),
+            ("APPLE", true),
+            ("banana", true),
+            ("Ice Cream", true),
+            ("applesauce", false),
+            ("app", false),
+        ] {
+            assert_eq!(
+                lexicon.contains(&word.parse::<Word>().unwrap()),
+                known,
+                "{word:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_builtin_list_en_gb() {
+        let builtin = BuiltinList {
+            pool: Pool::Standard,
+            locale: Locale::EnGb,
+        };
+        let words = builtin.list();
+        let words = words.iter().map(|w| w.word.as_ref()).collect::<Vec<_>>();
+        assert!(words.contains(&"courgette"));
+        assert!(!words.contains(&"zucchini"));
+        assert_eq!(words.len(), Pool::Standard.list().iter().count());
+    }
+
+    #[test]
+    fn test_shuffled() {
+        let filter = WordFilter {
+            min_length: Some(4),
+            max_length: Some(5),
+            ..WordFilter::default()
+        };
+        let list = Pool::Standard.list();
+        let mut shuffled = list.shuffled(&filter, &mut rand::thread_rng());
+        let mut expected = list
+            .iter()
+            .filter(|w| filter.accepts(w))
+            .cloned()
+            .collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        shuffled.sort_by(|a, b| a.word.as_ref().cmp(b.word.as_ref()));
+        expected.sort_by(|a, b| a.word.as_ref().cmp(b.word.as_ref()));
+        assert_eq!(shuffled, expected);
+    }
+
+    #[test]
+    fn test_filter_excludes_all() {
+        let filter = WordFilter {
+            min_length: Some(100),
+            max_length: None,
+            ..WordFilter::default()
+        };
+        let list = Pool::Kids.list();
+        assert!(list.choose(&filter, &mut rand::thread_rng()).is_none());
+    }
+
+    #[test]
+    fn test_filter_category() {
+        let list = WordList::from_csv(
+            "elephant,Large,Animals\ncarrot,,vegetables\nzebra,,animals\nchalk\n".as_bytes(),
+        )
+        .unwrap();
+        let filter = WordFilter {
+            category: Some(String::from("animals")),
+            ..WordFilter::default()
+        };
+        let mut chosen = list
+            .iter()
+            .filter(|w| filter.accepts(w))
+            .map(|w| w.word.as_ref())
+            .collect::<Vec<_>>();
+        chosen.sort_unstable();
+        assert_eq!(chosen, ["elephant", "zebra"]);
+    }
+
+    #[test]
+    fn test_filter_difficulty() {
+        let list = WordList::from_csv(
+            "cat,,,easy\nquixotic,,,hard\nzephyr,Wind,,hard\nanteater\njazz\n".as_bytes(),
+        )
+        .unwrap();
+        let filter = WordFilter {
+            difficulty: Some(WordDifficulty::Hard),
+            ..WordFilter::default()
+        };
+        let mut chosen = list
+            .iter()
+            .filter(|w| filter.accepts(w))
+            .map(|w| w.word.as_ref())
+            .collect::<Vec<_>>();
+        chosen.sort_unstable();
+        assert_eq!(chosen, ["jazz", "quixotic", "zephyr"]);
+    }
+
+    #[test]
+    fn test_estimate_difficulty() {
+        for (word, difficulty) in [
+            ("mississippi", WordDifficulty::Easy),
+            ("anteater", WordDifficulty::Easy),
+            ("carrot", WordDifficulty::Medium),
+            ("banana", WordDifficulty::Easy),
+            ("ice cream", WordDifficulty::Easy),
+            ("jazz", WordDifficulty::Hard),
+            ("fox", WordDifficulty::Hard),
+            ("123", WordDifficulty::Easy),
+        ] {
+            let word = word.parse::<Word>().unwrap();
+            assert_eq!(WordDifficulty::estimate(&word), difficulty, "{word:?}");
+        }
+    }
+
+    #[test]
+    fn test_filter_pattern() {
+        let list = WordList::from_plain("Quiz\naqua\nQuest\nbanana\nballoon\n").unwrap();
+        let accepted = |pattern: &str| {
+            let filter = WordFilter {
+                pattern: Some(pattern.parse().unwrap()),
+                ..WordFilter::default()
+            };
+            list.iter()
+                .filter(|w| filter.accepts(w))
+                .map(|w| w.word.as_ref())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(accepted("^q"), ["Quiz", "Quest"]);
+        assert_eq!(accepted("ll|oo"), ["balloon"]);
+    }
+
+    #[cfg(feature = "words-url")]
+    #[test]
+    fn test_from_url() {
+        use crate::testutil::serve;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/words.txt", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            serve(
+                &listener,
+                "200 OK",
+                "",
+                "# Shared list\nelephant\tLarge\ngiraffe\n",
+            )
+        });
+        let list = WordList::from_url(&url, ListOptions::default()).unwrap();
+        server.join().unwrap();
+        assert_eq!(
+            list.iter()
+                .map(|w| (w.word.as_ref(), w.hint.as_deref()))
+                .collect::<Vec<_>>(),
+            [("elephant", Some("Large")), ("giraffe", None)]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_command() {
+        let list = WordList::from_command(
+            r"printf 'elephant\tLarge\ngiraffe\n'",
+            ListOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            list.iter()
+                .map(|w| (w.word.as_ref(), w.hint.as_deref()))
+                .collect::<Vec<_>>(),
+            [("elephant", Some("Large")), ("giraffe", None)]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_failed_command() {
+        assert!(WordList::from_command("echo cat; exit 1", ListOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_word_of_the_day() {
+        use time::Month;
+        let list = Pool::Standard.list();
+        let pi_day = Date::from_calendar_date(2024, Month::March, 14).unwrap();
+        let next_day = pi_day.next_day().unwrap();
+        let word = list.word_of_the_day(pi_day).unwrap();
+        assert_eq!(list.word_of_the_day(pi_day), Some(word));
+        assert_ne!(list.word_of_the_day(next_day), Some(word));
+        assert_eq!(WordList::default().word_of_the_day(pi_day), None);
+    }
+
+    #[test]
+    fn test_length_limit_short_word() {
+        let limit = LengthLimit {
+            max: 5,
+            policy: LongWordPolicy::Error,
+        };
+        let mut word = "apple".parse::<Word>().unwrap();
+        assert_eq!(limit.apply(&mut word), Ok(false));
+        assert_eq!(word.as_ref(), "apple");
+    }
+
+    #[test]
+    fn test_length_limit_error() {
+        let limit = LengthLimit {
+            max: 5,
+            policy: LongWordPolicy::Error,
+        };
+        let mut word = "bananas".parse::<Word>().unwrap();
+        assert_eq!(
+            limit.apply(&mut word),
+            Err(WordTooLongError { length: 7, max: 5 })
+        );
+    }
+
+    #[test]
+    fn test_length_limit_truncate() {
+        let limit = LengthLimit {
+            max: 10,
+            policy: LongWordPolicy::Truncate,
+        };
+        let mut word = "the quick brown fox".parse::<Word>().unwrap();
+        assert_eq!(limit.apply(&mut word), Ok(true));
+        assert_eq!(word.as_ref(), "the quick");
+    }
+
+    #[test]
+    fn test_plain_list() {
+        let src = concat!(
+            "# Animals\n",
+            "elephant\tLarge, grey\tanimals\n",
+            "\n",
+            "  giraffe  \n",
+            "zebra\t\tanimals\n",
+        );
+        assert_eq!(WordFormat::Auto.resolve(src), WordFormat::Plain);
+        let list = WordList::from_plain(src).unwrap();
+        assert_eq!(
+            list.iter()
+                .map(|w| (w.word.as_ref(), w.hint.as_deref(), w.category.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                ("elephant", Some("Large, grey"), Some("animals")),
+                ("giraffe", None, None),
+                ("zebra", None, Some("animals")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_list_empty_word() {
+        let r = WordList::from_plain("cat\n\tPet\n");
+        assert_eq!(
+            r,
+            Err(PlainLineError {
+                line: 2,
+                source: RecordError::Word(ParseWordError)
+            })
+        );
+    }
+
+    #[test]
+    fn test_plain_list_bad_difficulty() {
+        let r = WordList::from_plain("cat\tPet\tanimals\tmedium\ndog\t\t\tsimple\n");
+        assert_eq!(
+            r,
+            Err(PlainLineError {
+                line: 2,
+                source: RecordError::Difficulty(ParseWordDifficultyError)
+            })
+        );
+    }
+
+    #[test]
+    fn test_toml_list() {
+        let src = concat!(
+            "# Hand-picked animals\n",
+            "[[words]]\n",
+            "word = \"elephant\"\n",
+            "hint = \"Large, grey\"\n",
+            "category = \"animals\"\n",
+            "\n",
+            "[[words]]\n",
+            "word = \"giraffe\"\n",
+        );
+        assert_eq!(WordFormat::Auto.resolve(src), WordFormat::Toml);
+        let list = WordList::from_toml(src).unwrap();
+        assert_eq!(
+            list.iter()
+                .map(|w| (w.word.as_ref(), w.hint.as_deref()))
+                .collect::<Vec<_>>(),
+            [("elephant", Some("Large, grey")), ("giraffe", None)]
+        );
+    }
+
+    #[test]
+    fn test_toml_list_empty_word() {
+        let r = WordList::from_toml("[[words]]\nword = \"  \"\n");
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_detect_csv() {
+        assert_eq!(
+            WordFormat::Auto.resolve("elephant,Animal\ncarrot\n"),
+            WordFormat::Csv
+        );
+        assert_eq!(WordFormat::Plain.resolve("a,b\n"), WordFormat::Plain);
+    }
+
     #[test]
     fn test_nonempty_builtin_list() {
-        let builtins = iter_words(WORDS);
-        assert!(builtins.count() > 0);
+        assert!(!Pool::Standard.entries().is_empty());
     }
 
     #[test]
     fn test_builtin_list_ok() {
-        let mut builtins = iter_words(WORDS);
-        assert!(builtins.all(|r| r.is_ok()));
+        let list = WordList::from_csv(&include_bytes!("words.csv")[..]).unwrap();
+        assert_eq!(Pool::Standard.list(), list);
+    }
+
+    #[test]
+    fn test_builtin_themes_ok() {
+        for pool in Pool::ALL {
+            assert!(!pool.entries().is_empty(), "{} list is empty", pool.name());
+            assert_eq!(pool.name().parse::<Pool>(), Ok(pool));
+        }
+    }
+
+    #[test]
+    fn test_builtin_kids_list_ok() {
+        let list = WordList::from_csv(&include_bytes!("kids.csv")[..]).unwrap();
+        assert_eq!(Pool::Kids.list(), list);
+    }
+
+    #[test]
+    fn test_builtin_sample() {
+        use rand::SeedableRng;
+        let builtin = BuiltinList {
+            pool: Pool::Standard,
+            locale: Locale::EnGb,
+        };
+        let filter = WordFilter {
+            max_length: Some(0),
+            ..WordFilter::default()
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(builtin.sample(&filter, &mut rng), None);
+        let filter = WordFilter {
+            min_length: Some(3),
+            ..WordFilter::default()
+        };
+        let entry = builtin.sample(&filter, &mut rng).unwrap();
+        assert!(builtin.list().iter().any(|w| *w == entry));
+    }
+
+    #[test]
+    fn test_builtin_fetch_lazy() {
+        use rand::SeedableRng;
+        let builtin = BuiltinList {
+            pool: Pool::Standard,
+            locale: Locale::EnUs,
+        };
+        let fetched = WordSource::Builtin(builtin)
+            .fetch(&WordFilter::default(), StdRng::seed_from_u64(0))
+            .unwrap();
+        let list = fetched.list.unwrap();
+        // The list isn't built until it's asked for
+        assert!(list.list.get().is_none());
+        assert!(list.get().iter().any(|w| *w == fetched.secret));
     }
 }
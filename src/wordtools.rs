@@ -0,0 +1,261 @@
+use crate::model::{normalize_char, ASCII_ALPHABET};
+use crate::words::{ListOptions, Pool, RecordError, WordFormat, WordList, WordWithHint};
+use anyhow::Context;
+use patharg::{InputArg, OutputArg};
+use std::collections::HashMap;
+use std::fmt;
+
+/// An operation for tidying up word lists
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ListEdit {
+    /// Remove duplicate words, merging their hints, categories, and
+    /// difficulties, and otherwise keep the words in their original order
+    Dedupe,
+    /// Combine the lists into one sorted list without duplicates
+    Merge,
+    /// Sort the words alphabetically, ignoring case
+    Sort,
+}
+
+/// Read the given word lists, concatenate them, apply `op` to the result, and
+/// write it to `outfile` in the format `output_format`
+pub(crate) fn edit(
+    op: ListEdit,
+    files: Vec<InputArg>,
+    format: WordFormat,
+    outfile: &OutputArg,
+    output_format: WordFormat,
+) -> anyhow::Result<()> {
+    let mut words = Vec::new();
+    for infile in files {
+        let list = WordList::from_file(
+            infile,
+            ListOptions {
+                format,
+                ..ListOptions::default()
+            },
+        )?;
+        words.extend(list.iter().cloned());
+    }
+    if matches!(op, ListEdit::Dedupe | ListEdit::Merge) {
+        words = dedupe(words);
+    }
+    if matches!(op, ListEdit::Sort | ListEdit::Merge) {
+        words.sort_by_cached_key(|w| w.word.as_ref().to_lowercase());
+    }
+    let out = WordList::from_iter(words).render(output_format)?;
+    outfile
+        .write(out)
+        .with_context(|| format!("failed to write {outfile:#}"))
+}
+
+/// Remove entries whose words (ignoring case) duplicate earlier ones.  Any
+/// fields missing from the first entry for a word are filled in from the
+/// duplicates.
+fn dedupe(words: Vec<WordWithHint>) -> Vec<WordWithHint> {
+    let mut deduped: Vec<WordWithHint> = Vec::with_capacity(words.len());
+    let mut index = HashMap::<String, usize>::new();
+    for w in words {
+        let key = normalized(&w);
+        if let Some(&i) = index.get(&key) {
+            let first = &mut deduped[i];
+            first.hint = first.hint.take().or(w.hint);
+            first.category = first.category.take().or(w.category);
+            first.difficulty = first.difficulty.or(w.difficulty);
+            first.part_of_speech = first.part_of_speech.take().or(w.part_of_speech);
+            if first.more_hints.is_empty() {
+                first.more_hints = w.more_hints;
+            }
+        } else {
+            index.insert(key, deduped.len());
+            deduped.push(w);
+        }
+    }
+    deduped
+}
+
+/// Returns `entry`'s word normalized for comparison
+fn normalized(entry: &WordWithHint) -> String {
+    entry.word.as_ref().chars().map(normalize_char).collect()
+}
+
+/// Print the names & descriptions of the builtin word lists
+pub(crate) fn themes() {
+    for pool in Pool::ALL {
+        let count = pool.list().iter().count();
+        println!("{:<10} {} ({count} words)", pool.name(), pool.description());
+    }
+}
+
+/// Check the given word lists for problems, printing each one found along
+/// with its file and line number.  Returns `Err` if any problems were found.
+pub(crate) fn check(files: Vec<InputArg>, format: WordFormat) -> anyhow::Result<()> {
+    let mut total = 0;
+    for infile in files {
+        let src = infile
+            .read_to_string()
+            .with_context(|| format!("failed to read {infile:#}"))?;
+        for (line, problem) in problems(&src, format) {
+            println!("{infile:#}:{line}: {problem}");
+            total += 1;
+        }
+    }
+    if total > 0 {
+        anyhow::bail!(
+            "found {total} problem{} in word lists",
+            if total == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
+
+/// Returns the problems in the word list `src` along with the lines on which
+/// they occur
+fn problems(src: &str, format: WordFormat) -> Vec<(usize, Problem)> {
+    let mut problems = Vec::new();
+    let mut seen = HashMap::new();
+    for record in WordList::records(src, format) {
+        let entry = match record.entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                problems.push((record.line, Problem::Invalid(e)));
+                continue;
+            }
+        };
+        problems.extend(
+            alphabet_problem(&entry)
+                .into_iter()
+                .map(|p| (record.line, p)),
+        );
+        let key = normalized(&entry);
+        if let Some(&first) = seen.get(&key) {
+            problems.push((record.line, Problem::Duplicate { first }));
+        } else {
+            seen.insert(key, record.line);
+        }
+    }
+    problems
+}
+
+/// Returns the problem with the characters in `entry`'s word, if any
+fn alphabet_problem(entry: &WordWithHint) -> Option<Problem> {
+    let word = entry.word.as_ref();
+    let guessable = |c: char| ASCII_ALPHABET.contains(normalize_char(c));
+    let mut unguessable = word
+        .chars()
+        .filter(|&c| c.is_alphabetic() && !guessable(c))
+        .collect::<Vec<_>>();
+    unguessable.dedup();
+    if !entry.word.has_letters_in(ASCII_ALPHABET) {
+        Some(Problem::NoLetters)
+    } else if !unguessable.is_empty() {
+        Some(Problem::Unguessable(unguessable))
+    } else {
+        None
+    }
+}
+
+/// A problem found in a word list entry
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Problem {
+    /// The entry could not be parsed
+    Invalid(RecordError),
+    /// The word has no letters that can be guessed
+    NoLetters,
+    /// The word contains letters outside the alphabet, which would be
+    /// revealed from the start rather than guessed
+    Unguessable(Vec<char>),
+    /// The word already appeared (ignoring case) on line `first`
+    Duplicate { first: usize },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Invalid(e) => write!(f, "{e}"),
+            Problem::NoLetters => write!(f, "word has no letters to guess"),
+            Problem::Unguessable(chars) => {
+                write!(f, "word contains letters that cannot be guessed: ")?;
+                for (i, c) in chars.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{c}")?;
+                }
+                Ok(())
+            }
+            Problem::Duplicate { first } => write!(f, "duplicate of word on line {first}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::ParseWordError;
+
+    #[test]
+    fn test_csv_problems() {
+        let src = "cat,Pet\n\"\",Nothing\nCAT\ncafé\n123\ndog,,,tough\n";
+        let found = problems(src, WordFormat::Csv)
+            .into_iter()
+            .map(|(line, p)| (line, p.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                (2, ParseWordError.to_string()),
+                (3, String::from("duplicate of word on line 1")),
+                (
+                    4,
+                    String::from("word contains letters that cannot be guessed: é")
+                ),
+                (5, String::from("word has no letters to guess")),
+                (
+                    6,
+                    String::from(r#"word difficulty must be "easy", "medium", or "hard""#)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_problems() {
+        let src = "# Animals\ncat\tPet\n\n\tNo word\ndog\n";
+        assert_eq!(
+            problems(src, WordFormat::Plain),
+            [(4, Problem::Invalid(RecordError::Word(ParseWordError)))]
+        );
+    }
+
+    #[test]
+    fn test_toml_problems() {
+        let src = "[[words]]\nword = \"cat\"\n\n[[words]]\nword = \"\"\n";
+        let found = problems(src, WordFormat::Toml);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 4);
+        let found = problems("[[words]]\nword = \"cat\"\nhint = \n", WordFormat::Toml);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 3);
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let list = WordList::parse(
+            "cat\nDog,Barks\nCAT,Meows,pets\ndog,Woofs,pets,easy\nbird\n",
+            ListOptions::default(),
+        )
+        .unwrap();
+        let deduped = WordList::from_iter(dedupe(list.iter().cloned().collect()));
+        assert_eq!(
+            deduped.render(WordFormat::Csv).unwrap(),
+            "cat,Meows,pets\nDog,Barks,pets,easy\nbird\n"
+        );
+    }
+
+    #[test]
+    fn test_no_problems() {
+        let src = "elephant,Large\ngiraffe\n\"ice cream\",Dessert\n";
+        assert_eq!(problems(src, WordFormat::Csv), []);
+    }
+}
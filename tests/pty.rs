@@ -0,0 +1,932 @@
+#![cfg(feature = "test-harness")]
+use hangman_harness::{Session, SessionBuilder, DEFAULT_TIMEOUT};
+use tempfile::TempDir;
+
+/// Spawn `hangman` with the given arguments, with its configuration & state
+/// files stored in a temporary directory
+fn spawn(args: &[&str]) -> (Session, TempDir) {
+    let tmpdir = tempfile::tempdir().expect("creating a tempdir should not fail");
+    let session = SessionBuilder::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .spawn()
+        .expect("spawning hangman should not fail");
+    (session, tmpdir)
+}
+
+#[test]
+fn win_game() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "abacus"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("a").unwrap();
+    let frame = session
+        .wait_for_text("Correct!  There are 2 'A's in the word.")
+        .unwrap();
+    assert!(frame.contains("a _ a _ _ _"));
+    session.send_keys("bcus").unwrap();
+    let frame = session.wait_for_text("Press the Any Key to exit.").unwrap();
+    assert!(frame.contains("Nicely solved!"));
+    assert!(frame.contains("a b a c u s"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn prompt_for_word() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "-"]);
+    session
+        .wait_for_text("Enter the secret word (it won't be shown):")
+        .unwrap();
+    session.send_keys("   \r").unwrap();
+    session
+        .wait_for_text("hangman words cannot be empty or all-whitespace")
+        .unwrap();
+    session.send_keys("cax\x7ft\r").unwrap();
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("_ _ _"));
+    session.send_keys("cat").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn word_from_file() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("secret.txt");
+    std::fs::write(&path, "  cat  \nignored\n").unwrap();
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word-file", path.to_str().unwrap()]);
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("_ _ _"));
+    session.send_keys("cat").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn word_from_env() {
+    let tmpdir = tempfile::tempdir().expect("creating a tempdir should not fail");
+    let mut session = SessionBuilder::new(env!("CARGO_BIN_EXE_hangman"))
+        .arg("--seed")
+        .arg("1")
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .env("HANGMAN_WORD", "cat")
+        .env("HANGMAN_WORDS_FILE", "/nonexistent")
+        .spawn()
+        .unwrap();
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("_ _ _"));
+    session.send_keys("cat").unwrap();
+    let frame = session.wait_for_text("Well done, you got it!").unwrap();
+    assert!(frame.contains("c a t"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn wordlist_overrides_env() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["--wordlist", "animals", "--no-tui", "--reveal-on-quit"])
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .env("HANGMAN_WORD", "zzz")
+        .env("HANGMAN_WORDS_FILE", "/nonexistent")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("The word was: "));
+    assert!(!stdout.contains("The word was: zzz"));
+}
+
+#[test]
+fn lose_game() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "abacus"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("e").unwrap();
+    let frame = session
+        .wait_for_text("Wrong!  There's no 'E' in the word.")
+        .unwrap();
+    // The sidebar lists each guess with when it was made
+    assert!(frame.contains("Guesses"));
+    assert!(frame.contains("✗ E  0:0"));
+    session.send_keys("dfghi").unwrap();
+    let frame = session.wait_for_text("Oh dear, you are dead!").unwrap();
+    assert!(frame.contains("a b a c u s"));
+    assert!(frame.contains("Wrong: E, D, F, G, H, I"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn hard_difficulty() {
+    let (mut session, _tmpdir) =
+        spawn(&["--seed", "1", "--difficulty", "hard", "--word", "abacus"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("defg").unwrap();
+    session
+        .wait_for_text("Game over.  Better luck next time!")
+        .unwrap();
+}
+
+#[test]
+fn quit_with_escape() {
+    let (mut session, _tmpdir) = spawn(&["--word", "abacus"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_escape().unwrap();
+    let frame = session.wait_for_text("Paused").unwrap();
+    assert!(frame.contains("Resume"));
+    // A word given on the command line can't be swapped for another
+    assert!(!frame.contains("New word"));
+    session.send_keys("q").unwrap();
+    session.wait_for_text("End this game? (y/n)").unwrap();
+    session.send_keys("y").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn reveal_on_quit() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--reveal-on-quit", "--word", "tea"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_bytes(b"\x03").unwrap();
+    session.wait_for_text("End this game? (y/n)").unwrap();
+    // Declining goes back to the game
+    session.send_keys("n").unwrap();
+    session.send_keys("t").unwrap();
+    let frame = session
+        .wait_for_text("Correct!  There is 1 'T' in the word.")
+        .unwrap();
+    assert!(!frame.contains("End this game?"));
+    session.send_bytes(b"\x03").unwrap();
+    session.wait_for_text("End this game? (y/n)").unwrap();
+    session.send_keys("y").unwrap();
+    session.wait_for_text("The word was: tea").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn pause_menu_new_word() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat\n").unwrap();
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "-f", path.to_str().unwrap()]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("c").unwrap();
+    session.wait_for_text("c _ _").unwrap();
+    session.send_escape().unwrap();
+    session.wait_for_text("New word").unwrap();
+    // Escape backs out of the options submenu & then resumes the game
+    session.send_keys("o").unwrap();
+    session.wait_for_text("Large letters").unwrap();
+    session.send_escape().unwrap();
+    session.wait_for_text("Resume").unwrap();
+    session.send_keys("n").unwrap();
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("_ _ _"));
+    session.send_keys("cat").unwrap();
+    session.wait_for_text("Nicely solved!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn pause_on_focus_loss() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "cat", "--hide-unfocused"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("c").unwrap();
+    session.wait_for_text("c _ _").unwrap();
+    // Focus-out & focus-in reports
+    session.send_bytes(b"\x1B[O").unwrap();
+    let frame = session
+        .wait_for_text("Return to this window to resume.")
+        .unwrap();
+    assert!(!frame.contains("c _ _"));
+    session.send_bytes(b"\x1B[I").unwrap();
+    session
+        .wait_for_frame(DEFAULT_TIMEOUT, |frame| {
+            !frame.contains("Return to this window") && frame.contains("c _ _")
+        })
+        .unwrap();
+    session.send_keys("at").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn suspend_and_resume() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "cat"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("c").unwrap();
+    session.wait_for_text("c _ _").unwrap();
+    assert!(session.in_alternate_screen());
+    let pid = session.process_id().unwrap().to_string();
+    let kill = |signal: &str| {
+        let status = std::process::Command::new("kill")
+            .arg(signal)
+            .arg(&pid)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    // Suspend once with Ctrl-Z and once with a signal from elsewhere
+    for by_key in [true, false] {
+        if by_key {
+            session.send_bytes(b"\x1A").unwrap();
+        } else {
+            kill("-TSTP");
+        }
+        let deadline = std::time::Instant::now() + DEFAULT_TIMEOUT;
+        while session.in_alternate_screen() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "terminal was not restored"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        kill("-CONT");
+        session.wait_for_text("c _ _").unwrap();
+        assert!(session.in_alternate_screen());
+    }
+    session.send_keys("at").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn terminate_restores_terminal() {
+    for signal in ["-TERM", "-HUP"] {
+        let (mut session, _tmpdir) = spawn(&["--word", "cat"]);
+        session
+            .wait_for_text("Try to guess the secret word!")
+            .unwrap();
+        // Open the pause menu to check that signals are noticed there too
+        session.send_escape().unwrap();
+        session.wait_for_text("Resume").unwrap();
+        let pid = session.process_id().unwrap().to_string();
+        let status = std::process::Command::new("kill")
+            .arg(signal)
+            .arg(&pid)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert!(!session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+        assert!(!session.in_alternate_screen());
+    }
+}
+
+#[test]
+fn no_terminal() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["--word", "cat"])
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("hangman requires an interactive terminal; see --no-tui"));
+}
+
+#[test]
+fn daily_ignores_difficulty() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let reveal = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+            .args(["--daily", "--no-tui", "--reveal-on-quit"])
+            .args(args)
+            .env("XDG_CONFIG_HOME", tmpdir.path())
+            .env("XDG_DATA_HOME", tmpdir.path())
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        stdout
+            .lines()
+            .find(|line| line.starts_with("The word was: "))
+            .map(String::from)
+            .unwrap()
+    };
+    let word = reveal(&[]);
+    assert_eq!(reveal(&["--difficulty", "easy"]), word);
+    assert_eq!(reveal(&["--kids"]), word);
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["--daily", "--wordlist", "animals"])
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be combined with --word-of-the-day"));
+}
+
+#[test]
+fn no_tui() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["--no-tui", "--word", "cat"])
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    {
+        use std::io::Write;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(b"c\nxy\n\nat\n").unwrap();
+    }
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Word: _ _ _\n"));
+    assert!(stdout.contains("Correct!  There is 1 'C' in the word.\n"));
+    assert!(stdout.contains("Misses: X Y\nMisses left: 4\n"));
+    assert!(stdout.contains("Word: c a t\n"));
+    assert!(stdout.contains("You win!\nGuesses: 5    Misses: 2\n"));
+}
+
+#[test]
+fn no_tui_word_list_from_stdin() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut cases = vec![
+        &["-f", "-", "--no-tui"][..],
+        &["--word-file", "-", "--no-tui"],
+    ];
+    if cfg!(unix) {
+        cases.push(&["--word-fd", "0", "--no-tui"]);
+    }
+    for args in cases {
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+            .args(args)
+            .env("XDG_CONFIG_HOME", tmpdir.path())
+            .env("XDG_DATA_HOME", tmpdir.path())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        {
+            use std::io::Write;
+            let mut stdin = child.stdin.take().unwrap();
+            // The input may be rejected before it's read, so ignore errors
+            let _ = stdin.write_all(b"cat\ndog\n");
+        }
+        let output = child.wait_with_output().unwrap();
+        assert!(!output.status.success(), "{args:?}");
+        assert!(output.stdout.is_empty(), "{args:?}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.contains("cannot be combined with reading the word list"),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn accessible() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["--accessible", "--word", "cat"])
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    {
+        use std::io::Write;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(b"e\nct\n").unwrap();
+    }
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(
+        "Try to guess the secret word! The word has 3 characters. 6 misses remaining. Revealed: blank blank blank.\n"
+    ));
+    assert!(stdout.contains(
+        "Wrong!  There's no 'E' in the word. 5 misses remaining. Revealed: blank blank blank.\nWrong guesses so far: E.\n"
+    ));
+    assert!(stdout.contains(
+        "Correct!  There is 1 'T' in the word. 5 misses remaining. Revealed: C blank T.\n"
+    ));
+    assert!(!stdout.contains('┌'));
+}
+
+#[test]
+fn paste_word() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "abacus"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_bytes(b"\x1B[200~abacas\x1B[201~").unwrap();
+    let frame = session
+        .wait_for_text("Wrong!  That's not the word.")
+        .unwrap();
+    assert!(frame.contains("_ _ _ _ _ _"));
+    session.send_bytes(b"\x1B[200~Abacus\n\x1B[201~").unwrap();
+    let frame = session.wait_for_text("You win!").unwrap();
+    assert!(frame.contains("a b a c u s"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn command_mode() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat\n").unwrap();
+    let tmpdir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmpdir.path().join("hangman")).unwrap();
+    std::fs::write(
+        tmpdir.path().join("hangman").join("config.toml"),
+        "[themes.dark]\nbase = \"vivid\"\n",
+    )
+    .unwrap();
+    let mut session = SessionBuilder::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["-f", path.to_str().unwrap()])
+        .env("XDG_CONFIG_HOME", tmpdir.path())
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .spawn()
+        .unwrap();
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("c").unwrap();
+    session.wait_for_text("c _ _").unwrap();
+    session.send_keys(":nwe").unwrap();
+    session.wait_for_text(":nwe").unwrap();
+    session.send_keys("\x7F\x7Few\r").unwrap();
+    let frame = session.wait_for_text("New word drawn").unwrap();
+    assert!(frame.contains("_ _ _"));
+    session.send_keys(":theme vivid\r").unwrap();
+    session.wait_for_text("Theme: vivid").unwrap();
+    // Themes from the configuration file can be switched to as well
+    session.send_keys(":theme dark\r").unwrap();
+    session.wait_for_text("Theme: dark").unwrap();
+    session.send_keys(":theme murky\r").unwrap();
+    session.wait_for_text("Unknown theme \"murky\"").unwrap();
+    session.send_keys(":frobnicate\r").unwrap();
+    session.wait_for_text("Not a command: frobnicate").unwrap();
+    // Escape leaves command mode without opening the pause menu
+    session.send_keys(":q").unwrap();
+    session.wait_for_text(":q").unwrap();
+    session.send_escape().unwrap();
+    session
+        .wait_for_frame(DEFAULT_TIMEOUT, |frame| !frame.contains(":q"))
+        .unwrap();
+    session.send_keys("a").unwrap();
+    let frame = session.wait_for_text("_ a _").unwrap();
+    assert!(!frame.contains("Paused"));
+    session.send_keys(":q\r").unwrap();
+    session.wait_for_text("End this game? (y/n)").unwrap();
+    session.send_keys("y").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn toast_on_toggle() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "cat"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("\t").unwrap();
+    session.wait_for_text("Assist mode on").unwrap();
+    // The notification goes away on its own after a few seconds
+    let frame = session.wait_for_text("Try to guess").unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(4));
+    session.send_keys("c").unwrap();
+    let frame2 = session.wait_for_text("c _ _").unwrap();
+    assert!(frame.contains("Assist mode on"));
+    assert!(!frame2.contains("Assist mode on"));
+    session.send_keys("at").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn record_streak() {
+    let (mut session, tmpdir) = spawn(&["--seed", "1", "--word", "cat"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("cat").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+    let mut status = SessionBuilder::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["status", "--porcelain"])
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .spawn()
+        .unwrap();
+    status.wait_for_text("streak:1 daily:todo").unwrap();
+    assert!(status.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn endless() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat\n").unwrap();
+    let (mut session, tmpdir) = spawn(&["--seed", "1", "--endless", "-f", path.to_str().unwrap()]);
+    session.wait_for_text("Words solved: 0").unwrap();
+    session.send_keys("xcat").unwrap();
+    session
+        .wait_for_text("Solved!  Press any key for the next word.")
+        .unwrap();
+    session.send_keys(" ").unwrap();
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("Words solved: 1"));
+    assert!(frame.contains("_ _ _"));
+    assert!(frame.contains("  │   o"));
+    session.send_keys("defgh").unwrap();
+    session.wait_for_text("Oh dear, you are dead!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+    let mut status = SessionBuilder::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["status", "--porcelain"])
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .spawn()
+        .unwrap();
+    status.wait_for_text("streak:0 daily:todo").unwrap();
+    assert!(status.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn suspend_at_next_word_prompt() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat\n").unwrap();
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--endless", "-f", path.to_str().unwrap()]);
+    session.wait_for_text("Words solved: 0").unwrap();
+    session.send_keys("cat").unwrap();
+    session
+        .wait_for_text("Solved!  Press any key for the next word.")
+        .unwrap();
+    let pid = session.process_id().unwrap().to_string();
+    let kill = |signal: &str| {
+        let status = std::process::Command::new("kill")
+            .arg(signal)
+            .arg(&pid)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    kill("-TSTP");
+    let deadline = std::time::Instant::now() + DEFAULT_TIMEOUT;
+    while session.in_alternate_screen() {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "terminal was not restored"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    kill("-CONT");
+    // Still waiting at the prompt rather than moving on to the next word
+    let frame = session
+        .wait_for_text("Solved!  Press any key for the next word.")
+        .unwrap();
+    assert!(frame.contains("Words solved: 1"));
+    assert!(session.in_alternate_screen());
+    session.send_keys(" ").unwrap();
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_bytes(b"\x03").unwrap();
+    session.wait_for_text("End this game? (y/n)").unwrap();
+    session.send_keys("y").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn lightning() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat\n").unwrap();
+    let (mut session, tmpdir) = spawn(&[
+        "--seed",
+        "1",
+        "--lightning",
+        "2",
+        "-f",
+        path.to_str().unwrap(),
+    ]);
+    session.wait_for_text("Word 1 of 2").unwrap();
+    session.send_keys("xcat").unwrap();
+    session
+        .wait_for_text("Solved!  Press any key for the next word.")
+        .unwrap();
+    session.send_keys(" ").unwrap();
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("Word 2 of 2"));
+    assert!(frame.contains("  │   o"));
+    session.send_keys("cat").unwrap();
+    session.wait_for_text("Nicely solved!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+    let mut status = SessionBuilder::new(env!("CARGO_BIN_EXE_hangman"))
+        .args(["status", "--porcelain"])
+        .env("XDG_DATA_HOME", tmpdir.path())
+        .spawn()
+        .unwrap();
+    status.wait_for_text("streak:1 daily:todo").unwrap();
+    assert!(status.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn match_rounds() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat\n").unwrap();
+    let (mut session, _tmpdir) = spawn(&["--rounds", "2", "-f", path.to_str().unwrap()]);
+    session.wait_for_text("Round 1 of 2    Score: 0").unwrap();
+    session.send_keys("cat").unwrap();
+    let frame = session
+        .wait_for_text("You win this round (+60 points)!")
+        .unwrap();
+    assert!(frame.contains("Round 1 of 2    Score: 60"));
+    assert!(frame.contains("Press any key to continue."));
+    session.send_keys(" ").unwrap();
+    session.wait_for_text("Round 2 of 2    Score: 60").unwrap();
+    session.send_keys("bdefgh").unwrap();
+    session
+        .wait_for_text("Oh dear, you are dead!  No points this round.")
+        .unwrap();
+    session.send_keys(" ").unwrap();
+    let frame = session.wait_for_text("Match over!").unwrap();
+    assert!(frame.contains("Won 1 of 2 rounds for a total of 60 points"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn cycle_hints() {
+    let words = tempfile::tempdir().unwrap();
+    let path = words.path().join("words.txt");
+    std::fs::write(&path, "cat,Pet,,,noun,Meows,Purrs\n").unwrap();
+    let (mut session, _tmpdir) = spawn(&[
+        "--rounds",
+        "1",
+        "--hint-cost",
+        "15",
+        "-f",
+        path.to_str().unwrap(),
+    ]);
+    session.wait_for_text("Hint 1/3 (noun): Pet").unwrap();
+    session.send_keys("+").unwrap();
+    session.wait_for_text("Hint 2/3 (noun): Meows").unwrap();
+    session.send_keys("+").unwrap();
+    session.wait_for_text("Hint 3/3 (noun): Purrs").unwrap();
+    session.send_keys("cat").unwrap();
+    session
+        .wait_for_text("You win this round (+30 points)!")
+        .unwrap();
+    session.send_keys(" ").unwrap();
+    session.wait_for_text("Match over!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn help_overlay() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--word", "tea"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("?").unwrap();
+    let frame = session.wait_for_text("Show this help").unwrap();
+    assert!(frame.contains("You lose after 6 incorrect guesses"));
+    assert!(frame.contains("Mode: a single word"));
+    assert!(frame.contains("Word source: a word chosen in advance"));
+    // The key that dismisses the overlay isn't taken as a guess
+    session.send_keys("x").unwrap();
+    session.send_keys("t").unwrap();
+    let frame = session
+        .wait_for_text("Correct!  There is 1 'T' in the word.")
+        .unwrap();
+    assert!(!frame.contains("Show this help"));
+    assert!(!frame.contains("│   o"));
+    session.send_keys("ea").unwrap();
+    session.wait_for_text("Well done, you got it!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn coop() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--coop", "--word", "cat"]);
+    session.wait_for_text("Player 1's turn").unwrap();
+    session.send_keys("c").unwrap();
+    session.wait_for_text("Player 2's turn").unwrap();
+    session.send_keys("xat").unwrap();
+    let frame = session.wait_for_text("Nicely solved!").unwrap();
+    assert!(frame.contains("P1: 2 right, 0 wrong    P2: 1 right, 1 wrong"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn analyze_after_game() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--analyze", "--word", "tea"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    session.send_keys("ztea").unwrap();
+    session.wait_for_text("Nicely solved!").unwrap();
+    session.send_keys("x").unwrap();
+    session.wait_for_text("Wasteful guesses: 1 of 4").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn status_bar() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--status-bar", "--word", "tea"]);
+    session
+        .wait_for_text("Time: 0:00    Guesses: 0    Misses left: 6")
+        .unwrap();
+    // The clock advances without a key being pressed
+    session.wait_for_text("Time: 0:01").unwrap();
+    session.send_keys("z").unwrap();
+    session
+        .wait_for_text("Guesses: 1    Misses left: 5")
+        .unwrap();
+    session.send_keys("tea").unwrap();
+    session.wait_for_text("Nicely solved!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn timer() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--timer", "1", "--word", "tea"]);
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains(" 1s"));
+    let frame = session
+        .wait_for_text("Time's up!  That counts as a miss.")
+        .unwrap();
+    assert!(frame.contains("│   o"));
+    session.send_keys("tea").unwrap();
+    session.wait_for_text("Nicely solved!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn mouse() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--mouse", "--word", "ab"]);
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    // Find where each letter is drawn, as the 1-based coordinates used by
+    // SGR mouse reporting
+    let locate = |ch: char| {
+        let (row, line) = frame
+            .lines
+            .iter()
+            .enumerate()
+            .find(|(_, ln)| ln.contains("A B C D E F"))
+            .unwrap();
+        let base = line.chars().position(|c| c == 'A').unwrap();
+        let column = base + usize::from(u8::try_from(ch).unwrap() - b'A') * 2;
+        (column + 1, row + 1)
+    };
+    let (x, y) = locate('B');
+    session
+        .send_bytes(format!("\x1B[<35;{x};{y}M").as_bytes())
+        .unwrap();
+    session.wait_for_text("B: available").unwrap();
+    session
+        .send_bytes(format!("\x1B[<0;{x};{y}M\x1B[<0;{x};{y}m").as_bytes())
+        .unwrap();
+    let frame = session
+        .wait_for_text("Correct!  There is 1 'B' in the word.")
+        .unwrap();
+    assert!(frame.contains("B: already guessed"));
+    let (x, y) = locate('A');
+    session
+        .send_bytes(format!("\x1B[<0;{x};{y}M\x1B[<0;{x};{y}m").as_bytes())
+        .unwrap();
+    session.wait_for_text("Press the Any Key to exit.").unwrap();
+    session.send_bytes(b"\x1B[<0;1;1M").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn cursor_keys() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--cursor-keys", "--word", "ac"]);
+    session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    // The cursor starts on A, and moves on to B once A is guessed
+    session.send_keys("\r").unwrap();
+    session
+        .wait_for_text("Correct!  There is 1 'A' in the word.")
+        .unwrap();
+    session.send_keys("\x1B[C\r").unwrap();
+    session.wait_for_text("Press the Any Key to exit.").unwrap();
+    session.send_keys("\r").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn hide_panels() {
+    let (mut session, _tmpdir) =
+        spawn(&["--word", "cat", "--hide", "gallows", "--hide", "message"]);
+    let frame = session.wait_for_text("_ _ _").unwrap();
+    assert!(frame.contains("A B C D E F"));
+    assert!(!frame.contains("┌"));
+    assert!(!frame.contains("Try to guess the secret word!"));
+    session.send_keys("e").unwrap();
+    let frame = session.wait_for_text("Wrong: E").unwrap();
+    assert!(!frame.contains("Wrong!"));
+    session.send_keys("cat").unwrap();
+    session.wait_for_text("Press the Any Key to exit.").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn duel() {
+    let (mut session, _tmpdir) = spawn(&["--seed", "1", "--duel", "--word", "zzz"]);
+    let frame = session
+        .wait_for_text("Try to guess the secret word!")
+        .unwrap();
+    assert!(frame.contains("Computer"));
+    session.send_keys("q").unwrap();
+    session
+        .wait_for_text("You: 'Q' missed.  Computer: 'E' missed.")
+        .unwrap();
+    session.send_keys("z").unwrap();
+    let frame = session.wait_for_text("You win!").unwrap();
+    assert!(frame.contains("z z z"));
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}
+
+#[test]
+fn reverse() {
+    let (mut session, _tmpdir) = spawn(&["--reverse"]);
+    session
+        .wait_for_text("How many letters does it have?")
+        .unwrap();
+    session.send_keys("0\r").unwrap();
+    session
+        .wait_for_text("Please enter a number of letters from 1 to 40.")
+        .unwrap();
+    session.send_keys("1\r").unwrap();
+    session.wait_for_text("in your word?").unwrap();
+    session.send_keys("2\r").unwrap();
+    session
+        .wait_for_text("Sorry, position 2 is outside the word (1 to 1).")
+        .unwrap();
+    session.send_keys("1\r").unwrap();
+    session.wait_for_text("I guessed your word!").unwrap();
+    session.send_keys("x").unwrap();
+    assert!(session.wait_for_exit(DEFAULT_TIMEOUT).unwrap());
+}